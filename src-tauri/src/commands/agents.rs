@@ -340,6 +340,9 @@ pub fn init_database(app: &AppHandle) -> SqliteResult<Connection> {
         [],
     )?;
 
+    // Bring app-specific tables up to the current schema version
+    super::migrations::run_migrations(&conn)?;
+
     Ok(conn)
 }
 
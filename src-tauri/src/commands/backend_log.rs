@@ -0,0 +1,167 @@
+use log::{LevelFilter, Log, Metadata, Record};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock};
+use tauri::State;
+use super::agents::AgentDb;
+
+/// Entries kept in memory for `get_backend_logs`
+const LOG_BUFFER_LIMIT: usize = 2000;
+const DEFAULT_LOG_LEVEL: LevelFilter = LevelFilter::Warn;
+const LOG_LEVEL_SETTING: &str = "backend_log_level";
+/// Target prefix of this crate's records (the binary and `queen_code_lib`); only
+/// these are kept for the UI so dependency chatter doesn't push them out
+const APP_TARGET_PREFIX: &str = "queen_code";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackendLogEntry {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub level: String,
+    /// Module that logged it, e.g. `queen_code::commands::servers`
+    pub target: String,
+    pub message: String,
+}
+
+/// Keeps recent records for the UI and still writes them to stderr through
+/// env_logger; which records get through is decided by `log::max_level`
+struct BackendLogger {
+    stderr: env_logger::Logger,
+    /// Level an explicit `RUST_LOG` asks for, which the saved level never lowers
+    env_level: Option<LevelFilter>,
+    recent: Mutex<VecDeque<BackendLogEntry>>,
+}
+
+impl Log for BackendLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        if record.target().starts_with(APP_TARGET_PREFIX) {
+            self.remember(record);
+        }
+
+        if self.stderr.matches(record) {
+            self.stderr.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.stderr.flush();
+    }
+}
+
+impl BackendLogger {
+    fn remember(&self, record: &Record) {
+        let Ok(mut recent) = self.recent.lock() else {
+            return;
+        };
+        if recent.len() >= LOG_BUFFER_LIMIT {
+            recent.pop_front();
+        }
+        recent.push_back(BackendLogEntry {
+            timestamp: chrono::Utc::now(),
+            level: record.level().as_str().to_lowercase(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        });
+    }
+}
+
+static LOGGER: OnceLock<BackendLogger> = OnceLock::new();
+
+/// Installs the logger at warn level, or higher if `RUST_LOG` asks for more.
+/// `RUST_LOG` still narrows what reaches stderr.
+pub fn init() {
+    let stderr = env_logger::Builder::new()
+        .filter_level(LevelFilter::Trace)
+        .parse_default_env()
+        .build();
+    let env_level = std::env::var_os("RUST_LOG")
+        .map(|_| env_logger::Builder::from_default_env().build().filter());
+    let logger = LOGGER.get_or_init(|| BackendLogger {
+        stderr,
+        env_level,
+        recent: Mutex::new(VecDeque::new()),
+    });
+
+    if log::set_logger(logger).is_ok() {
+        apply_level(DEFAULT_LOG_LEVEL);
+    }
+}
+
+/// Sets the max level, keeping at least what `RUST_LOG` asked for
+fn apply_level(level: LevelFilter) {
+    let env_level = LOGGER.get().and_then(|logger| logger.env_level);
+    log::set_max_level(env_level.map_or(level, |env_level| level.max(env_level)));
+}
+
+fn parse_level(level: &str) -> Result<LevelFilter, String> {
+    match level.trim().to_lowercase().as_str() {
+        "off" => Ok(LevelFilter::Off),
+        "error" => Ok(LevelFilter::Error),
+        "warn" | "warning" => Ok(LevelFilter::Warn),
+        "info" => Ok(LevelFilter::Info),
+        "debug" => Ok(LevelFilter::Debug),
+        "trace" => Ok(LevelFilter::Trace),
+        other => Err(format!(
+            "Unknown log level '{}'; use off, error, warn, info, debug or trace",
+            other
+        )),
+    }
+}
+
+/// Applies the level saved by `set_backend_log_level`, if any
+pub fn load_backend_log_level(db: &AgentDb) -> Result<(), String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let saved = conn
+        .query_row(
+            "SELECT value FROM app_settings WHERE key = ?1",
+            rusqlite::params![LOG_LEVEL_SETTING],
+            |row| row.get::<_, String>(0),
+        )
+        .ok();
+
+    if let Some(level) = saved {
+        apply_level(parse_level(&level)?);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_backend_log_level() -> String {
+    log::max_level().as_str().to_lowercase()
+}
+
+/// Changes how much the backend logs, immediately and for later launches
+#[tauri::command]
+pub fn set_backend_log_level(level: String, db: State<'_, AgentDb>) -> Result<String, String> {
+    let filter = parse_level(&level)?;
+    let name = filter.as_str().to_lowercase();
+
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    conn.execute(
+        "INSERT OR REPLACE INTO app_settings (key, value) VALUES (?1, ?2)",
+        rusqlite::params![LOG_LEVEL_SETTING, name],
+    )
+    .map_err(|e| format!("Failed to save log level: {}", e))?;
+
+    apply_level(filter);
+    log::info!("Backend log level set to {}", name);
+    Ok(name)
+}
+
+/// The most recent log entries, oldest first (the last 200 unless `limit` is given)
+#[tauri::command]
+pub fn get_backend_logs(limit: Option<usize>) -> Vec<BackendLogEntry> {
+    let Some(recent) = LOGGER.get().and_then(|logger| logger.recent.lock().ok()) else {
+        return Vec::new();
+    };
+    let limit = limit.unwrap_or(200).min(LOG_BUFFER_LIMIT);
+
+    recent.iter().skip(recent.len().saturating_sub(limit)).cloned().collect()
+}
@@ -0,0 +1,164 @@
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Command, Output, Stdio};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::mpsc;
+
+/// Lines are forwarded at most this often, or sooner once `FLUSH_LINES` are waiting
+const FLUSH_INTERVAL_MS: u64 = 100;
+const FLUSH_LINES: usize = 200;
+/// Output kept (and forwarded) per operation before it's cut off with a marker
+const HISTORY_LIMIT_BYTES: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogLine {
+    /// `stdout` or `stderr`
+    pub stream: String,
+    pub line: String,
+}
+
+/// One flush of coalesced output for the operation `id`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogBatch {
+    pub id: String,
+    pub lines: Vec<LogLine>,
+    /// Output went past the retention limit and the rest was dropped
+    pub truncated: bool,
+}
+
+/// Where a running command sends its lines; cheap to clone into reader threads
+pub type LogSink = mpsc::UnboundedSender<LogLine>;
+
+/// Coalesces a chatty command's output into periodic `LogBatch` events so a tool
+/// printing megabytes can't flood the IPC channel or the UI
+pub struct LogBatcher {
+    sink: LogSink,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl LogBatcher {
+    pub fn spawn(app: AppHandle, event: &'static str, id: String) -> Self {
+        Self::start(app, event, id, None)
+    }
+
+    /// Like `spawn`, but every forwarded line is also appended to `transcript`,
+    /// with stderr lines prefixed, so the output outlives the UI
+    pub fn spawn_with_transcript(app: AppHandle, event: &'static str, id: String, transcript: std::fs::File) -> Self {
+        Self::start(app, event, id, Some(transcript))
+    }
+
+    fn start(app: AppHandle, event: &'static str, id: String, mut transcript: Option<std::fs::File>) -> Self {
+        let (sink, mut rx) = mpsc::unbounded_channel::<LogLine>();
+
+        let task = tokio::spawn(async move {
+            let mut pending: Vec<LogLine> = Vec::new();
+            let mut retained = 0usize;
+            let mut truncated = false;
+            let mut ticker = tokio::time::interval(Duration::from_millis(FLUSH_INTERVAL_MS));
+
+            let flush = |pending: &mut Vec<LogLine>, truncated: bool| {
+                if !pending.is_empty() {
+                    let batch = LogBatch {
+                        id: id.clone(),
+                        lines: std::mem::take(pending),
+                        truncated,
+                    };
+                    let _ = app.emit(event, &batch);
+                }
+            };
+
+            loop {
+                tokio::select! {
+                    line = rx.recv() => {
+                        let Some(line) = line else { break };
+                        if truncated {
+                            continue;
+                        }
+                        retained += line.line.len();
+                        if retained > HISTORY_LIMIT_BYTES {
+                            truncated = true;
+                            let marker = format!("... output truncated after {} KB", HISTORY_LIMIT_BYTES / 1024);
+                            if let Some(file) = transcript.as_mut() {
+                                let _ = writeln!(file, "{}", marker);
+                            }
+                            pending.push(LogLine {
+                                stream: line.stream,
+                                line: marker,
+                            });
+                            flush(&mut pending, truncated);
+                            continue;
+                        }
+                        if let Some(file) = transcript.as_mut() {
+                            let prefix = if line.stream == "stderr" { "[stderr] " } else { "" };
+                            let _ = writeln!(file, "{}{}", prefix, line.line);
+                        }
+                        pending.push(line);
+                        if pending.len() >= FLUSH_LINES {
+                            flush(&mut pending, truncated);
+                        }
+                    }
+                    _ = ticker.tick() => flush(&mut pending, truncated),
+                }
+            }
+
+            flush(&mut pending, truncated);
+        });
+
+        Self { sink, task }
+    }
+
+    pub fn sink(&self) -> LogSink {
+        self.sink.clone()
+    }
+
+    /// Waits for every line sent so far to be flushed
+    pub async fn finish(self) {
+        drop(self.sink);
+        let _ = self.task.await;
+    }
+}
+
+fn forward_lines<R: Read + Send + 'static>(
+    reader: R,
+    stream: &'static str,
+    sink: LogSink,
+) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut captured = Vec::new();
+        for line in BufReader::new(reader).lines().map_while(Result::ok) {
+            if captured.len() < HISTORY_LIMIT_BYTES {
+                captured.extend_from_slice(line.as_bytes());
+                captured.push(b'\n');
+            }
+            let _ = sink.send(LogLine {
+                stream: stream.to_string(),
+                line,
+            });
+        }
+        captured
+    })
+}
+
+/// Like `Command::output`, but each line also goes to `sink` as it's printed.
+/// The captured output is capped the same way the forwarded output is.
+pub fn output_logged(command: &mut Command, sink: &LogSink) -> std::io::Result<Output> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let stdout = child.stdout.take().map(|out| forward_lines(out, "stdout", sink.clone()));
+    let stderr = child.stderr.take().map(|err| forward_lines(err, "stderr", sink.clone()));
+    let status = child.wait()?;
+
+    let collect = |reader: Option<std::thread::JoinHandle<Vec<u8>>>| {
+        reader.and_then(|r| r.join().ok()).unwrap_or_default()
+    };
+    Ok(Output {
+        status,
+        stdout: collect(stdout),
+        stderr: collect(stderr),
+    })
+}
@@ -0,0 +1,222 @@
+use rusqlite::{params, Connection, Result as SqliteResult};
+
+/// A single schema change. Migrations run in order and each one runs at most once
+/// per database, tracked by the `schema_version` key in `app_settings`.
+struct Migration {
+    version: i64,
+    description: &'static str,
+    sql: &'static str,
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "project labels for grouping dev servers",
+        sql: "CREATE TABLE IF NOT EXISTS project_labels (
+            project_root TEXT NOT NULL,
+            label TEXT NOT NULL,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            PRIMARY KEY (project_root, label)
+        )",
+    },
+    Migration {
+        version: 2,
+        description: "tracked projects",
+        sql: "CREATE TABLE IF NOT EXISTS projects (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            path TEXT NOT NULL UNIQUE,
+            template TEXT,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )",
+    },
+    Migration {
+        version: 3,
+        description: "saved dev sessions",
+        sql: "CREATE TABLE IF NOT EXISTS dev_sessions (
+            name TEXT PRIMARY KEY,
+            servers TEXT NOT NULL,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )",
+    },
+    Migration {
+        version: 4,
+        description: "Queen CLI install history",
+        sql: "CREATE TABLE IF NOT EXISTS install_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            package_manager TEXT NOT NULL,
+            package_manager_version TEXT,
+            command TEXT NOT NULL,
+            action TEXT NOT NULL,
+            previous_version TEXT,
+            installed_version TEXT,
+            success INTEGER NOT NULL,
+            error TEXT,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )",
+    },
+    Migration {
+        version: 5,
+        description: "saved scan snapshots",
+        sql: "CREATE TABLE IF NOT EXISTS scan_snapshots (
+            name TEXT PRIMARY KEY,
+            servers TEXT NOT NULL,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )",
+    },
+    Migration {
+        version: 6,
+        description: "per-server preferences",
+        sql: "CREATE TABLE IF NOT EXISTS server_prefs (
+            server_id TEXT NOT NULL,
+            key TEXT NOT NULL,
+            value TEXT NOT NULL,
+            updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            PRIMARY KEY (server_id, key)
+        )",
+    },
+    Migration {
+        version: 7,
+        description: "server up/down events",
+        sql: "CREATE TABLE IF NOT EXISTS server_events (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            server_id TEXT NOT NULL,
+            port INTEGER NOT NULL,
+            pid INTEGER NOT NULL,
+            event TEXT NOT NULL,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        );
+        CREATE INDEX IF NOT EXISTS idx_server_events_server ON server_events (server_id, id)",
+    },
+];
+
+pub fn current_schema_version() -> i64 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+fn read_schema_version(conn: &Connection) -> i64 {
+    conn.query_row(
+        "SELECT value FROM app_settings WHERE key = 'schema_version'",
+        [],
+        |row| row.get::<_, String>(0),
+    )
+    .ok()
+    .and_then(|v| v.parse::<i64>().ok())
+    .unwrap_or(0)
+}
+
+/// Applies every migration newer than the stored schema version.
+/// Each migration and its version bump share a transaction so a failure leaves
+/// the database at the last fully applied version.
+pub fn run_migrations(conn: &Connection) -> SqliteResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS app_settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+
+    let version = read_schema_version(conn);
+
+    for migration in MIGRATIONS.iter().filter(|m| m.version > version) {
+        log::info!(
+            "Applying database migration {} ({})",
+            migration.version,
+            migration.description
+        );
+
+        let tx = conn.unchecked_transaction()?;
+        tx.execute_batch(migration.sql)?;
+        tx.execute(
+            "INSERT OR REPLACE INTO app_settings (key, value) VALUES ('schema_version', ?1)",
+            params![migration.version.to_string()],
+        )?;
+        tx.commit()?;
+    }
+
+    if version < current_schema_version() {
+        log::info!(
+            "Database schema upgraded from v{} to v{}",
+            version,
+            current_schema_version()
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table_exists(conn: &Connection, name: &str) -> bool {
+        conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            params![name],
+            |row| row.get::<_, i64>(0),
+        )
+        .unwrap()
+            > 0
+    }
+
+    #[test]
+    fn test_migrations_from_empty_database() {
+        let conn = Connection::open_in_memory().unwrap();
+
+        run_migrations(&conn).unwrap();
+
+        assert_eq!(read_schema_version(&conn), current_schema_version());
+        assert!(table_exists(&conn, "project_labels"));
+        assert!(table_exists(&conn, "projects"));
+
+        // Running again is a no-op
+        run_migrations(&conn).unwrap();
+        assert_eq!(read_schema_version(&conn), current_schema_version());
+    }
+
+    #[test]
+    fn test_migrations_from_partial_older_schema() {
+        let conn = Connection::open_in_memory().unwrap();
+
+        // A database from before versioning: settings and labels exist, no schema_version
+        conn.execute_batch(
+            "CREATE TABLE app_settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL,
+                created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );
+            CREATE TABLE project_labels (
+                project_root TEXT NOT NULL,
+                label TEXT NOT NULL,
+                created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                PRIMARY KEY (project_root, label)
+            );
+            INSERT INTO app_settings (key, value) VALUES ('queen_projects_directory', '/tmp/projects');
+            INSERT INTO project_labels (project_root, label) VALUES ('/tmp/projects/app', 'work');",
+        )
+        .unwrap();
+
+        run_migrations(&conn).unwrap();
+
+        assert_eq!(read_schema_version(&conn), current_schema_version());
+        assert!(table_exists(&conn, "projects"));
+
+        let directory: String = conn
+            .query_row(
+                "SELECT value FROM app_settings WHERE key = 'queen_projects_directory'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(directory, "/tmp/projects");
+
+        let labels: i64 = conn
+            .query_row("SELECT COUNT(*) FROM project_labels", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(labels, 1);
+    }
+}
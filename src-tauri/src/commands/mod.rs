@@ -7,3 +7,8 @@ pub mod slash_commands;
 pub mod proxy;
 pub mod servers;
 pub mod queen;
+pub mod projects;
+pub mod migrations;
+pub mod signatures;
+pub mod log_stream;
+pub mod backend_log;
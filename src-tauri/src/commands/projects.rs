@@ -0,0 +1,904 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::State;
+use walkdir::WalkDir;
+use super::agents::AgentDb;
+use super::servers::{running_package_managers, running_server_cwds, RunningPackageManager};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackedProject {
+    pub id: i64,
+    pub name: String,
+    pub path: String,
+    pub template: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortConflict {
+    pub port: u16,
+    pub projects: Vec<TrackedProject>,
+    pub suggested_ports: Vec<u16>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CruftEntry {
+    pub kind: String,
+    /// Relative to the project root
+    pub path: String,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectCruft {
+    pub project_path: String,
+    pub entries: Vec<CruftEntry>,
+    pub total_bytes: u64,
+}
+
+const CRUFT_DIRS: &[&str] = &[
+    "node_modules",
+    ".next",
+    ".nuxt",
+    ".svelte-kit",
+    ".turbo",
+    ".parcel-cache",
+    "dist",
+    "build",
+    "target",
+    "__pycache__",
+    ".pytest_cache",
+    ".mypy_cache",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComposePort {
+    pub service: String,
+    pub host_port: u16,
+    pub container_port: Option<u16>,
+    pub host_ip: Option<String>,
+    pub protocol: String,
+    pub compose_file: String,
+}
+
+const COMPOSE_FILES: &[&str] = &["compose.yaml", "compose.yml", "docker-compose.yml", "docker-compose.yaml"];
+
+const ENV_FILES: &[&str] = &[".env.development.local", ".env.local", ".env.development", ".env"];
+const VITE_CONFIG_FILES: &[&str] = &[
+    "vite.config.ts",
+    "vite.config.js",
+    "vite.config.mts",
+    "vite.config.mjs",
+];
+const DEV_SCRIPT_NAMES: &[&str] = &["dev", "start", "serve"];
+
+fn parse_port(value: &str) -> Option<u16> {
+    value.parse::<u16>().ok().filter(|&port| port > 0)
+}
+
+fn port_from_scripts(project_path: &Path) -> Option<u16> {
+    let content = fs::read_to_string(project_path.join("package.json")).ok()?;
+    let package: serde_json::Value = serde_json::from_str(&content).ok()?;
+    let scripts = package.get("scripts")?.as_object()?;
+
+    let port_flag = Regex::new(r"(?:--port[=\s]+|-p\s+|\bPORT=)(\d{1,5})\b").ok()?;
+
+    DEV_SCRIPT_NAMES.iter().find_map(|name| {
+        let script = scripts.get(*name)?.as_str()?;
+        port_flag
+            .captures(script)
+            .and_then(|caps| parse_port(&caps[1]))
+    })
+}
+
+fn port_from_vite_config(project_path: &Path) -> Option<u16> {
+    let server_port = Regex::new(r"(?s)server\s*:\s*\{.*?\bport\s*:\s*(\d{1,5})").ok()?;
+
+    VITE_CONFIG_FILES.iter().find_map(|file| {
+        let content = fs::read_to_string(project_path.join(file)).ok()?;
+        server_port
+            .captures(&content)
+            .and_then(|caps| parse_port(&caps[1]))
+    })
+}
+
+fn port_from_env_files(project_path: &Path) -> Option<u16> {
+    ENV_FILES.iter().find_map(|file| {
+        let content = fs::read_to_string(project_path.join(file)).ok()?;
+        content.lines().find_map(|line| {
+            let line = line.trim();
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let value = line.strip_prefix("PORT=")?;
+            parse_port(value.trim().trim_matches('"').trim_matches('\''))
+        })
+    })
+}
+
+/// Resolves the port a project intends to run its dev server on.
+/// Explicit script flags win over bundler config, which wins over `.env`.
+pub fn detect_project_dev_port(project_path: &Path) -> Option<u16> {
+    port_from_scripts(project_path)
+        .or_else(|| port_from_vite_config(project_path))
+        .or_else(|| port_from_env_files(project_path))
+}
+
+#[tauri::command]
+pub fn get_project_dev_port(path: String) -> Result<Option<u16>, String> {
+    let project_path = Path::new(&path);
+    if !project_path.is_dir() {
+        return Err(format!("Project directory '{}' does not exist", path));
+    }
+
+    Ok(detect_project_dev_port(project_path))
+}
+
+/// Lockfiles in the order their package manager should win when several exist
+const LOCKFILES: &[(&str, &str)] = &[
+    ("bun.lockb", "bun"),
+    ("bun.lock", "bun"),
+    ("pnpm-lock.yaml", "pnpm"),
+    ("yarn.lock", "yarn"),
+    ("package-lock.json", "npm"),
+];
+
+/// The package manager a project uses, judged by its lockfile; npm when there is none
+pub fn detect_project_package_manager(project_path: &Path) -> &'static str {
+    LOCKFILES
+        .iter()
+        .find(|(file, _)| project_path.join(file).is_file())
+        .map(|(_, manager)| *manager)
+        .unwrap_or("npm")
+}
+
+/// Picks the package.json script that runs the dev server: `preferred` when given,
+/// otherwise the first of `dev`, `start` and `serve` the project defines
+pub fn detect_project_dev_script(project_path: &Path, preferred: Option<&str>) -> Result<String, String> {
+    let content = fs::read_to_string(project_path.join("package.json"))
+        .map_err(|e| format!("Failed to read package.json: {}", e))?;
+    let package: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse package.json: {}", e))?;
+    let scripts = package
+        .get("scripts")
+        .and_then(|s| s.as_object())
+        .ok_or("package.json has no scripts")?;
+
+    match preferred {
+        Some(name) if scripts.contains_key(name) => Ok(name.to_string()),
+        Some(name) => Err(format!("package.json has no '{}' script", name)),
+        None => DEV_SCRIPT_NAMES
+            .iter()
+            .find(|name| scripts.contains_key(**name))
+            .map(|name| name.to_string())
+            .ok_or_else(|| format!("package.json has none of the {} scripts", DEV_SCRIPT_NAMES.join("/"))),
+    }
+}
+
+/// Falls back to the framework's default port when the project doesn't set one
+pub fn default_framework_port(project_path: &Path) -> Option<u16> {
+    let content = fs::read_to_string(project_path.join("package.json")).ok()?;
+    let package: serde_json::Value = serde_json::from_str(&content).ok()?;
+    let has_dependency = |name: &str| {
+        ["dependencies", "devDependencies"]
+            .iter()
+            .any(|key| package.get(key).and_then(|d| d.get(name)).is_some())
+    };
+
+    if has_dependency("vite") {
+        Some(5173)
+    } else if has_dependency("next") || has_dependency("react-scripts") {
+        Some(3000)
+    } else {
+        None
+    }
+}
+
+/// Manifest files and the stack each one indicates
+const STACK_MARKERS: &[(&str, &str)] = &[
+    ("Cargo.toml", "Rust"),
+    ("pyproject.toml", "Python"),
+    ("requirements.txt", "Python"),
+    ("go.mod", "Go"),
+    ("Gemfile", "Ruby"),
+    ("composer.json", "PHP"),
+    ("deno.json", "Deno"),
+];
+
+/// Frameworks recognised from package.json dependencies, most specific first
+const NODE_FRAMEWORKS: &[(&str, &str)] = &[
+    ("@tauri-apps/api", "Tauri"),
+    ("next", "Next.js"),
+    ("nuxt", "Nuxt"),
+    ("@sveltejs/kit", "SvelteKit"),
+    ("astro", "Astro"),
+    ("vite", "Vite"),
+    ("react-scripts", "Create React App"),
+    ("express", "Express"),
+];
+
+/// Stacks a project uses, from its manifests: Node projects are named by
+/// framework when one is recognised. Empty when nothing matched.
+pub fn detect_project_stack(project_path: &Path) -> Vec<String> {
+    let mut stack: Vec<String> = Vec::new();
+
+    if let Ok(content) = fs::read_to_string(project_path.join("package.json")) {
+        let package: serde_json::Value = serde_json::from_str(&content).unwrap_or_default();
+        let has_dependency = |name: &str| {
+            ["dependencies", "devDependencies"]
+                .iter()
+                .any(|key| package.get(key).and_then(|d| d.get(name)).is_some())
+        };
+        stack.extend(
+            NODE_FRAMEWORKS
+                .iter()
+                .filter(|(dependency, _)| has_dependency(dependency))
+                .map(|(_, framework)| framework.to_string()),
+        );
+        if stack.is_empty() {
+            stack.push("Node.js".to_string());
+        }
+    }
+
+    for (marker, name) in STACK_MARKERS {
+        if project_path.join(marker).is_file() && !stack.iter().any(|s| s == name) {
+            stack.push(name.to_string());
+        }
+    }
+
+    stack
+}
+
+fn parse_port_range(value: &str) -> Option<Vec<u16>> {
+    match value.split_once('-') {
+        Some((start, end)) => {
+            let (start, end) = (parse_port(start.trim())?, parse_port(end.trim())?);
+            (start <= end).then(|| (start..=end).collect())
+        }
+        None => parse_port(value.trim()).map(|port| vec![port]),
+    }
+}
+
+/// Short syntax: `"3000:3000"`, `"127.0.0.1:8080:80"`, `"9000-9001:9000-9001/udp"`.
+/// A bare container port publishes to a random host port, so it yields nothing.
+fn parse_short_port_mapping(mapping: &str) -> Vec<(Option<String>, u16, Option<u16>, String)> {
+    let (mapping, protocol) = mapping.split_once('/').unwrap_or((mapping, "tcp"));
+    let parts: Vec<&str> = mapping.rsplitn(3, ':').collect();
+    if parts.len() < 2 {
+        return Vec::new();
+    }
+
+    let host_ip = parts.get(2).map(|ip| ip.trim_matches(['[', ']']).to_string());
+    let Some(host_ports) = parse_port_range(parts[1]) else {
+        return Vec::new();
+    };
+    let container_ports = parse_port_range(parts[0]).unwrap_or_default();
+
+    host_ports
+        .iter()
+        .enumerate()
+        .map(|(i, &host_port)| {
+            let container_port = container_ports.get(i).or(container_ports.first()).copied();
+            (host_ip.clone(), host_port, container_port, protocol.to_string())
+        })
+        .collect()
+}
+
+fn yaml_scalar(value: &serde_yaml::Value) -> Option<String> {
+    match value {
+        serde_yaml::Value::String(s) => Some(s.clone()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn parse_compose_ports(content: &str, compose_file: &str) -> Vec<ComposePort> {
+    let Ok(document) = serde_yaml::from_str::<serde_yaml::Value>(content) else {
+        return Vec::new();
+    };
+    let Some(services) = document.get("services").and_then(|s| s.as_mapping()) else {
+        return Vec::new();
+    };
+
+    let mut ports = Vec::new();
+    for (name, service) in services {
+        let Some(name) = name.as_str() else {
+            continue;
+        };
+        let Some(entries) = service.get("ports").and_then(|p| p.as_sequence()) else {
+            continue;
+        };
+
+        for entry in entries {
+            let mappings = if entry.is_mapping() {
+                // Long syntax: { target, published, host_ip, protocol }
+                let target = entry.get("target").and_then(yaml_scalar).and_then(|t| parse_port(&t));
+                let protocol = entry
+                    .get("protocol")
+                    .and_then(yaml_scalar)
+                    .unwrap_or_else(|| "tcp".to_string());
+                let host_ip = entry.get("host_ip").and_then(yaml_scalar);
+
+                entry
+                    .get("published")
+                    .and_then(yaml_scalar)
+                    .and_then(|published| parse_port_range(&published))
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|host_port| (host_ip.clone(), host_port, target, protocol.clone()))
+                    .collect()
+            } else {
+                yaml_scalar(entry)
+                    .map(|mapping| parse_short_port_mapping(&mapping))
+                    .unwrap_or_default()
+            };
+
+            for (host_ip, host_port, container_port, protocol) in mappings {
+                ports.push(ComposePort {
+                    service: name.to_string(),
+                    host_port,
+                    container_port,
+                    host_ip,
+                    protocol,
+                    compose_file: compose_file.to_string(),
+                });
+            }
+        }
+    }
+
+    ports
+}
+
+/// Host ports published by the project's compose file(s), with their service names
+pub fn compose_published_ports(project_path: &Path) -> Vec<ComposePort> {
+    COMPOSE_FILES
+        .iter()
+        .filter_map(|file| {
+            let content = fs::read_to_string(project_path.join(file)).ok()?;
+            Some(parse_compose_ports(&content, file))
+        })
+        .flatten()
+        .collect()
+}
+
+#[tauri::command]
+pub fn get_project_compose_ports(path: String) -> Result<Vec<ComposePort>, String> {
+    let project_path = Path::new(&path);
+    if !project_path.is_dir() {
+        return Err(format!("Project directory '{}' does not exist", path));
+    }
+
+    Ok(compose_published_ports(project_path))
+}
+
+pub fn load_tracked_projects(db: &AgentDb) -> Result<Vec<TrackedProject>, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let mut stmt = conn
+        .prepare("SELECT id, name, path, template, created_at FROM projects ORDER BY created_at DESC")
+        .map_err(|e| e.to_string())?;
+
+    let projects = stmt
+        .query_map([], |row| {
+            Ok(TrackedProject {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                path: row.get(2)?,
+                template: row.get(3)?,
+                created_at: row.get(4)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(projects)
+}
+
+pub fn track_project(db: &AgentDb, path: &Path, template: Option<&str>) -> Result<TrackedProject, String> {
+    let path_str = path.to_string_lossy().to_string();
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path_str.clone());
+
+    {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        conn.execute(
+            "INSERT OR IGNORE INTO projects (name, path, template) VALUES (?1, ?2, ?3)",
+            rusqlite::params![name, path_str, template],
+        )
+        .map_err(|e| format!("Failed to track project: {}", e))?;
+    }
+
+    load_tracked_projects(db)?
+        .into_iter()
+        .find(|p| p.path == path_str)
+        .ok_or_else(|| "Failed to load tracked project".to_string())
+}
+
+#[tauri::command]
+pub fn list_tracked_projects(db: State<'_, AgentDb>) -> Result<Vec<TrackedProject>, String> {
+    load_tracked_projects(&db)
+}
+
+#[tauri::command]
+pub fn add_tracked_project(path: String, db: State<'_, AgentDb>) -> Result<TrackedProject, String> {
+    let project_path = Path::new(&path);
+    if !project_path.is_dir() {
+        return Err(format!("Project directory '{}' does not exist", path));
+    }
+
+    track_project(&db, project_path, None)
+}
+
+#[tauri::command]
+pub fn remove_tracked_project(id: i64, db: State<'_, AgentDb>) -> Result<(), String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    conn.execute("DELETE FROM projects WHERE id = ?1", rusqlite::params![id])
+        .map_err(|e| format!("Failed to remove project: {}", e))?;
+
+    Ok(())
+}
+
+fn is_port_free(port: u16) -> bool {
+    std::net::TcpListener::bind(("127.0.0.1", port)).is_ok()
+}
+
+#[tauri::command]
+pub fn detect_project_port_conflicts(db: State<'_, AgentDb>) -> Result<Vec<PortConflict>, String> {
+    let mut by_port: BTreeMap<u16, Vec<TrackedProject>> = BTreeMap::new();
+    for project in load_tracked_projects(&db)? {
+        if let Some(port) = detect_project_dev_port(Path::new(&project.path)) {
+            by_port.entry(port).or_default().push(project);
+        }
+    }
+
+    let mut taken: HashSet<u16> = by_port.keys().copied().collect();
+    let mut conflicts = Vec::new();
+
+    for (port, projects) in by_port {
+        if projects.len() < 2 {
+            continue;
+        }
+
+        // Every project after the first needs its own port
+        let mut suggested_ports = Vec::new();
+        let mut candidate = port;
+        while suggested_ports.len() < projects.len() - 1 && candidate < u16::MAX {
+            candidate += 1;
+            if !taken.contains(&candidate) && is_port_free(candidate) {
+                taken.insert(candidate);
+                suggested_ports.push(candidate);
+            }
+        }
+
+        conflicts.push(PortConflict {
+            port,
+            projects,
+            suggested_ports,
+        });
+    }
+
+    Ok(conflicts)
+}
+
+fn dir_size(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+fn canonical_project_root(path: &str) -> Result<PathBuf, String> {
+    let root = fs::canonicalize(path).map_err(|e| format!("Invalid project path '{}': {}", path, e))?;
+    if !root.is_dir() {
+        return Err(format!("'{}' is not a directory", path));
+    }
+    Ok(root)
+}
+
+#[tauri::command]
+pub fn compute_project_cruft(path: String) -> Result<ProjectCruft, String> {
+    let root = canonical_project_root(&path)?;
+
+    let mut entries = Vec::new();
+    let mut walker = WalkDir::new(&root).follow_links(false).min_depth(1).into_iter();
+    while let Some(entry) = walker.next() {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        if !entry.file_type().is_dir() {
+            continue;
+        }
+
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name == ".git" {
+            walker.skip_current_dir();
+            continue;
+        }
+        if !CRUFT_DIRS.contains(&name.as_str()) {
+            continue;
+        }
+
+        entries.push(CruftEntry {
+            kind: name,
+            path: entry
+                .path()
+                .strip_prefix(&root)
+                .unwrap_or(entry.path())
+                .to_string_lossy()
+                .to_string(),
+            bytes: dir_size(entry.path()),
+        });
+        // Nested cruft (e.g. node_modules/**/dist) is already counted in the parent
+        walker.skip_current_dir();
+    }
+
+    entries.sort_by_key(|e| std::cmp::Reverse(e.bytes));
+    let total_bytes = entries.iter().map(|e| e.bytes).sum();
+
+    Ok(ProjectCruft {
+        project_path: root.to_string_lossy().to_string(),
+        entries,
+        total_bytes,
+    })
+}
+
+/// Deletes the given cruft directories (paths as returned by `compute_project_cruft`)
+/// and returns the number of bytes freed
+#[tauri::command]
+pub async fn clean_project_cruft(path: String, targets: Vec<String>) -> Result<u64, String> {
+    let root = canonical_project_root(&path)?;
+
+    let running: Vec<String> = running_server_cwds()
+        .await?
+        .into_iter()
+        .filter(|cwd| Path::new(cwd).starts_with(&root))
+        .collect();
+    if !running.is_empty() {
+        return Err(format!(
+            "A server is running from this project ({}); stop it before cleaning",
+            running.join(", ")
+        ));
+    }
+
+    let mut resolved = Vec::new();
+    for target in &targets {
+        let candidate = root.join(target);
+        let metadata = fs::symlink_metadata(&candidate)
+            .map_err(|e| format!("Cannot access '{}': {}", target, e))?;
+
+        // Never follow a symlink out of the project
+        if metadata.file_type().is_symlink() || !metadata.is_dir() {
+            return Err(format!("'{}' is not a directory inside the project", target));
+        }
+        let canonical = fs::canonicalize(&candidate).map_err(|e| e.to_string())?;
+        if !canonical.starts_with(&root) || canonical == root {
+            return Err(format!("'{}' is outside the project root", target));
+        }
+
+        let is_cruft = canonical
+            .file_name()
+            .map(|name| CRUFT_DIRS.iter().any(|dir| name == *dir))
+            .unwrap_or(false);
+        if !is_cruft {
+            return Err(format!("'{}' is not a recognised build or dependency directory", target));
+        }
+
+        resolved.push(canonical);
+    }
+
+    let mut freed = 0;
+    for dir in resolved {
+        let bytes = dir_size(&dir);
+        fs::remove_dir_all(&dir).map_err(|e| format!("Failed to remove {}: {}", dir.display(), e))?;
+        freed += bytes;
+    }
+
+    Ok(freed)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeVersionCheck {
+    /// The range as written, e.g. `>=18 <21` or `20.11.1`
+    pub required: Option<String>,
+    /// Where the requirement came from: `.nvmrc`, `.node-version` or `engines.node`
+    pub source: Option<String>,
+    pub installed: Option<String>,
+    pub matches: bool,
+    pub note: Option<String>,
+}
+
+fn required_node_version(project_path: &Path) -> Option<(String, String)> {
+    for file in [".nvmrc", ".node-version"] {
+        if let Ok(content) = fs::read_to_string(project_path.join(file)) {
+            let version = content
+                .lines()
+                .map(|line| line.split('#').next().unwrap_or("").trim())
+                .find(|line| !line.is_empty());
+            if let Some(version) = version {
+                return Some((version.to_string(), file.to_string()));
+            }
+        }
+    }
+
+    let content = fs::read_to_string(project_path.join("package.json")).ok()?;
+    let package: serde_json::Value = serde_json::from_str(&content).ok()?;
+    let engines = package.get("engines")?.get("node")?.as_str()?;
+    Some((engines.trim().to_string(), "engines.node".to_string()))
+}
+
+/// Translates an npm-style range (`^18 || >=20`, `16 - 18`, `>=18 <21`, `v20`) into
+/// semver requirements, one per `||` alternative
+fn parse_node_range(range: &str) -> Result<Vec<semver::VersionReq>, String> {
+    range
+        .split("||")
+        .map(|alternative| {
+            let alternative = alternative.trim();
+            let req = if alternative.is_empty() || alternative == "*" || alternative == "x" {
+                "*".to_string()
+            } else if let Some((low, high)) = alternative.split_once(" - ") {
+                format!(">={}, <={}", low.trim().trim_start_matches('v'), high.trim().trim_start_matches('v'))
+            } else {
+                // Re-attach operators written with a space, e.g. `>= 18`
+                let mut comparators: Vec<String> = Vec::new();
+                for token in alternative.split_whitespace() {
+                    match comparators.last_mut() {
+                        Some(last) if last.chars().all(|c| "<>=~^".contains(c)) => last.push_str(token),
+                        _ => comparators.push(token.to_string()),
+                    }
+                }
+
+                comparators
+                    .into_iter()
+                    .map(|comparator| {
+                        let comparator = comparator.replacen('v', "", 1);
+                        let is_wildcard = comparator.contains(['x', 'X', '*']);
+                        // A bare version pins it exactly (or the whole major/minor when partial)
+                        if comparator.starts_with(|c: char| c.is_ascii_digit()) && !is_wildcard {
+                            format!("={}", comparator)
+                        } else {
+                            comparator
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+
+            semver::VersionReq::parse(&req).map_err(|e| format!("Unsupported range '{}': {}", alternative, e))
+        })
+        .collect()
+}
+
+fn installed_node_version() -> Option<String> {
+    let output = std::process::Command::new("node").arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().trim_start_matches('v').to_string())
+}
+
+#[tauri::command]
+pub fn check_node_version_match(path: String) -> Result<NodeVersionCheck, String> {
+    let project_path = Path::new(&path);
+    if !project_path.is_dir() {
+        return Err(format!("Project directory '{}' does not exist", path));
+    }
+
+    let installed = installed_node_version();
+    let Some((required, source)) = required_node_version(project_path) else {
+        return Ok(NodeVersionCheck {
+            required: None,
+            source: None,
+            installed,
+            matches: true,
+            note: Some("The project does not pin a Node.js version".to_string()),
+        });
+    };
+
+    let mut check = NodeVersionCheck {
+        required: Some(required.clone()),
+        source: Some(source),
+        installed: installed.clone(),
+        matches: false,
+        note: None,
+    };
+
+    let Some(installed) = installed else {
+        check.note = Some("Node.js is not installed or not on PATH".to_string());
+        return Ok(check);
+    };
+
+    // nvm aliases such as `lts/*` or `node` can't be checked without asking nvm
+    if required.starts_with("lts/") || required == "node" || required == "stable" {
+        check.note = Some(format!("'{}' is an nvm alias and can't be compared", required));
+        return Ok(check);
+    }
+
+    let version = semver::Version::parse(&installed)
+        .map_err(|e| format!("Unexpected node version '{}': {}", installed, e))?;
+    match parse_node_range(&required) {
+        Ok(alternatives) => {
+            check.matches = alternatives.iter().any(|req| req.matches(&version));
+            if !check.matches {
+                check.note = Some(format!("Node.js {} does not satisfy '{}'", installed, required));
+            }
+        }
+        Err(e) => check.note = Some(e),
+    }
+
+    Ok(check)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockfileInfo {
+    pub file: String,
+    pub package_manager: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockfileReport {
+    pub project_path: String,
+    pub lockfiles: Vec<LockfileInfo>,
+    /// From package.json's `packageManager` field (Corepack), e.g. "pnpm"
+    pub declared_package_manager: Option<String>,
+    /// Package managers that started servers currently running from this project
+    pub running: Vec<RunningPackageManager>,
+    pub conflict: bool,
+    pub recommended_package_manager: String,
+    pub recommendation: String,
+}
+
+fn declared_package_manager(project_path: &Path) -> Option<String> {
+    let content = fs::read_to_string(project_path.join("package.json")).ok()?;
+    let package: serde_json::Value = serde_json::from_str(&content).ok()?;
+    let declared = package.get("packageManager")?.as_str()?;
+    // "pnpm@9.1.0+sha512...."
+    Some(declared.split('@').next().unwrap_or(declared).to_string())
+}
+
+/// Flags projects with lockfiles from more than one package manager, or whose
+/// running servers were started with a different one than the lockfile implies
+#[tauri::command]
+pub async fn detect_lockfile_conflicts(path: String) -> Result<LockfileReport, String> {
+    let root = canonical_project_root(&path)?;
+
+    let lockfiles: Vec<LockfileInfo> = LOCKFILES
+        .iter()
+        .filter(|(file, _)| root.join(file).is_file())
+        .map(|(file, manager)| LockfileInfo {
+            file: file.to_string(),
+            package_manager: manager.to_string(),
+        })
+        .collect();
+    let mut managers: Vec<&str> = lockfiles.iter().map(|l| l.package_manager.as_str()).collect();
+    managers.dedup();
+
+    let declared = declared_package_manager(&root);
+    let running = running_package_managers(&root).await.unwrap_or_default();
+
+    // Corepack's declaration is the project's own statement of intent, so it wins;
+    // otherwise trust the most recently written lockfile
+    let newest_lockfile = lockfiles
+        .iter()
+        .max_by_key(|l| fs::metadata(root.join(&l.file)).and_then(|m| m.modified()).ok())
+        .map(|l| l.package_manager.clone());
+    let recommended = declared
+        .clone()
+        .or(newest_lockfile)
+        .unwrap_or_else(|| "npm".to_string());
+
+    let mut problems = Vec::new();
+    if managers.len() > 1 {
+        let stale: Vec<&str> = lockfiles
+            .iter()
+            .filter(|l| l.package_manager != recommended)
+            .map(|l| l.file.as_str())
+            .collect();
+        problems.push(format!(
+            "Lockfiles from {} are present. Keep {}'s and delete {}.",
+            managers.join(" and "),
+            recommended,
+            stale.join(", ")
+        ));
+    }
+    if let (Some(declared), [only]) = (&declared, managers.as_slice()) {
+        if declared != only {
+            problems.push(format!(
+                "package.json declares {} but the lockfile is {}'s.",
+                declared, only
+            ));
+        }
+    }
+    for server in running.iter().filter(|r| r.package_manager != recommended) {
+        problems.push(format!(
+            "The server on port {} was started with {} but this project uses {}.",
+            server.port, server.package_manager, recommended
+        ));
+    }
+
+    let recommendation = if !problems.is_empty() {
+        format!("{} Use {} for installs and scripts.", problems.join(" "), recommended)
+    } else if lockfiles.is_empty() {
+        format!("No lockfile yet; run `{} install` to create one.", recommended)
+    } else {
+        format!("Lockfiles are consistent; keep using {}.", recommended)
+    };
+
+    Ok(LockfileReport {
+        project_path: root.to_string_lossy().to_string(),
+        lockfiles,
+        declared_package_manager: declared,
+        running,
+        conflict: !problems.is_empty(),
+        recommended_package_manager: recommended,
+        recommendation,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn satisfies(range: &str, version: &str) -> bool {
+        let version = semver::Version::parse(version).unwrap();
+        parse_node_range(range).unwrap().iter().any(|req| req.matches(&version))
+    }
+
+    #[test]
+    fn test_parse_node_range_caret_and_tilde() {
+        assert!(satisfies("^18.2", "18.9.1"));
+        assert!(!satisfies("^18.2", "18.1.0"));
+        assert!(!satisfies("^18.2", "19.0.0"));
+
+        assert!(satisfies("~18.2.1", "18.2.5"));
+        assert!(!satisfies("~18.2.1", "18.3.0"));
+    }
+
+    #[test]
+    fn test_parse_node_range_comparators() {
+        assert!(satisfies(">=18", "22.1.0"));
+        assert!(satisfies(">= 18", "18.0.0"));
+        assert!(!satisfies(">=18", "16.20.2"));
+
+        assert!(satisfies(">=18 <21", "20.11.0"));
+        assert!(!satisfies(">=18 <21", "21.0.0"));
+
+        assert!(satisfies("16 - 18", "17.3.0"));
+        assert!(!satisfies("16 - 18", "20.0.0"));
+
+        assert!(satisfies("v20", "20.3.1"));
+        assert!(!satisfies("v20", "21.0.0"));
+    }
+
+    #[test]
+    fn test_parse_node_range_alternatives() {
+        assert_eq!(parse_node_range("^16 || >=20").unwrap().len(), 2);
+        assert!(satisfies("^16 || >=20", "16.5.0"));
+        assert!(!satisfies("^16 || >=20", "18.0.0"));
+        assert!(satisfies("^16 || >=20", "22.0.0"));
+    }
+
+    #[test]
+    fn test_parse_node_range_x_ranges() {
+        assert!(satisfies("18.x", "18.4.0"));
+        assert!(!satisfies("18.x", "19.0.0"));
+        assert!(satisfies("20.1.x", "20.1.7"));
+        assert!(!satisfies("20.1.x", "20.2.0"));
+        for any in ["*", "x", ""] {
+            assert!(satisfies(any, "22.0.0"), "{:?}", any);
+        }
+    }
+
+    #[test]
+    fn test_parse_node_range_invalid() {
+        assert!(parse_node_range("latest").is_err());
+        assert!(parse_node_range(">=eighteen").is_err());
+        assert!(parse_node_range("^18 || lts").is_err());
+    }
+}
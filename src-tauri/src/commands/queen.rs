@@ -1,16 +1,23 @@
+use futures::stream::{FuturesUnordered, StreamExt};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::process::{Command, Stdio};
-use tauri::State;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
 use super::agents::AgentDb;
+use super::log_stream::{output_logged, LogBatcher, LogSink};
+use super::projects::{detect_project_stack, load_tracked_projects, track_project, TrackedProject};
+use super::servers::{open_with_system_default, scan_dev_servers, DevServer, PortReservationState, ScanEnvelope};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueenCliStatus {
     pub installed: bool,
     pub version: Option<String>,
     pub commands_available: CommandsAvailable,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommandsAvailable {
     pub queen_rag: bool,
     pub queen_nextjs: bool,
@@ -25,8 +32,42 @@ pub struct TemplateInfo {
     pub description: String,
 }
 
+const QUEEN_COMMANDS: &[&str] = &["queen-rag", "queen-nextjs", "queen-tauri", "queen-init"];
+const VERSION_PROBE_TIMEOUT_SECS: u64 = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandProbe {
+    pub command: String,
+    pub path: Option<String>,
+    pub runnable: bool,
+    pub version: Option<String>,
+    pub error: Option<String>,
+    /// Further matches later on PATH that the first one shadows
+    pub shadowed: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueenCliVerification {
+    pub commands: Vec<CommandProbe>,
+    pub all_runnable: bool,
+    pub versions_consistent: bool,
+    pub cancelled: bool,
+}
+
+/// Cancellation handle for an in-flight `verify_queen_cli_detailed` run
+#[derive(Default)]
+pub struct QueenCliVerifyState {
+    /// The latest run's id and its cancel sender
+    cancel: Mutex<Option<(u64, tokio::sync::watch::Sender<bool>)>>,
+    next_run: std::sync::atomic::AtomicU64,
+}
+
 #[tauri::command]
 pub async fn check_queen_cli_status() -> Result<QueenCliStatus, String> {
+    Ok(queen_cli_status())
+}
+
+fn queen_cli_status() -> QueenCliStatus {
     let queen_rag = check_command_exists("queen-rag");
     let queen_nextjs = check_command_exists("queen-nextjs");
     let queen_tauri = check_command_exists("queen-tauri");
@@ -40,7 +81,7 @@ pub async fn check_queen_cli_status() -> Result<QueenCliStatus, String> {
         None
     };
 
-    Ok(QueenCliStatus {
+    QueenCliStatus {
         installed: all_installed,
         version,
         commands_available: CommandsAvailable {
@@ -49,26 +90,234 @@ pub async fn check_queen_cli_status() -> Result<QueenCliStatus, String> {
             queen_tauri,
             queen_init,
         },
-    })
+    }
 }
 
-#[tauri::command]
-pub async fn install_queen_cli() -> Result<String, String> {
+const CLI_STATUS_CACHE_TTL: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone)]
+struct CachedCliStatus {
+    checked_at: std::time::Instant,
+    status: QueenCliStatus,
+    latest_version: Option<String>,
+}
+
+/// Queen CLI status and the latest published version, reused across dashboard loads
+#[derive(Default)]
+pub struct QueenCliStatusCache {
+    entry: Mutex<Option<CachedCliStatus>>,
+}
+
+impl QueenCliStatusCache {
+    fn invalidate(&self) {
+        if let Ok(mut entry) = self.entry.lock() {
+            *entry = None;
+        }
+    }
+}
+
+fn latest_queen_version() -> Option<String> {
     let output = Command::new("npm")
-        .args(&["install", "-g", "@kenkaiiii/queen-claude"])
+        .args(["view", QUEEN_PACKAGE, "version"])
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .output()
-        .map_err(|e| format!("Failed to execute npm: {}", e))?;
+        .ok()
+        .filter(|o| o.status.success())?;
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!version.is_empty()).then_some(version)
+}
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Installation failed: {}", stderr));
+async fn cached_cli_status(cache: &QueenCliStatusCache) -> CachedCliStatus {
+    if let Some(entry) = cache.entry.lock().ok().and_then(|e| e.clone()) {
+        if entry.checked_at.elapsed() < CLI_STATUS_CACHE_TTL {
+            return entry;
+        }
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (status, latest_version) =
+        tokio::task::spawn_blocking(|| (queen_cli_status(), latest_queen_version()))
+            .await
+            .unwrap_or_else(|_| (queen_cli_status(), None));
+    let entry = CachedCliStatus {
+        checked_at: std::time::Instant::now(),
+        status,
+        latest_version,
+    };
+    if let Ok(mut cached) = cache.entry.lock() {
+        *cached = Some(entry.clone());
+    }
+    entry
+}
+
+fn is_newer_version(latest: &str, installed: &str) -> bool {
+    match (semver::Version::parse(latest), semver::Version::parse(installed)) {
+        (Ok(latest), Ok(installed)) => latest > installed,
+        _ => false,
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Dashboard {
+    pub servers: Vec<DevServer>,
+    pub queen_cli: QueenCliStatus,
+    pub latest_queen_version: Option<String>,
+    pub update_available: bool,
+    pub projects: Vec<TrackedProject>,
+    pub projects_directory: Option<String>,
+}
+
+/// Everything the main view needs in one round-trip: a single server scan plus
+/// cached CLI status, tracked projects and the active projects directory
+#[tauri::command]
+pub async fn get_dashboard(
+    app: AppHandle,
+    db: State<'_, AgentDb>,
+    reservations: State<'_, PortReservationState>,
+    cli_cache: State<'_, QueenCliStatusCache>,
+) -> Result<Dashboard, String> {
+    let (servers, cli) = tokio::join!(
+        scan_dev_servers(None, app, db.clone(), reservations),
+        cached_cli_status(&cli_cache)
+    );
+    let servers = servers?;
+
+    let projects = load_tracked_projects(&db)?;
+    let projects_directory = {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        active_projects_directory(&conn).ok()
+    };
+
+    let update_available = match (&cli.status.version, &cli.latest_version) {
+        (Some(installed), Some(latest)) => is_newer_version(latest, installed),
+        _ => false,
+    };
+
+    Ok(Dashboard {
+        servers,
+        queen_cli: cli.status,
+        latest_queen_version: cli.latest_version,
+        update_available,
+        projects,
+        projects_directory,
+    })
+}
+
+const QUEEN_PACKAGE: &str = "@kenkaiiii/queen-claude";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallRecord {
+    pub id: i64,
+    pub package_manager: String,
+    pub package_manager_version: Option<String>,
+    pub command: String,
+    /// "install", "update" or "reinstall"
+    pub action: String,
+    pub previous_version: Option<String>,
+    pub installed_version: Option<String>,
+    pub success: bool,
+    pub error: Option<String>,
+    pub created_at: String,
+}
+
+/// Streams npm's output as coalesced `queen-install-output` batches while installing
+#[tauri::command]
+pub async fn install_queen_cli(
+    app: AppHandle,
+    db: State<'_, AgentDb>,
+    cli_cache: State<'_, QueenCliStatusCache>,
+) -> Result<String, String> {
+    let previous_version = get_queen_version();
+    let npm_path = which::which("npm")
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|_| "npm".to_string());
+    let npm_version = Command::new("npm")
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string());
+
+    let log = LogBatcher::spawn(app, "queen-install-output", QUEEN_PACKAGE.to_string());
+    let result = output_logged(Command::new("npm").args(["install", "-g", QUEEN_PACKAGE]), &log.sink())
+        .map_err(|e| format!("Failed to execute npm: {}", e))
+        .and_then(|output| {
+            if output.status.success() {
+                Ok(String::from_utf8_lossy(&output.stdout).to_string())
+            } else {
+                Err(format!("Installation failed: {}", String::from_utf8_lossy(&output.stderr)))
+            }
+        });
+
+    log.finish().await;
+
+    let installed_version = if result.is_ok() { get_queen_version() } else { None };
+    let action = match (&previous_version, &installed_version) {
+        (None, _) => "install",
+        (Some(before), Some(after)) if before == after => "reinstall",
+        (Some(_), _) => "update",
+    };
+
+    let record = {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        conn.execute(
+            "INSERT INTO install_history (package_manager, package_manager_version, command, action,
+                previous_version, installed_version, success, error)
+             VALUES ('npm', ?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            rusqlite::params![
+                npm_version,
+                format!("{} install -g {}", npm_path, QUEEN_PACKAGE),
+                action,
+                previous_version,
+                installed_version,
+                result.is_ok(),
+                result.as_ref().err(),
+            ],
+        )
+    };
+    if let Err(e) = record {
+        log::warn!("Failed to record Queen CLI install: {}", e);
+    }
+    cli_cache.invalidate();
+
+    result
+}
+
+#[tauri::command]
+pub fn get_install_history(db: State<'_, AgentDb>) -> Result<Vec<InstallRecord>, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    load_install_history(&conn)
+}
+
+fn load_install_history(conn: &rusqlite::Connection) -> Result<Vec<InstallRecord>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, package_manager, package_manager_version, command, action, previous_version,
+                    installed_version, success, error, created_at
+             FROM install_history ORDER BY id DESC",
+        )
+        .map_err(|e| e.to_string())?;
 
-    Ok(stdout.to_string())
+    let records = stmt
+        .query_map([], |row| {
+            Ok(InstallRecord {
+                id: row.get(0)?,
+                package_manager: row.get(1)?,
+                package_manager_version: row.get(2)?,
+                command: row.get(3)?,
+                action: row.get(4)?,
+                previous_version: row.get(5)?,
+                installed_version: row.get(6)?,
+                success: row.get(7)?,
+                error: row.get(8)?,
+                created_at: row.get(9)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(records)
 }
 
 #[tauri::command]
@@ -92,16 +341,557 @@ pub fn get_queen_templates() -> Vec<TemplateInfo> {
     ]
 }
 
+/// Commands the CLI installs that aren't project templates
+const NON_TEMPLATE_COMMANDS: &[&str] = &["queen-init"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateValidation {
+    pub cli_version: Option<String>,
+    /// `package` when read from the installed CLI's `bin` entries, `path` when
+    /// only the offered templates could be looked up on PATH
+    pub source: String,
+    pub valid: Vec<String>,
+    /// Offered templates the installed CLI can't scaffold
+    pub stale: Vec<String>,
+    /// Templates the CLI provides that aren't offered yet
+    pub missing: Vec<String>,
+    pub up_to_date: bool,
+    pub message: Option<String>,
+}
+
+/// `queen-*` template commands declared in the globally installed CLI's package.json
+fn installed_cli_templates() -> Option<Vec<String>> {
+    let prefix = npm_global_prefix()
+        .inspect_err(|e| log::debug!("Can't locate the global CLI package: {}", e))
+        .ok()?;
+    let modules = if cfg!(target_os = "windows") {
+        std::path::Path::new(&prefix).join("node_modules")
+    } else {
+        std::path::Path::new(&prefix).join("lib").join("node_modules")
+    };
+    let manifest = modules.join(QUEEN_PACKAGE).join("package.json");
+    let content = std::fs::read_to_string(&manifest)
+        .inspect_err(|e| log::debug!("Failed to read {}: {}", manifest.display(), e))
+        .ok()?;
+    let package: serde_json::Value = serde_json::from_str(&content)
+        .inspect_err(|e| log::debug!("Failed to parse {}: {}", manifest.display(), e))
+        .ok()?;
+
+    Some(
+        package["bin"]
+            .as_object()?
+            .keys()
+            .filter(|name| name.starts_with("queen-") && !NON_TEMPLATE_COMMANDS.contains(&name.as_str()))
+            .cloned()
+            .collect(),
+    )
+}
+
+/// Cross-checks `get_queen_templates` against the templates the installed CLI
+/// provides, so the creation flow never offers one it can't scaffold
+#[tauri::command]
+pub async fn validate_templates_against_cli() -> Result<TemplateValidation, String> {
+    let offered: Vec<String> = get_queen_templates().into_iter().map(|t| t.id).collect();
+
+    let (installed, cli_version) = tokio::task::spawn_blocking(|| (installed_cli_templates(), get_queen_version()))
+        .await
+        .map_err(|e| format!("Failed to inspect the Queen CLI: {}", e))?;
+
+    let (source, supported) = match installed {
+        Some(templates) => ("package", templates),
+        None => {
+            let probe = offered.clone();
+            let found = tokio::task::spawn_blocking(move || {
+                probe.into_iter().filter(|t| check_command_exists(t)).collect::<Vec<_>>()
+            })
+            .await
+            .map_err(|e| format!("Failed to inspect the Queen CLI: {}", e))?;
+            ("path", found)
+        }
+    };
+
+    let valid: Vec<String> = offered.iter().filter(|t| supported.contains(t)).cloned().collect();
+    let stale: Vec<String> = offered.iter().filter(|t| !supported.contains(t)).cloned().collect();
+    let mut missing: Vec<String> = supported.into_iter().filter(|t| !offered.contains(t)).collect();
+    missing.sort();
+
+    let message = if valid.is_empty() && cli_version.is_none() {
+        Some("The Queen CLI isn't installed, so no template can be scaffolded".to_string())
+    } else if !stale.is_empty() {
+        Some(format!(
+            "The installed Queen CLI{} can't scaffold {}; update the CLI or hide these templates",
+            cli_version.as_deref().map(|v| format!(" ({})", v)).unwrap_or_default(),
+            stale.join(", ")
+        ))
+    } else if !missing.is_empty() {
+        Some(format!("The Queen CLI also provides {}; update the app to offer them", missing.join(", ")))
+    } else {
+        None
+    };
+
+    Ok(TemplateValidation {
+        cli_version,
+        source: source.to_string(),
+        up_to_date: stale.is_empty() && missing.is_empty(),
+        valid,
+        stale,
+        missing,
+        message,
+    })
+}
+
+const NPM_REGISTRY_URL: &str = "https://registry.npmjs.org";
+const NPM_NAME_CHECK_TIMEOUT_SECS: u64 = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NpmNameCheck {
+    pub name: String,
+    /// None when the check was skipped or the registry couldn't be reached
+    pub available: Option<bool>,
+    pub skipped_reason: Option<String>,
+}
+
+fn offline_mode(db: &AgentDb) -> bool {
+    db.0.lock()
+        .ok()
+        .and_then(|conn| {
+            conn.query_row(
+                "SELECT value FROM app_settings WHERE key = 'offline_mode'",
+                [],
+                |row| row.get::<_, String>(0),
+            )
+            .ok()
+        })
+        .map(|v| v == "true")
+        .unwrap_or(false)
+}
+
+#[tauri::command]
+pub fn get_offline_mode(db: State<'_, AgentDb>) -> bool {
+    offline_mode(&db)
+}
+
+/// Offline mode skips checks that need the network, such as npm name lookups
+#[tauri::command]
+pub fn set_offline_mode(enabled: bool, db: State<'_, AgentDb>) -> Result<(), String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    conn.execute(
+        "INSERT OR REPLACE INTO app_settings (key, value) VALUES ('offline_mode', ?1)",
+        rusqlite::params![enabled.to_string()],
+    )
+    .map_err(|e| format!("Failed to save offline mode: {}", e))?;
+    Ok(())
+}
+
+async fn npm_name_check(name: &str, db: &AgentDb) -> NpmNameCheck {
+    let skipped = |reason: String| NpmNameCheck {
+        name: name.to_string(),
+        available: None,
+        skipped_reason: Some(reason),
+    };
+    if offline_mode(db) {
+        return skipped("Offline mode is on".to_string());
+    }
+
+    let client = match reqwest::Client::builder()
+        .timeout(Duration::from_secs(NPM_NAME_CHECK_TIMEOUT_SECS))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => return skipped(format!("Failed to create HTTP client: {}", e)),
+    };
+
+    // Scoped names keep their @ but the slash must be encoded
+    let url = format!("{}/{}", NPM_REGISTRY_URL, name.replace('/', "%2F"));
+    match client.head(url).send().await {
+        Ok(response) if response.status() == reqwest::StatusCode::NOT_FOUND => NpmNameCheck {
+            name: name.to_string(),
+            available: Some(true),
+            skipped_reason: None,
+        },
+        Ok(response) if response.status().is_success() => NpmNameCheck {
+            name: name.to_string(),
+            available: Some(false),
+            skipped_reason: None,
+        },
+        Ok(response) => skipped(format!("The npm registry answered {}", response.status())),
+        Err(e) => skipped(format!("Couldn't reach the npm registry: {}", e)),
+    }
+}
+
+/// Whether `name` is still free on the npm registry. Never fails: when offline or
+/// the registry is unreachable the result says why it was skipped.
+#[tauri::command]
+pub async fn check_npm_name_available(name: String, db: State<'_, AgentDb>) -> Result<NpmNameCheck, String> {
+    Ok(npm_name_check(name.trim(), &db).await)
+}
+
+/// Versions listed by `get_queen_cli_release_notes`, newest first
+const RELEASE_NOTES_VERSIONS: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueenCliRelease {
+    pub version: String,
+    pub published_at: Option<String>,
+    pub description: Option<String>,
+    /// Set when the version was deprecated on npm, with the maintainer's message
+    pub deprecated: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueenCliReleaseNotes {
+    pub package: String,
+    pub latest_version: Option<String>,
+    pub releases: Vec<QueenCliRelease>,
+    pub changelog_url: String,
+    /// Why nothing was fetched: offline mode or an unreachable registry
+    pub skipped_reason: Option<String>,
+}
+
+fn npm_package_page() -> String {
+    format!("https://www.npmjs.com/package/{}?activeTab=versions", QUEEN_PACKAGE)
+}
+
+/// Changelog URL set by the user, which wins over anything derived from npm
+fn configured_changelog_url(db: &AgentDb) -> Option<String> {
+    db.0.lock()
+        .ok()
+        .and_then(|conn| {
+            conn.query_row(
+                "SELECT value FROM app_settings WHERE key = 'queen_cli_changelog_url'",
+                [],
+                |row| row.get::<_, String>(0),
+            )
+            .ok()
+        })
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+}
+
+/// Sets the changelog URL the release notes link to; an empty URL goes back to
+/// the one derived from npm
+#[tauri::command]
+pub fn set_queen_cli_changelog_url(url: String, db: State<'_, AgentDb>) -> Result<(), String> {
+    let url = url.trim();
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    if url.is_empty() {
+        conn.execute("DELETE FROM app_settings WHERE key = 'queen_cli_changelog_url'", [])
+            .map_err(|e| format!("Failed to clear changelog URL: {}", e))?;
+        return Ok(());
+    }
+    if !url.starts_with("https://") && !url.starts_with("http://") {
+        return Err(format!("'{}' isn't an http(s) URL", url));
+    }
+
+    conn.execute(
+        "INSERT OR REPLACE INTO app_settings (key, value) VALUES ('queen_cli_changelog_url', ?1)",
+        rusqlite::params![url],
+    )
+    .map_err(|e| format!("Failed to save changelog URL: {}", e))?;
+    Ok(())
+}
+
+/// The changelog to link to: the configured URL, then the project's GitHub
+/// releases from the registry metadata, then the npm versions page
+fn resolve_changelog_url(configured_url: Option<String>, metadata: Option<&serde_json::Value>) -> String {
+    configured_url
+        .or_else(|| metadata.and_then(|metadata| github_releases_url(&metadata["repository"])))
+        .unwrap_or_else(npm_package_page)
+}
+
+/// GitHub releases page for a package's `repository` field, if it points at GitHub
+fn github_releases_url(repository: &serde_json::Value) -> Option<String> {
+    let url = repository.get("url").or(Some(repository))?.as_str()?;
+    let path = url.split("github.com").nth(1)?.trim_start_matches([':', '/']);
+    let path = path.trim_end_matches('/').trim_end_matches(".git");
+    (path.split('/').count() == 2).then(|| format!("https://github.com/{}/releases", path))
+}
+
+fn parse_release_notes(metadata: &serde_json::Value, configured_url: Option<String>) -> QueenCliReleaseNotes {
+    let latest_version = metadata["dist-tags"]["latest"].as_str().map(String::from);
+    let text = |value: &serde_json::Value| value.as_str().map(String::from);
+
+    let mut releases: Vec<QueenCliRelease> = metadata["versions"]
+        .as_object()
+        .map(|versions| {
+            versions
+                .iter()
+                .map(|(version, manifest)| QueenCliRelease {
+                    version: version.clone(),
+                    published_at: text(&metadata["time"][version]),
+                    description: text(&manifest["description"]),
+                    deprecated: text(&manifest["deprecated"]),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    releases.sort_by(|a, b| {
+        match (semver::Version::parse(&a.version), semver::Version::parse(&b.version)) {
+            (Ok(a), Ok(b)) => b.cmp(&a),
+            _ => b.published_at.cmp(&a.published_at),
+        }
+    });
+    releases.truncate(RELEASE_NOTES_VERSIONS);
+
+    let changelog_url = resolve_changelog_url(configured_url, Some(metadata));
+
+    QueenCliReleaseNotes {
+        package: QUEEN_PACKAGE.to_string(),
+        latest_version,
+        releases,
+        changelog_url,
+        skipped_reason: None,
+    }
+}
+
+/// Recent Queen CLI versions from the npm registry metadata. Never fails: offline
+/// or when the registry can't be reached the result is empty and says why.
+#[tauri::command]
+pub async fn get_queen_cli_release_notes(db: State<'_, AgentDb>) -> Result<QueenCliReleaseNotes, String> {
+    let configured_url = configured_changelog_url(&db);
+    let skipped = |reason: String| QueenCliReleaseNotes {
+        package: QUEEN_PACKAGE.to_string(),
+        latest_version: None,
+        releases: Vec::new(),
+        changelog_url: resolve_changelog_url(configured_url.clone(), None),
+        skipped_reason: Some(reason),
+    };
+    if offline_mode(&db) {
+        return Ok(skipped("Offline mode is on".to_string()));
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(NPM_NAME_CHECK_TIMEOUT_SECS))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let url = format!("{}/{}", NPM_REGISTRY_URL, QUEEN_PACKAGE.replace('/', "%2F"));
+    let response = match client.get(url).send().await {
+        Ok(response) if response.status().is_success() => response,
+        Ok(response) => return Ok(skipped(format!("The npm registry answered {}", response.status()))),
+        Err(e) => return Ok(skipped(format!("Couldn't reach the npm registry: {}", e))),
+    };
+    match response.json::<serde_json::Value>().await {
+        Ok(metadata) => Ok(parse_release_notes(&metadata, configured_url)),
+        Err(e) => Ok(skipped(format!("Unreadable registry metadata: {}", e))),
+    }
+}
+
+/// Opens the same changelog `get_queen_cli_release_notes` links to in the default browser
+#[tauri::command]
+pub async fn open_queen_cli_changelog(db: State<'_, AgentDb>) -> Result<String, String> {
+    let url = get_queen_cli_release_notes(db).await?.changelog_url;
+    open_with_system_default(&url)?;
+    Ok(url)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueenProjectCreated {
+    pub path: String,
+    /// Set when the path is likely too long for nested dependencies on Windows
+    pub warning: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueenProjectWarning {
+    pub project_name: String,
+    pub message: String,
+}
+
+/// `check_npm_name` looks the name up on npm after creating the project and emits
+/// a `queen-project-warning` if it's taken; it never blocks creation
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn create_queen_project(
     template: String,
     project_name: String,
-    parent_directory: String,
+    parent_directory: Option<String>,
+    git_url: Option<String>,
+    node_version: Option<String>,
+    check_npm_name: Option<bool>,
+    app: AppHandle,
+    db: State<'_, AgentDb>,
+) -> Result<QueenProjectCreated, String> {
+    let warning = path_length_warning(parent_directory.as_deref(), &project_name, &db);
+    let (log, transcript) = start_create_log(&app, &project_name);
+    let result = scaffold_project(
+        template,
+        project_name.clone(),
+        parent_directory,
+        git_url,
+        node_version,
+        &db,
+        Some(&log.sink()),
+    );
+    log.finish().await;
+    let path = with_path_warning(
+        finish_create_log(&db, transcript, &project_name, result),
+        warning.as_ref(),
+    )?;
+
+    if check_npm_name.unwrap_or(false) && npm_name_check(&project_name, &db).await.available == Some(false) {
+        let _ = app.emit(
+            "queen-project-warning",
+            &QueenProjectWarning {
+                message: format!(
+                    "'{}' is already taken on npm; rename the package before publishing it",
+                    project_name
+                ),
+                project_name,
+            },
+        );
+    }
+
+    Ok(QueenProjectCreated { path, warning })
+}
+
+/// Scaffold transcripts kept in `create-logs`; older ones are pruned
+const CREATE_LOG_LIMIT: usize = 20;
+
+fn create_logs_dir(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join("create-logs");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    Ok(dir)
+}
+
+/// Streams scaffold output as `queen-create-output` and into a pending transcript
+/// that `finish_create_log` names after the project once it's tracked
+fn start_create_log(app: &AppHandle, project_name: &str) -> (LogBatcher, Option<std::path::PathBuf>) {
+    let transcript = create_logs_dir(app).and_then(|dir| {
+        let path = dir.join(format!("pending-{}.log", uuid::Uuid::new_v4()));
+        std::fs::File::create(&path)
+            .map(|file| (file, path))
+            .map_err(|e| e.to_string())
+    });
+
+    match transcript {
+        Ok((file, path)) => (
+            LogBatcher::spawn_with_transcript(app.clone(), "queen-create-output", project_name.to_string(), file),
+            Some(path),
+        ),
+        Err(e) => {
+            log::warn!("Scaffold output for {} won't be saved: {}", project_name, e);
+            (LogBatcher::spawn(app.clone(), "queen-create-output", project_name.to_string()), None)
+        }
+    }
+}
+
+/// Renames the transcript to `<project id>.log`, or `failed-<timestamp>-<name>.log`
+/// (pointed to from the error) when scaffolding failed, then prunes old transcripts
+fn finish_create_log(
+    db: &AgentDb,
+    transcript: Option<std::path::PathBuf>,
+    project_name: &str,
+    result: Result<String, String>,
+) -> Result<String, String> {
+    let Some(pending) = transcript else {
+        return result;
+    };
+    let Some(dir) = pending.parent().map(|dir| dir.to_path_buf()) else {
+        return result;
+    };
+
+    let result = match result {
+        Ok(path) => {
+            match load_tracked_projects(db)
+                .ok()
+                .and_then(|projects| projects.into_iter().find(|p| p.path == path))
+            {
+                Some(project) => {
+                    let _ = std::fs::rename(&pending, dir.join(format!("{}.log", project.id)));
+                }
+                None => {
+                    let _ = std::fs::remove_file(&pending);
+                }
+            }
+            Ok(path)
+        }
+        Err(error) => {
+            let failed = dir.join(format!(
+                "failed-{}-{}.log",
+                chrono::Local::now().format("%Y%m%d-%H%M%S"),
+                project_name
+            ));
+            match std::fs::rename(&pending, &failed) {
+                Ok(()) => Err(format!("{} (full output: {})", error, failed.display())),
+                Err(_) => Err(error),
+            }
+        }
+    };
+
+    prune_create_logs(&dir);
+    result
+}
+
+fn prune_create_logs(dir: &std::path::Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut logs: Vec<(std::time::SystemTime, std::path::PathBuf)> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| !e.file_name().to_string_lossy().starts_with("pending-"))
+        .filter_map(|e| Some((e.metadata().ok()?.modified().ok()?, e.path())))
+        .collect();
+    logs.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+
+    for (_, path) in logs.into_iter().skip(CREATE_LOG_LIMIT) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+fn create_log_path(app: &AppHandle, project_id: i64) -> Result<std::path::PathBuf, String> {
+    let path = create_logs_dir(app)?.join(format!("{}.log", project_id));
+    if !path.is_file() {
+        return Err(format!("No scaffold log is kept for project {}", project_id));
+    }
+    Ok(path)
+}
+
+/// Everything the template and queen-init printed while the project was created
+#[tauri::command]
+pub fn get_create_log(project_id: i64, app: AppHandle) -> Result<String, String> {
+    let path = create_log_path(&app, project_id)?;
+    std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+/// Opens the project's scaffold log in the default text viewer, returning its path
+#[tauri::command]
+pub fn open_create_log(project_id: i64, app: AppHandle) -> Result<String, String> {
+    let path = create_log_path(&app, project_id)?.to_string_lossy().to_string();
+    open_with_system_default(&path)?;
+    Ok(path)
+}
+
+/// The requested parent directory, or the active projects directory when none is given
+fn resolve_parent_directory(parent_directory: Option<String>, db: &AgentDb) -> Result<String, String> {
+    match parent_directory.filter(|dir| !dir.trim().is_empty()) {
+        Some(dir) => Ok(dir),
+        None => active_projects_directory(&*db.0.lock().map_err(|e| e.to_string())?),
+    }
+}
+
+fn scaffold_project(
+    template: String,
+    project_name: String,
+    parent_directory: Option<String>,
+    git_url: Option<String>,
+    node_version: Option<String>,
+    db: &AgentDb,
+    log: Option<&LogSink>,
 ) -> Result<String, String> {
+    let run = |command: &mut Command| match log {
+        Some(sink) => output_logged(command, sink),
+        None => command.stdout(Stdio::piped()).stderr(Stdio::piped()).output(),
+    };
+
     if !validate_project_name(&project_name) {
         return Err("Invalid project name. Use lowercase letters, dashes only, max 25 characters.".to_string());
     }
 
+    let parent_directory = resolve_parent_directory(parent_directory, db)?;
     let parent_path = std::path::Path::new(&parent_directory);
     let project_path = parent_path.join(&project_name);
 
@@ -114,24 +904,50 @@ pub async fn create_queen_project(
             .map_err(|e| format!("Failed to create parent directory: {}", e))?;
     }
 
-    let output = Command::new(&template)
-        .arg(&project_name)
-        .current_dir(&parent_directory)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
-        .map_err(|e| format!("Failed to execute {}: {}", template, e))?;
+    let node_version = node_version.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+    let version_manager = match &node_version {
+        Some(version) => {
+            if !validate_node_version(version) {
+                return Err(format!("Invalid node version: {}", version));
+            }
+            let preferred = db
+                .0
+                .lock()
+                .map_err(|e| e.to_string())?
+                .query_row(
+                    "SELECT value FROM app_settings WHERE key = 'node_version_manager'",
+                    [],
+                    |row| row.get::<_, String>(0),
+                )
+                .ok();
+            let manager = select_node_version_manager(preferred.as_deref());
+            if manager.is_none() {
+                log::warn!("No node version manager found; scaffolding {} with the system node instead of {}", project_name, version);
+            }
+            manager
+        }
+        None => None,
+    };
+    let runtime = node_version.as_deref().zip(version_manager);
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Project creation failed: {}", stderr));
+    if template == "git" {
+        let git_url = git_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .ok_or("A git URL is required for the git template")?;
+        clone_git_template(git_url, &project_name, parent_path)?;
+    } else {
+        let output = run(node_command(&template, runtime).arg(&project_name).current_dir(&parent_directory))
+            .map_err(|e| format!("Failed to execute {}: {}", template, e))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("Project creation failed: {}", stderr));
+        }
     }
 
-    let init_output = Command::new("queen-init")
-        .current_dir(&project_path)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
+    let init_output = run(node_command("queen-init", runtime).current_dir(&project_path))
         .map_err(|e| format!("Failed to execute queen-init: {}", e))?;
 
     if !init_output.status.success() {
@@ -139,28 +955,786 @@ pub async fn create_queen_project(
         return Err(format!("queen-init failed: {}", stderr));
     }
 
+    let template_source = if template == "git" { git_url.as_deref() } else { Some(template.as_str()) };
+    if let Err(e) = track_project(db, &project_path, template_source) {
+        log::warn!("Failed to track created project: {}", e);
+    }
+
     Ok(project_path.to_string_lossy().to_string())
 }
 
-fn check_command_exists(command: &str) -> bool {
-    Command::new("which")
-        .arg(command)
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .status()
-        .map(|status| status.success())
-        .unwrap_or(false)
+/// Files and directories the Queen foundation writes into a project, backed up
+/// before `reinit_queen_project` lets queen-init overwrite them
+const QUEEN_FOUNDATION_FILES: &[&str] = &["CLAUDE.md", "AGENTS.md", ".claude", ".mcp.json"];
+
+/// Markers of a directory that is safe to run queen-init in
+const PROJECT_MARKERS: &[&str] = &["package.json", "Cargo.toml", "pyproject.toml", "go.mod", "CLAUDE.md", ".claude"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReinitResult {
+    pub path: String,
+    pub backup_dir: Option<String>,
+    pub backed_up: Vec<String>,
+    /// Files queen-init added, modified or deleted, for review before committing
+    pub git_changes: Vec<GitFileChange>,
+    /// Set when uncommitted changes were stashed before init
+    pub stash_message: Option<String>,
+    pub warning: Option<String>,
 }
 
-fn get_queen_version() -> Option<String> {
-    let output = Command::new("npm")
-        .args(&["list", "-g", "@kenkaiiii/queen-claude", "--depth=0"])
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
-        .ok()?;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitFileChange {
+    pub path: String,
+    /// One of `added`, `modified`, `deleted`
+    pub change: String,
+}
 
-    if output.status.success() {
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReinitGitStatus {
+    pub is_git_repo: bool,
+    pub dirty: bool,
+    /// Uncommitted changes that queen-init's changes would get mixed into
+    pub uncommitted: Vec<GitFileChange>,
+    pub warning: Option<String>,
+}
+
+/// `git status` entries under the project as project-relative path -> (XY code, content hash),
+/// or None outside a git repo. The hash catches init changing a file that was already modified.
+type GitSnapshot = BTreeMap<String, (String, Option<Vec<u8>>)>;
+
+/// The project's path inside its repository (`sub/dir/`, empty at the root); git status
+/// reports paths relative to the repository root, not the working directory
+fn git_project_prefix(project_path: &std::path::Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--show-prefix"])
+        .current_dir(project_path)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn git_status_snapshot(project_path: &std::path::Path) -> Option<GitSnapshot> {
+    use sha2::Digest;
+
+    let prefix = git_project_prefix(project_path)?;
+    let output = Command::new("git")
+        .args(["status", "--porcelain=v1", "-z", "--untracked-files=all", "--", "."])
+        .current_dir(project_path)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut entries = stdout.split('\0').filter(|e| !e.is_empty());
+    let mut snapshot = GitSnapshot::new();
+    while let Some(entry) = entries.next() {
+        let (Some(code), Some(repo_path)) = (entry.get(..2), entry.get(3..)) else {
+            continue;
+        };
+        // Renames and copies are followed by their original path
+        if code.starts_with(['R', 'C']) {
+            entries.next();
+        }
+        let path = repo_path.strip_prefix(prefix.as_str()).unwrap_or(repo_path);
+        let hash = std::fs::read(project_path.join(path))
+            .ok()
+            .map(|bytes| sha2::Sha256::digest(&bytes).to_vec());
+        snapshot.insert(path.to_string(), (code.to_string(), hash));
+    }
+
+    Some(snapshot)
+}
+
+fn git_change_kind(code: &str) -> &'static str {
+    if code == "??" || code.contains('A') {
+        "added"
+    } else if code.contains('D') {
+        "deleted"
+    } else {
+        "modified"
+    }
+}
+
+/// Entries that appeared or changed between two snapshots, ignoring the reinit backup
+fn git_snapshot_changes(before: &GitSnapshot, after: &GitSnapshot) -> Vec<GitFileChange> {
+    let mut changes: Vec<GitFileChange> = after
+        .iter()
+        .filter(|(path, _)| !path.starts_with(".queen-backup-"))
+        .filter(|(path, entry)| before.get(*path) != Some(*entry))
+        .map(|(path, (code, _))| GitFileChange {
+            path: path.clone(),
+            change: git_change_kind(code).to_string(),
+        })
+        .collect();
+
+    // Previously dirty files init restored to their committed state
+    changes.extend(
+        before
+            .keys()
+            .filter(|path| !after.contains_key(*path))
+            .map(|path| GitFileChange {
+                path: path.clone(),
+                change: "modified".to_string(),
+            }),
+    );
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    changes
+}
+
+fn reinit_git_status(project_path: &std::path::Path) -> ReinitGitStatus {
+    let Some(snapshot) = git_status_snapshot(project_path) else {
+        return ReinitGitStatus {
+            is_git_repo: false,
+            dirty: false,
+            uncommitted: Vec::new(),
+            warning: None,
+        };
+    };
+
+    let uncommitted: Vec<GitFileChange> = snapshot
+        .iter()
+        .map(|(path, (code, _))| GitFileChange {
+            path: path.clone(),
+            change: git_change_kind(code).to_string(),
+        })
+        .collect();
+    let warning = (!uncommitted.is_empty()).then(|| {
+        format!(
+            "{} uncommitted change(s) would be mixed with queen-init's; commit or stash them first",
+            uncommitted.len()
+        )
+    });
+
+    ReinitGitStatus {
+        is_git_repo: true,
+        dirty: !uncommitted.is_empty(),
+        uncommitted,
+        warning,
+    }
+}
+
+/// Uncommitted changes in a project about to be reinitialized, so the user can
+/// commit or stash before queen-init runs
+#[tauri::command]
+pub fn check_reinit_git_status(path: String) -> Result<ReinitGitStatus, String> {
+    let project_path = std::fs::canonicalize(&path).map_err(|e| format!("Invalid project path '{}': {}", path, e))?;
+    Ok(reinit_git_status(&project_path))
+}
+
+fn git_stash(project_path: &std::path::Path, message: &str) -> Result<(), String> {
+    let output = Command::new("git")
+        .args(["stash", "push", "--include-untracked", "-m", message, "--", "."])
+        .current_dir(project_path)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|e| format!("Failed to execute git: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("git stash failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(())
+}
+
+/// Copies a file or directory tree. Symlinks are recreated as links rather than
+/// followed, so a link back into the project can't make the copy recurse forever.
+fn copy_recursively(from: &std::path::Path, to: &std::path::Path) -> std::io::Result<()> {
+    let metadata = std::fs::symlink_metadata(from)?;
+    if metadata.is_symlink() {
+        copy_symlink(from, to)
+    } else if metadata.is_dir() {
+        std::fs::create_dir_all(to)?;
+        for entry in std::fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursively(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        if let Some(parent) = to.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(from, to).map(|_| ())
+    }
+}
+
+fn copy_symlink(from: &std::path::Path, to: &std::path::Path) -> std::io::Result<()> {
+    let target = std::fs::read_link(from)?;
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(&target, to)
+    }
+
+    #[cfg(windows)]
+    {
+        if from.is_dir() {
+            std::os::windows::fs::symlink_dir(&target, to)
+        } else {
+            std::os::windows::fs::symlink_file(&target, to)
+        }
+    }
+}
+
+/// Re-applies the Queen foundation to an existing project by running queen-init
+/// in it, streaming its output as coalesced `queen-init-output` batches. Foundation files are
+/// copied to `.queen-backup-<timestamp>` first. In a git repo the files init changed are
+/// reported; `stash_changes` stashes uncommitted work beforehand.
+#[tauri::command]
+pub async fn reinit_queen_project(
+    path: String,
+    stash_changes: Option<bool>,
+    app: AppHandle,
+) -> Result<ReinitResult, String> {
+    let project_path = std::fs::canonicalize(&path).map_err(|e| format!("Invalid project path '{}': {}", path, e))?;
+    if !project_path.is_dir() {
+        return Err(format!("'{}' is not a directory", path));
+    }
+    if !PROJECT_MARKERS.iter().any(|m| project_path.join(m).exists()) {
+        return Err(format!(
+            "'{}' doesn't look like a project (none of {} found)",
+            path,
+            PROJECT_MARKERS.join(", ")
+        ));
+    }
+
+    let git_status = reinit_git_status(&project_path);
+    let mut stash_message = None;
+    let mut warning = None;
+    if git_status.dirty {
+        if stash_changes.unwrap_or(false) {
+            let message = format!("Before queen-init {}", chrono::Local::now().format("%Y-%m-%d %H:%M:%S"));
+            git_stash(&project_path, &message)?;
+            stash_message = Some(message);
+        } else {
+            warning = git_status.warning;
+        }
+    }
+    let before = git_status_snapshot(&project_path);
+
+    let existing: Vec<&str> = QUEEN_FOUNDATION_FILES
+        .iter()
+        .copied()
+        .filter(|f| project_path.join(f).exists())
+        .collect();
+    let backup_dir = if existing.is_empty() {
+        None
+    } else {
+        let dir = project_path.join(format!(".queen-backup-{}", chrono::Local::now().format("%Y%m%d-%H%M%S")));
+        for file in &existing {
+            copy_recursively(&project_path.join(file), &dir.join(file))
+                .map_err(|e| format!("Failed to back up {}: {}", file, e))?;
+        }
+        Some(dir)
+    };
+
+    let path_str = project_path.to_string_lossy().to_string();
+    let log = LogBatcher::spawn(app, "queen-init-output", path_str.clone());
+    let sink = log.sink();
+    let init_path = project_path.clone();
+    let output = tokio::task::spawn_blocking(move || {
+        output_logged(node_command("queen-init", None).current_dir(&init_path), &sink)
+    })
+    .await
+    .map_err(|e| format!("queen-init task failed: {}", e))?
+    .map_err(|e| format!("Failed to execute queen-init: {}", e));
+    log.finish().await;
+    let output = output?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let backup_note = backup_dir
+            .as_ref()
+            .map(|dir| format!(" Your previous files are in {}.", dir.display()))
+            .unwrap_or_default();
+        return Err(format!("queen-init failed: {}{}", stderr, backup_note));
+    }
+
+    let git_changes = match (before, git_status_snapshot(&project_path)) {
+        (Some(before), Some(after)) => git_snapshot_changes(&before, &after),
+        _ => Vec::new(),
+    };
+
+    Ok(ReinitResult {
+        path: path_str,
+        backup_dir: backup_dir.map(|d| d.to_string_lossy().to_string()),
+        backed_up: existing.iter().map(|f| f.to_string()).collect(),
+        git_changes,
+        stash_message,
+        warning,
+    })
+}
+
+const DEFAULT_CREATE_CONCURRENCY: usize = 2;
+const MAX_CREATE_CONCURRENCY: usize = 16;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectSpec {
+    pub template: String,
+    pub project_name: String,
+    pub parent_directory: Option<String>,
+    pub git_url: Option<String>,
+    pub node_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchCreateProgress {
+    pub batch_id: String,
+    pub project_name: String,
+    /// queued, running, completed or failed
+    pub status: String,
+    /// 1-based place in line while queued
+    pub queue_position: Option<usize>,
+    pub path: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchCreateResult {
+    pub project_name: String,
+    pub path: Option<String>,
+    pub error: Option<String>,
+    pub warning: Option<String>,
+}
+
+fn create_concurrency(conn: &rusqlite::Connection) -> usize {
+    conn.query_row(
+        "SELECT value FROM app_settings WHERE key = 'create_concurrency'",
+        [],
+        |row| row.get::<_, String>(0),
+    )
+    .ok()
+    .and_then(|v| v.parse::<usize>().ok())
+    .filter(|n| *n > 0)
+    .unwrap_or(DEFAULT_CREATE_CONCURRENCY)
+}
+
+#[tauri::command]
+pub fn get_create_concurrency(db: State<'_, AgentDb>) -> Result<usize, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    Ok(create_concurrency(&conn))
+}
+
+/// How many projects `create_queen_projects` scaffolds at once
+#[tauri::command]
+pub fn set_create_concurrency(n: usize, db: State<'_, AgentDb>) -> Result<(), String> {
+    if n == 0 || n > MAX_CREATE_CONCURRENCY {
+        return Err(format!("Concurrency must be between 1 and {}", MAX_CREATE_CONCURRENCY));
+    }
+
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    conn.execute(
+        "INSERT OR REPLACE INTO app_settings (key, value) VALUES (?1, ?2)",
+        rusqlite::params!["create_concurrency", n.to_string()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+fn emit_batch_progress(app: &AppHandle, progress: BatchCreateProgress) {
+    if let Err(e) = app.emit("queen-batch-progress", &progress) {
+        log::warn!("Failed to emit batch progress: {}", e);
+    }
+}
+
+/// Tells every project still waiting for a slot where it now stands in line
+fn emit_queue_positions(app: &AppHandle, batch_id: &str, waiting: &[String]) {
+    for (index, project_name) in waiting.iter().enumerate() {
+        emit_batch_progress(
+            app,
+            BatchCreateProgress {
+                batch_id: batch_id.to_string(),
+                project_name: project_name.clone(),
+                status: "queued".to_string(),
+                queue_position: Some(index + 1),
+                path: None,
+                error: None,
+            },
+        );
+    }
+}
+
+/// Scaffolds several projects, a few at a time (see `set_create_concurrency`), emitting
+/// `queen-batch-progress` events as each one is queued, starts and finishes
+#[tauri::command]
+pub async fn create_queen_projects(
+    projects: Vec<ProjectSpec>,
+    app: AppHandle,
+    db: State<'_, AgentDb>,
+) -> Result<Vec<BatchCreateResult>, String> {
+    let limit = create_concurrency(&*db.0.lock().map_err(|e| e.to_string())?);
+    let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(limit));
+    let batch_id = uuid::Uuid::new_v4().to_string();
+
+    // Permits are handed out first come, first served, so spec order is queue order
+    let waiting = Mutex::new(
+        projects
+            .iter()
+            .skip(limit)
+            .map(|p| p.project_name.clone())
+            .collect::<Vec<_>>(),
+    );
+    emit_queue_positions(&app, &batch_id, &waiting.lock().map_err(|e| e.to_string())?);
+
+    let jobs = projects.into_iter().map(|spec| {
+        let app = app.clone();
+        let semaphore = semaphore.clone();
+        let batch_id = batch_id.clone();
+        let waiting = &waiting;
+        async move {
+            let project_name = spec.project_name.clone();
+            let _permit = semaphore.acquire_owned().await.ok();
+
+            if let Ok(mut waiting) = waiting.lock() {
+                if let Some(index) = waiting.iter().position(|name| *name == project_name) {
+                    waiting.remove(index);
+                    emit_queue_positions(&app, &batch_id, &waiting);
+                }
+            }
+            let progress = |status: &str, path: Option<String>, error: Option<String>| BatchCreateProgress {
+                batch_id: batch_id.clone(),
+                project_name: project_name.clone(),
+                status: status.to_string(),
+                queue_position: None,
+                path,
+                error,
+            };
+            emit_batch_progress(&app, progress("running", None, None));
+
+            let warning = path_length_warning(spec.parent_directory.as_deref(), &project_name, &app.state::<AgentDb>());
+            let handle = app.clone();
+            let (log, transcript) = start_create_log(&app, &project_name);
+            let sink = log.sink();
+            let result = tokio::task::spawn_blocking(move || {
+                let db = handle.state::<AgentDb>();
+                scaffold_project(
+                    spec.template,
+                    spec.project_name,
+                    spec.parent_directory,
+                    spec.git_url,
+                    spec.node_version,
+                    &db,
+                    Some(&sink),
+                )
+            })
+            .await
+            .map_err(|e| format!("Project creation task failed: {}", e))
+            .and_then(|r| r);
+            log.finish().await;
+            let result = with_path_warning(
+                finish_create_log(&app.state::<AgentDb>(), transcript, &project_name, result),
+                warning.as_ref(),
+            );
+
+            match &result {
+                Ok(path) => emit_batch_progress(&app, progress("completed", Some(path.clone()), None)),
+                Err(e) => emit_batch_progress(&app, progress("failed", None, Some(e.clone()))),
+            }
+
+            BatchCreateResult {
+                project_name,
+                warning,
+                path: result.as_ref().ok().cloned(),
+                error: result.err(),
+            }
+        }
+    });
+
+    Ok(futures::future::join_all(jobs).await)
+}
+
+const NODE_VERSION_MANAGERS: &[&str] = &["fnm", "volta", "nvm"];
+
+fn validate_node_version(version: &str) -> bool {
+    regex::Regex::new(r"^v?\d+(\.\d+){0,2}$|^lts/[\w*-]+$")
+        .map(|re| re.is_match(version))
+        .unwrap_or(false)
+}
+
+/// nvm is a shell function rather than a binary, so look for its script instead
+fn nvm_script() -> Option<std::path::PathBuf> {
+    std::env::var_os("NVM_DIR")
+        .map(std::path::PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".nvm")))
+        .map(|dir| dir.join("nvm.sh"))
+        .filter(|script| script.exists())
+}
+
+fn is_node_version_manager_available(manager: &str) -> bool {
+    match manager {
+        "nvm" => nvm_script().is_some(),
+        other => which::which(other).is_ok(),
+    }
+}
+
+fn select_node_version_manager(preferred: Option<&str>) -> Option<&'static str> {
+    NODE_VERSION_MANAGERS
+        .iter()
+        .copied()
+        .find(|m| Some(*m) == preferred && is_node_version_manager_available(m))
+        .or_else(|| {
+            NODE_VERSION_MANAGERS
+                .iter()
+                .copied()
+                .find(|m| is_node_version_manager_available(m))
+        })
+}
+
+/// Builds a command for `program`, run under `(node_version, manager)` when given
+fn node_command(program: &str, runtime: Option<(&str, &str)>) -> Command {
+    match runtime {
+        Some((version, "fnm")) => {
+            let mut command = Command::new("fnm");
+            command.args(["exec", "--using", version, program]);
+            command
+        }
+        Some((version, "volta")) => {
+            let mut command = Command::new("volta");
+            command.args(["run", "--node", version.trim_start_matches('v'), program]);
+            command
+        }
+        Some((version, "nvm")) => {
+            let script = nvm_script().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+            // Pass everything as positional arguments so nothing needs shell quoting
+            let mut command = Command::new("bash");
+            command.args([
+                "-c",
+                r#"source "$0" >/dev/null && nvm exec "$@""#,
+                &script,
+                version,
+                program,
+            ]);
+            command
+        }
+        _ => Command::new(program),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeVersionManagers {
+    pub available: Vec<String>,
+    pub preferred: Option<String>,
+}
+
+#[tauri::command]
+pub fn get_node_version_managers(db: State<'_, AgentDb>) -> Result<NodeVersionManagers, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let preferred = conn
+        .query_row(
+            "SELECT value FROM app_settings WHERE key = 'node_version_manager'",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .ok()
+        .filter(|m| !m.is_empty());
+
+    Ok(NodeVersionManagers {
+        available: NODE_VERSION_MANAGERS
+            .iter()
+            .filter(|m| is_node_version_manager_available(m))
+            .map(|m| m.to_string())
+            .collect(),
+        preferred,
+    })
+}
+
+#[tauri::command]
+pub fn set_node_version_manager(manager: Option<String>, db: State<'_, AgentDb>) -> Result<(), String> {
+    if let Some(manager) = &manager {
+        if !NODE_VERSION_MANAGERS.contains(&manager.as_str()) {
+            return Err(format!("Unsupported version manager '{}'; use fnm, volta or nvm", manager));
+        }
+    }
+
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    conn.execute(
+        "INSERT OR REPLACE INTO app_settings (key, value) VALUES (?1, ?2)",
+        rusqlite::params!["node_version_manager", manager.unwrap_or_default()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+const WINDOWS_MAX_PATH: usize = 260;
+/// Room taken by deeply nested dependency files, e.g.
+/// `\node_modules\.pnpm\@scope+package@1.2.3\node_modules\@scope\package\dist\esm\index.js`
+const NESTED_DEPENDENCY_ALLOWANCE: usize = 120;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathLengthCheck {
+    pub base_length: usize,
+    pub estimated_max_length: usize,
+    pub limit: usize,
+    pub long_paths_enabled: Option<bool>,
+    pub at_risk: bool,
+    pub suggestion: Option<String>,
+}
+
+#[cfg(target_os = "windows")]
+fn windows_long_paths_enabled() -> Option<bool> {
+    let output = Command::new("reg")
+        .args([
+            "query",
+            r"HKLM\SYSTEM\CurrentControlSet\Control\FileSystem",
+            "/v",
+            "LongPathsEnabled",
+        ])
+        .output()
+        .ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    Some(text.contains("0x1"))
+}
+
+fn estimate_path_length(parent: &std::path::Path, project_name: &str) -> PathLengthCheck {
+    let base_length = parent.join(project_name).to_string_lossy().chars().count();
+    let estimated_max_length = base_length + NESTED_DEPENDENCY_ALLOWANCE;
+
+    #[cfg(target_os = "windows")]
+    let long_paths_enabled = windows_long_paths_enabled();
+    #[cfg(not(target_os = "windows"))]
+    let long_paths_enabled: Option<bool> = None;
+
+    // MAX_PATH only applies on Windows, and not at all once long paths are enabled
+    let at_risk = cfg!(target_os = "windows")
+        && long_paths_enabled != Some(true)
+        && estimated_max_length > WINDOWS_MAX_PATH;
+
+    let suggestion = at_risk.then(|| {
+        format!(
+            "The project path is {} characters; nested dependencies would likely exceed Windows' {}-character limit. \
+             Choose a shorter parent directory (e.g. C:\\dev) or enable long path support \
+             (set HKLM\\SYSTEM\\CurrentControlSet\\Control\\FileSystem\\LongPathsEnabled to 1).",
+            base_length, WINDOWS_MAX_PATH
+        )
+    });
+
+    PathLengthCheck {
+        base_length,
+        estimated_max_length,
+        limit: WINDOWS_MAX_PATH,
+        long_paths_enabled,
+        at_risk,
+        suggestion,
+    }
+}
+
+/// The path length suggestion for a project about to be created. It never blocks
+/// creation: it's returned with the result, or appended to the error if scaffolding fails.
+fn path_length_warning(parent_directory: Option<&str>, project_name: &str, db: &AgentDb) -> Option<String> {
+    let parent = resolve_parent_directory(parent_directory.map(str::to_string), db).ok()?;
+    estimate_path_length(std::path::Path::new(&parent), project_name).suggestion
+}
+
+fn with_path_warning(result: Result<String, String>, warning: Option<&String>) -> Result<String, String> {
+    match warning {
+        Some(warning) => result.map_err(|e| format!("{}\n\n{}", e, warning)),
+        None => result,
+    }
+}
+
+#[tauri::command]
+pub fn check_path_length(parent: String, name: String) -> PathLengthCheck {
+    estimate_path_length(std::path::Path::new(&parent), &name)
+}
+
+fn validate_git_url(url: &str) -> bool {
+    // git (and ssh) would read a leading `-` in the user or host as an option
+    if url.starts_with('-') {
+        return false;
+    }
+    let https = regex::Regex::new(r"^https?://[\w.-]+(:\d+)?/[\w.~/-]+?(\.git)?/?$").unwrap();
+    let ssh = regex::Regex::new(r"^(ssh://)?\w[\w.-]*@\w[\w.-]*[:/][\w.~/-]+?(\.git)?$").unwrap();
+
+    https.is_match(url) || ssh.is_match(url)
+}
+
+fn clone_git_template(git_url: &str, project_name: &str, parent_path: &std::path::Path) -> Result<(), String> {
+    if !validate_git_url(git_url) {
+        return Err(format!("Invalid git URL: {}", git_url));
+    }
+
+    let output = Command::new("git")
+        .args(["clone", "--depth", "1", "--", git_url, project_name])
+        .current_dir(parent_path)
+        // Fail instead of hanging on a credential prompt nobody can answer
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .env("GIT_SSH_COMMAND", "ssh -o BatchMode=yes")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|e| format!("Failed to execute git: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let lower = stderr.to_lowercase();
+        let _ = std::fs::remove_dir_all(parent_path.join(project_name));
+
+        if lower.contains("authentication failed")
+            || lower.contains("could not read username")
+            || lower.contains("permission denied")
+            || lower.contains("terminal prompts disabled")
+        {
+            return Err(format!(
+                "Could not access {}: authentication required. Check the URL or configure git credentials/SSH keys for this host.",
+                git_url
+            ));
+        }
+        if lower.contains("repository not found") || lower.contains("does not appear to be a git repository") {
+            return Err(format!("Repository not found: {}", git_url));
+        }
+
+        return Err(format!("git clone failed: {}", stderr));
+    }
+
+    std::fs::remove_dir_all(parent_path.join(project_name).join(".git"))
+        .map_err(|e| format!("Failed to remove template git history: {}", e))?;
+
+    Ok(())
+}
+
+fn check_command_exists(command: &str) -> bool {
+    match Command::new("which")
+        .arg(command)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+    {
+        Ok(status) => {
+            if !status.success() {
+                log::debug!("{} not found on PATH", command);
+            }
+            status.success()
+        }
+        Err(e) => {
+            log::debug!("Failed to run which for {}: {}", command, e);
+            false
+        }
+    }
+}
+
+fn get_queen_version() -> Option<String> {
+    let output = Command::new("npm")
+        .args(&["list", "-g", QUEEN_PACKAGE, "--depth=0"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .inspect_err(|e| log::debug!("Failed to run npm list for the Queen CLI version: {}", e))
+        .ok()?;
+
+    if output.status.success() {
         let stdout = String::from_utf8_lossy(&output.stdout);
         for line in stdout.lines() {
             if line.contains("@kenkaiiii/queen-claude@") {
@@ -169,6 +1743,13 @@ fn get_queen_version() -> Option<String> {
                 }
             }
         }
+        log::debug!("{} is not listed in the global npm packages", QUEEN_PACKAGE);
+    } else {
+        log::debug!(
+            "npm list -g exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
     }
 
     None
@@ -182,10 +1763,17 @@ fn validate_project_name(name: &str) -> bool {
     name.chars().all(|c| c.is_ascii_lowercase() || c == '-')
 }
 
-#[tauri::command]
-pub fn get_queen_projects_directory(db: State<'_, AgentDb>) -> Result<String, String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectsDirectory {
+    pub name: String,
+    pub path: String,
+    pub active: bool,
+    /// Set when the directory is inside a cloud-synced folder
+    pub sync_warning: Option<String>,
+}
 
+/// The active directory lives in `queen_projects_directory` so the single-value commands keep working
+fn active_projects_directory(conn: &rusqlite::Connection) -> Result<String, String> {
     if let Ok(dir) = conn.query_row(
         "SELECT value FROM app_settings WHERE key = 'queen_projects_directory'",
         [],
@@ -194,11 +1782,7 @@ pub fn get_queen_projects_directory(db: State<'_, AgentDb>) -> Result<String, St
         return Ok(dir);
     }
 
-    let default_dir = dirs::home_dir()
-        .ok_or_else(|| "Could not find home directory".to_string())?
-        .join("queen-projects")
-        .to_string_lossy()
-        .to_string();
+    let default_dir = default_projects_directory()?;
 
     conn.execute(
         "INSERT OR REPLACE INTO app_settings (key, value) VALUES (?1, ?2)",
@@ -209,18 +1793,1275 @@ pub fn get_queen_projects_directory(db: State<'_, AgentDb>) -> Result<String, St
     Ok(default_dir)
 }
 
-#[tauri::command]
-pub fn set_queen_projects_directory(
-    directory: String,
-    db: State<'_, AgentDb>,
-) -> Result<(), String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
+fn default_projects_directory() -> Result<String, String> {
+    Ok(dirs::home_dir()
+        .ok_or_else(|| "Could not find home directory".to_string())?
+        .join("queen-projects")
+        .to_string_lossy()
+        .to_string())
+}
 
+/// Places people commonly keep their code, relative to the home directory
+const COMMON_CODE_DIRECTORIES: &[&str] = &["Projects", "projects", "dev", "code", "Code", "Developer", "src", "repos"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeDirectoryCandidate {
+    pub path: String,
+    pub repo_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectsDirectorySuggestion {
+    pub current: String,
+    /// The most populated existing code directory, offered only while the
+    /// untouched `~/queen-projects` default is active and not dismissed
+    pub suggested: Option<String>,
+    pub candidates: Vec<CodeDirectoryCandidate>,
+}
+
+/// Immediate subdirectories that look like projects
+fn count_repos(dir: &std::path::Path) -> usize {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| {
+                    let path = e.path();
+                    path.join(".git").exists() || path.join("package.json").is_file() || path.join("Cargo.toml").is_file()
+                })
+                .count()
+        })
+        .unwrap_or(0)
+}
+
+#[tauri::command]
+pub fn suggest_projects_directory(db: State<'_, AgentDb>) -> Result<ProjectsDirectorySuggestion, String> {
+    let home = dirs::home_dir().ok_or_else(|| "Could not find home directory".to_string())?;
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let current = active_projects_directory(&conn)?;
+    let dismissed = conn
+        .query_row(
+            "SELECT value FROM app_settings WHERE key = 'projects_directory_suggestion_dismissed'",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .map(|v| v == "true")
+        .unwrap_or(false);
+
+    // Case-insensitive filesystems list `Projects` and `projects` as the same directory
+    let mut candidates: Vec<CodeDirectoryCandidate> = Vec::new();
+    for name in COMMON_CODE_DIRECTORIES {
+        let Ok(path) = std::fs::canonicalize(home.join(name)) else {
+            continue;
+        };
+        let path_str = path.to_string_lossy().to_string();
+        if candidates.iter().any(|c| c.path == path_str) {
+            continue;
+        }
+        let repo_count = count_repos(&path);
+        if repo_count > 0 {
+            candidates.push(CodeDirectoryCandidate {
+                path: path_str,
+                repo_count,
+            });
+        }
+    }
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.repo_count));
+
+    let suggested = (!dismissed && current == default_projects_directory()?)
+        .then(|| candidates.first().map(|c| c.path.clone()))
+        .flatten();
+
+    Ok(ProjectsDirectorySuggestion {
+        current,
+        suggested,
+        candidates,
+    })
+}
+
+/// Keeps the current directory and stops offering the suggestion; accepting one
+/// goes through `set_queen_projects_directory`
+#[tauri::command]
+pub fn dismiss_projects_directory_suggestion(db: State<'_, AgentDb>) -> Result<(), String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    conn.execute(
+        "INSERT OR REPLACE INTO app_settings (key, value) VALUES ('projects_directory_suggestion_dismissed', 'true')",
+        [],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Named directories as (name, path), always including the active one
+fn load_projects_directories(conn: &rusqlite::Connection) -> Result<Vec<(String, String)>, String> {
+    let mut directories: Vec<(String, String)> = conn
+        .query_row(
+            "SELECT value FROM app_settings WHERE key = 'queen_projects_directories'",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+
+    let active = active_projects_directory(conn)?;
+    if !directories.iter().any(|(_, path)| *path == active) {
+        directories.insert(0, (unique_directory_name(&directories, &active), active));
+        save_projects_directories(conn, &directories)?;
+    }
+
+    Ok(directories)
+}
+
+fn save_projects_directories(
+    conn: &rusqlite::Connection,
+    directories: &[(String, String)],
+) -> Result<(), String> {
+    let json = serde_json::to_string(directories).map_err(|e| e.to_string())?;
     conn.execute(
         "INSERT OR REPLACE INTO app_settings (key, value) VALUES (?1, ?2)",
-        rusqlite::params!["queen_projects_directory", directory],
+        rusqlite::params!["queen_projects_directories", json],
     )
     .map_err(|e| e.to_string())?;
 
     Ok(())
 }
+
+fn unique_directory_name(directories: &[(String, String)], path: &str) -> String {
+    let base = std::path::Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Default".to_string());
+
+    let mut name = base.clone();
+    let mut suffix = 2;
+    while directories.iter().any(|(existing, _)| *existing == name) {
+        name = format!("{} {}", base, suffix);
+        suffix += 1;
+    }
+    name
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UntrackedProject {
+    pub name: String,
+    pub path: String,
+    pub stack: Vec<String>,
+    pub has_git: bool,
+}
+
+/// Directories skipped when scanning deeper than one level
+const SCAN_SKIP_DIRS: &[&str] = &["node_modules", "target", "dist", "build", "vendor"];
+
+fn collect_untracked_projects(
+    dir: &std::path::Path,
+    depth: usize,
+    tracked: &std::collections::HashSet<std::path::PathBuf>,
+    found: &mut Vec<UntrackedProject>,
+) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !path.is_dir() || name.starts_with('.') || SCAN_SKIP_DIRS.contains(&name.as_str()) {
+            continue;
+        }
+
+        let stack = detect_project_stack(&path);
+        let has_git = path.join(".git").exists();
+        if stack.is_empty() && !has_git {
+            if depth > 1 {
+                collect_untracked_projects(&path, depth - 1, tracked, found);
+            }
+            continue;
+        }
+
+        let canonical = std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+        if !tracked.contains(&canonical) {
+            found.push(UntrackedProject {
+                name,
+                path: canonical.to_string_lossy().to_string(),
+                stack,
+                has_git,
+            });
+        }
+    }
+}
+
+/// Project folders in the active projects directory that aren't tracked, e.g. ones
+/// created outside the app, so they can be imported or cleaned up. Only immediate
+/// subdirectories are checked unless `max_depth` says otherwise; projects are never
+/// descended into.
+#[tauri::command]
+pub fn scan_projects_directory(
+    max_depth: Option<usize>,
+    db: State<'_, AgentDb>,
+) -> Result<Vec<UntrackedProject>, String> {
+    let directory = {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        active_projects_directory(&conn)?
+    };
+    let tracked: std::collections::HashSet<std::path::PathBuf> = load_tracked_projects(&db)?
+        .into_iter()
+        .map(|p| std::fs::canonicalize(&p.path).unwrap_or_else(|_| std::path::PathBuf::from(&p.path)))
+        .collect();
+
+    let mut found = Vec::new();
+    let depth = max_depth.unwrap_or(1).max(1);
+    collect_untracked_projects(std::path::Path::new(&directory), depth, &tracked, &mut found);
+    found.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+
+    Ok(found)
+}
+
+#[tauri::command]
+pub fn get_queen_projects_directory(db: State<'_, AgentDb>) -> Result<String, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    active_projects_directory(&conn)
+}
+
+/// Folder-name prefixes cloud-sync clients create, e.g. `Dropbox (Personal)` or
+/// `OneDrive - Contoso`, with the provider they belong to
+const CLOUD_SYNC_FOLDERS: &[(&str, &str)] = &[
+    ("Dropbox", "Dropbox"),
+    ("OneDrive", "OneDrive"),
+    ("iCloud Drive", "iCloud Drive"),
+    ("iCloudDrive", "iCloud Drive"),
+    ("Mobile Documents", "iCloud Drive"),
+    ("Google Drive", "Google Drive"),
+    ("GoogleDrive", "Google Drive"),
+    ("Box Sync", "Box"),
+    ("pCloud Drive", "pCloud"),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectsDirectorySyncRisk {
+    pub directory: String,
+    pub provider: Option<String>,
+    /// The synced folder the directory sits under
+    pub sync_root: Option<String>,
+    pub warning: Option<String>,
+}
+
+/// The cloud-synced folder `path` sits under, as (provider, root)
+fn cloud_sync_root(path: &std::path::Path) -> Option<(String, std::path::PathBuf)> {
+    let mut root = std::path::PathBuf::new();
+    let mut components = path.components();
+    while let Some(component) = components.next() {
+        root.push(component);
+        let name = component.as_os_str().to_string_lossy();
+
+        // macOS File Provider mounts: ~/Library/CloudStorage/Dropbox, GoogleDrive-me@example.com, ...
+        if name == "CloudStorage" {
+            let account = components.next()?;
+            root.push(account);
+            let account = account.as_os_str().to_string_lossy();
+            let provider = CLOUD_SYNC_FOLDERS
+                .iter()
+                .find(|(prefix, _)| account.starts_with(prefix))
+                .map(|(_, provider)| provider.to_string())
+                .unwrap_or_else(|| account.split('-').next().unwrap_or(&account).to_string());
+            return Some((provider, root));
+        }
+
+        if let Some((_, provider)) = CLOUD_SYNC_FOLDERS.iter().find(|(prefix, _)| name.starts_with(prefix)) {
+            return Some((provider.to_string(), root));
+        }
+    }
+    None
+}
+
+fn sync_exclusion_tip(provider: &str) -> &'static str {
+    match provider {
+        "Dropbox" => "or exclude it per project with `xattr -w com.dropbox.ignored 1 node_modules` (macOS/Linux)",
+        "iCloud Drive" => "or rename it to `node_modules.nosync` and symlink `node_modules` to it",
+        _ => "since this client can't exclude individual folders",
+    }
+}
+
+fn projects_directory_sync_risk(directory: &str) -> ProjectsDirectorySyncRisk {
+    let path = std::path::Path::new(directory);
+    // A symlinked projects directory may point into a synced folder
+    let found = cloud_sync_root(path)
+        .or_else(|| std::fs::canonicalize(path).ok().and_then(|real| cloud_sync_root(&real)));
+
+    let Some((provider, root)) = found else {
+        return ProjectsDirectorySyncRisk {
+            directory: directory.to_string(),
+            provider: None,
+            sync_root: None,
+            warning: None,
+        };
+    };
+
+    let sync_root = root.to_string_lossy().to_string();
+    let warning = format!(
+        "'{}' is inside {} ({}). Syncing node_modules causes constant churn and can corrupt installs; \
+         move the projects directory outside the synced folder, {}.",
+        directory,
+        provider,
+        sync_root,
+        sync_exclusion_tip(&provider)
+    );
+
+    ProjectsDirectorySyncRisk {
+        directory: directory.to_string(),
+        provider: Some(provider),
+        sync_root: Some(sync_root),
+        warning: Some(warning),
+    }
+}
+
+/// Warns when the active projects directory lives under Dropbox, iCloud Drive,
+/// OneDrive or another cloud-synced folder
+#[tauri::command]
+pub fn check_projects_directory_sync_risk(db: State<'_, AgentDb>) -> Result<ProjectsDirectorySyncRisk, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let directory = active_projects_directory(&conn)?;
+    Ok(projects_directory_sync_risk(&directory))
+}
+
+/// Sets the active directory, adding it to the list if it isn't there yet, and
+/// reports whether it's inside a cloud-synced folder
+#[tauri::command]
+pub fn set_queen_projects_directory(
+    directory: String,
+    db: State<'_, AgentDb>,
+) -> Result<ProjectsDirectorySyncRisk, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+
+    let mut directories = load_projects_directories(&conn)?;
+    if !directories.iter().any(|(_, path)| *path == directory) {
+        directories.push((unique_directory_name(&directories, &directory), directory.clone()));
+        save_projects_directories(&conn, &directories)?;
+    }
+
+    conn.execute(
+        "INSERT OR REPLACE INTO app_settings (key, value) VALUES (?1, ?2)",
+        rusqlite::params!["queen_projects_directory", &directory],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(projects_directory_sync_risk(&directory))
+}
+
+#[tauri::command]
+pub fn list_projects_directories(db: State<'_, AgentDb>) -> Result<Vec<ProjectsDirectory>, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let active = active_projects_directory(&conn)?;
+
+    Ok(load_projects_directories(&conn)?
+        .into_iter()
+        .map(|(name, path)| ProjectsDirectory {
+            active: path == active,
+            sync_warning: projects_directory_sync_risk(&path).warning,
+            name,
+            path,
+        })
+        .collect())
+}
+
+/// Adds a named directory; each listed directory carries its cloud sync warning
+#[tauri::command]
+pub fn add_projects_directory(
+    name: String,
+    path: String,
+    db: State<'_, AgentDb>,
+) -> Result<Vec<ProjectsDirectory>, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Directory name cannot be empty".to_string());
+    }
+    if !std::path::Path::new(&path).is_absolute() {
+        return Err(format!("'{}' is not an absolute path", path));
+    }
+
+    {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        let mut directories = load_projects_directories(&conn)?;
+        if directories.iter().any(|(existing, _)| *existing == name) {
+            return Err(format!("A projects directory named '{}' already exists", name));
+        }
+        if directories.iter().any(|(_, existing)| *existing == path) {
+            return Err(format!("'{}' is already a projects directory", path));
+        }
+
+        directories.push((name, path));
+        save_projects_directories(&conn, &directories)?;
+    }
+
+    list_projects_directories(db)
+}
+
+/// Switches the active directory and reports whether it's inside a cloud-synced folder
+#[tauri::command]
+pub fn set_active_projects_directory(
+    name: String,
+    db: State<'_, AgentDb>,
+) -> Result<ProjectsDirectorySyncRisk, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+
+    let (_, path) = load_projects_directories(&conn)?
+        .into_iter()
+        .find(|(existing, _)| *existing == name)
+        .ok_or_else(|| format!("No projects directory named '{}'", name))?;
+
+    conn.execute(
+        "INSERT OR REPLACE INTO app_settings (key, value) VALUES (?1, ?2)",
+        rusqlite::params!["queen_projects_directory", &path],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(projects_directory_sync_risk(&path))
+}
+
+fn extract_version(output: &str) -> Option<String> {
+    let version = regex::Regex::new(r"\d+\.\d+\.\d+[\w.+-]*").ok()?;
+    version.find(output).map(|m| m.as_str().to_string())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandMatch {
+    pub path: String,
+    /// Symlink target, e.g. the npm package a global shim points at
+    pub resolved_path: Option<String>,
+    /// The match that actually runs (first on PATH)
+    pub active: bool,
+}
+
+fn command_matches(command: &str) -> Vec<CommandMatch> {
+    let mut seen: Vec<std::path::PathBuf> = Vec::new();
+
+    which::which_all(command)
+        .map(|paths| paths.collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        // The same directory can appear on PATH twice; that's not a shadow
+        .filter(|path| {
+            if seen.contains(path) {
+                false
+            } else {
+                seen.push(path.clone());
+                true
+            }
+        })
+        .enumerate()
+        .map(|(index, path)| CommandMatch {
+            resolved_path: std::fs::canonicalize(&path)
+                .ok()
+                .filter(|resolved| *resolved != path)
+                .map(|resolved| resolved.to_string_lossy().to_string()),
+            path: path.to_string_lossy().to_string(),
+            active: index == 0,
+        })
+        .collect()
+}
+
+/// Every match of `command` across PATH in lookup order, so an older binary
+/// shadowing a fresh install is easy to spot
+#[tauri::command]
+pub fn find_command_shadows(command: String) -> Result<Vec<CommandMatch>, String> {
+    let command = command.trim();
+    if command.is_empty() || command.contains(['/', '\\']) {
+        return Err("Expected a bare command name".to_string());
+    }
+
+    Ok(command_matches(command))
+}
+
+async fn probe_command_version(
+    command: &'static str,
+    mut cancel: tokio::sync::watch::Receiver<bool>,
+) -> CommandProbe {
+    let mut probe = CommandProbe {
+        command: command.to_string(),
+        path: which::which(command).ok().map(|p| p.to_string_lossy().to_string()),
+        runnable: false,
+        version: None,
+        error: None,
+        shadowed: command_matches(command)
+            .into_iter()
+            .skip(1)
+            .map(|m| m.path)
+            .collect(),
+    };
+
+    if probe.path.is_none() {
+        let global_bin = npm_global_prefix().ok().map(|prefix| npm_global_bin(&prefix));
+        let link = inspect_cli_link(command, global_bin.as_deref());
+        probe.error = Some(match link.detail {
+            Some(detail) if link.status == "dangling" => {
+                format!("{}; reinstall the Queen CLI", detail)
+            }
+            _ => "Not found on PATH".to_string(),
+        });
+        return probe;
+    }
+
+    let output = tokio::process::Command::new(command)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+
+    tokio::select! {
+        result = tokio::time::timeout(Duration::from_secs(VERSION_PROBE_TIMEOUT_SECS), output) => match result {
+            Ok(Ok(output)) if output.status.success() => {
+                probe.runnable = true;
+                probe.version = extract_version(&String::from_utf8_lossy(&output.stdout))
+                    .or_else(|| extract_version(&String::from_utf8_lossy(&output.stderr)));
+            }
+            Ok(Ok(output)) => {
+                probe.error = Some(format!(
+                    "Exited with {}: {}",
+                    output.status,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ));
+            }
+            Ok(Err(e)) => probe.error = Some(format!("Failed to execute: {}", e)),
+            Err(_) => probe.error = Some(format!("Timed out after {}s", VERSION_PROBE_TIMEOUT_SECS)),
+        },
+        _ = cancel.changed() => probe.error = Some("Cancelled".to_string()),
+    }
+
+    probe
+}
+
+#[tauri::command]
+pub async fn verify_queen_cli_detailed(
+    app: AppHandle,
+    state: State<'_, QueenCliVerifyState>,
+) -> Result<QueenCliVerification, String> {
+    let (cancel_tx, cancel_rx) = tokio::sync::watch::channel(false);
+    let run = state.next_run.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    if let Some((_, previous)) = state.cancel.lock().map_err(|e| e.to_string())?.replace((run, cancel_tx)) {
+        let _ = previous.send(true);
+    }
+
+    let mut probes: FuturesUnordered<_> = QUEEN_COMMANDS
+        .iter()
+        .map(|&command| probe_command_version(command, cancel_rx.clone()))
+        .collect();
+
+    let mut commands = Vec::new();
+    while let Some(probe) = probes.next().await {
+        let _ = app.emit("queen-cli-verify-progress", &probe);
+        commands.push(probe);
+    }
+
+    let cancelled = *cancel_rx.borrow();
+    {
+        // A newer run may have replaced this one; leave its sender in place
+        let mut cancel = state.cancel.lock().map_err(|e| e.to_string())?;
+        if cancel.as_ref().is_some_and(|(owner, _)| *owner == run) {
+            cancel.take();
+        }
+    }
+
+    commands.sort_by_key(|c| QUEEN_COMMANDS.iter().position(|&name| name == c.command));
+
+    let mut versions: Vec<&String> = commands.iter().filter_map(|c| c.version.as_ref()).collect();
+    versions.dedup();
+
+    Ok(QueenCliVerification {
+        all_runnable: commands.iter().all(|c| c.runnable),
+        versions_consistent: versions.len() <= 1,
+        cancelled,
+        commands,
+    })
+}
+
+#[tauri::command]
+pub fn cancel_queen_cli_verification(state: State<'_, QueenCliVerifyState>) -> Result<(), String> {
+    if let Some((_, cancel)) = state.cancel.lock().map_err(|e| e.to_string())?.take() {
+        let _ = cancel.send(true);
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NpmGlobalPathStatus {
+    pub npm_prefix: String,
+    pub global_bin: String,
+    pub on_path: bool,
+    pub shell: Option<String>,
+    pub rc_file: Option<String>,
+    pub instructions: Option<String>,
+}
+
+fn npm_global_prefix() -> Result<String, String> {
+    // `npm bin -g` was removed in npm 9, so derive the bin directory from the prefix
+    let output = Command::new("npm")
+        .args(["prefix", "-g"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|e| format!("Failed to execute npm: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "npm prefix -g failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn npm_global_bin(prefix: &str) -> std::path::PathBuf {
+    if cfg!(target_os = "windows") {
+        std::path::PathBuf::from(prefix)
+    } else {
+        std::path::Path::new(prefix).join("bin")
+    }
+}
+
+fn is_on_path(dir: &std::path::Path) -> bool {
+    let target = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    std::env::var_os("PATH")
+        .map(|path| {
+            std::env::split_paths(&path)
+                .any(|entry| entry.canonicalize().unwrap_or(entry) == target)
+        })
+        .unwrap_or(false)
+}
+
+/// The user's login shell and the rc file it reads for interactive sessions
+fn user_shell_rc() -> (Option<String>, Option<std::path::PathBuf>) {
+    let shell = std::env::var("SHELL").ok().and_then(|shell| {
+        std::path::Path::new(&shell)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+    });
+    let home = dirs::home_dir();
+
+    let rc_file = match (shell.as_deref(), home) {
+        (Some("zsh"), Some(home)) => Some(home.join(".zshrc")),
+        (Some("bash"), Some(home)) if cfg!(target_os = "macos") => Some(home.join(".bash_profile")),
+        (Some("bash"), Some(home)) => Some(home.join(".bashrc")),
+        (Some("fish"), Some(home)) => Some(home.join(".config").join("fish").join("config.fish")),
+        _ => None,
+    };
+
+    (shell, rc_file)
+}
+
+fn path_export_line(shell: Option<&str>, bin: &str) -> String {
+    match shell {
+        Some("fish") => format!("fish_add_path \"{}\"", bin),
+        _ => format!("export PATH=\"{}:$PATH\"", bin),
+    }
+}
+
+/// Checks whether npm's global bin directory is on PATH, the usual reason Queen
+/// commands are "missing" right after a successful install
+#[tauri::command]
+pub fn check_npm_global_path() -> Result<NpmGlobalPathStatus, String> {
+    let npm_prefix = npm_global_prefix()?;
+    let global_bin = npm_global_bin(&npm_prefix);
+    let global_bin_str = global_bin.to_string_lossy().to_string();
+    let on_path = is_on_path(&global_bin);
+    let (shell, rc_file) = user_shell_rc();
+
+    let instructions = (!on_path).then(|| {
+        if cfg!(target_os = "windows") {
+            format!(
+                "Add \"{}\" to your user PATH (System Properties > Environment Variables), then restart the app.",
+                global_bin_str
+            )
+        } else {
+            let line = path_export_line(shell.as_deref(), &global_bin_str);
+            match &rc_file {
+                Some(rc) => format!("Add this line to {} and restart the app:\n{}", rc.display(), line),
+                None => format!("Add this line to your shell profile and restart the app:\n{}", line),
+            }
+        }
+    });
+
+    Ok(NpmGlobalPathStatus {
+        npm_prefix,
+        global_bin: global_bin_str,
+        on_path,
+        shell,
+        rc_file: rc_file.map(|rc| rc.to_string_lossy().to_string()),
+        instructions,
+    })
+}
+
+/// Appends npm's global bin directory to the user's shell rc file. Returns the file changed.
+#[tauri::command]
+pub fn fix_npm_global_path() -> Result<String, String> {
+    if cfg!(target_os = "windows") {
+        return Err("Automatic PATH changes are not supported on Windows; follow the instructions instead".to_string());
+    }
+
+    let global_bin = npm_global_bin(&npm_global_prefix()?).to_string_lossy().to_string();
+    let (shell, rc_file) = user_shell_rc();
+    let rc_file = rc_file.ok_or_else(|| "Could not determine your shell's rc file".to_string())?;
+
+    let existing = std::fs::read_to_string(&rc_file).unwrap_or_default();
+    if existing.contains(&global_bin) {
+        return Ok(rc_file.to_string_lossy().to_string());
+    }
+
+    if let Some(parent) = rc_file.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+
+    let mut addition = String::new();
+    if !existing.is_empty() && !existing.ends_with('\n') {
+        addition.push('\n');
+    }
+    addition.push_str("\n# npm global packages (added by Queen Code)\n");
+    addition.push_str(&path_export_line(shell.as_deref(), &global_bin));
+    addition.push('\n');
+
+    use std::io::Write;
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&rc_file)
+        .and_then(|mut file| file.write_all(addition.as_bytes()))
+        .map_err(|e| format!("Failed to update {}: {}", rc_file.display(), e))?;
+
+    Ok(rc_file.to_string_lossy().to_string())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CliLinkStatus {
+    pub command: String,
+    pub path: Option<String>,
+    pub target: Option<String>,
+    /// ok, missing, dangling or not_executable
+    pub status: String,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CliSymlinkIntegrity {
+    pub commands: Vec<CliLinkStatus>,
+    pub healthy: bool,
+    pub reinstall_suggested: bool,
+    pub suggestion: Option<String>,
+}
+
+#[cfg(unix)]
+fn is_executable(path: &std::path::Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path)
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &std::path::Path) -> bool {
+    path.is_file()
+}
+
+/// Checks the entry for `command` on PATH, or in the npm global bin when `which`
+/// skipped it because its link dangles
+fn inspect_cli_link(command: &str, global_bin: Option<&std::path::Path>) -> CliLinkStatus {
+    let shim = if cfg!(target_os = "windows") {
+        format!("{}.cmd", command)
+    } else {
+        command.to_string()
+    };
+    let path = which::which(command).ok().or_else(|| {
+        global_bin
+            .map(|bin| bin.join(&shim))
+            .filter(|candidate| std::fs::symlink_metadata(candidate).is_ok())
+    });
+
+    let mut status = CliLinkStatus {
+        command: command.to_string(),
+        path: path.as_ref().map(|p| p.to_string_lossy().to_string()),
+        target: None,
+        status: "ok".to_string(),
+        detail: None,
+    };
+
+    let Some(path) = path else {
+        status.status = "missing".to_string();
+        status.detail = Some("Not found on PATH or in the npm global bin".to_string());
+        return status;
+    };
+
+    if let Ok(link) = std::fs::read_link(&path) {
+        let target = path.parent().map(|dir| dir.join(&link)).unwrap_or(link);
+        status.target = Some(target.to_string_lossy().to_string());
+    }
+
+    match std::fs::canonicalize(&path) {
+        Err(_) => {
+            status.status = "dangling".to_string();
+            status.detail = Some(format!(
+                "{} points to {}, which no longer exists",
+                path.display(),
+                status.target.as_deref().unwrap_or("a missing file")
+            ));
+        }
+        Ok(resolved) if !is_executable(&resolved) => {
+            status.status = "not_executable".to_string();
+            status.detail = Some(format!("{} is not executable", resolved.display()));
+        }
+        Ok(_) => {}
+    }
+
+    status
+}
+
+/// Finds `queen-*` commands whose global bin links dangle or point at a file that
+/// can't run, which `check_command_exists` alone reports as installed
+#[tauri::command]
+pub fn check_cli_symlink_integrity() -> Result<CliSymlinkIntegrity, String> {
+    let global_bin = npm_global_prefix().ok().map(|prefix| npm_global_bin(&prefix));
+    let commands: Vec<CliLinkStatus> = QUEEN_COMMANDS
+        .iter()
+        .map(|command| inspect_cli_link(command, global_bin.as_deref()))
+        .collect();
+
+    let broken: Vec<&str> = commands
+        .iter()
+        .filter(|c| c.status == "dangling" || c.status == "not_executable")
+        .map(|c| c.command.as_str())
+        .collect();
+    let reinstall_suggested = !broken.is_empty();
+
+    Ok(CliSymlinkIntegrity {
+        healthy: commands.iter().all(|c| c.status == "ok"),
+        suggestion: reinstall_suggested.then(|| {
+            format!(
+                "{} {} broken; reinstall the Queen CLI to restore the links",
+                broken.join(", "),
+                if broken.len() == 1 { "is" } else { "are" }
+            )
+        }),
+        reinstall_suggested,
+        commands,
+    })
+}
+
+/// Package managers that keep their own global install of the CLI, with the
+/// command that removes it
+const GLOBAL_PACKAGE_MANAGERS: &[(&str, &str)] = &[
+    ("npm", "npm uninstall -g"),
+    ("pnpm", "pnpm remove -g"),
+    ("yarn", "yarn global remove"),
+    ("bun", "bun remove -g"),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CliInstall {
+    pub package_manager: String,
+    pub version: Option<String>,
+    pub package_path: String,
+    /// First `queen-*` command in this package manager's global bin directory
+    pub bin_path: Option<String>,
+    /// PATH resolves the `queen-*` commands to this install
+    pub active: bool,
+    pub uninstall_command: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CliInstallReport {
+    pub installs: Vec<CliInstall>,
+    pub conflict: bool,
+    pub recommendation: Option<String>,
+}
+
+fn command_stdout(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program)
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .inspect_err(|e| log::debug!("Failed to run {} {}: {}", program, args.join(" "), e))
+        .ok()?;
+
+    if !output.status.success() {
+        log::debug!(
+            "{} {} exited with {}: {}",
+            program,
+            args.join(" "),
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string()).filter(|s| !s.is_empty())
+}
+
+/// A package manager's global `node_modules` and bin directories
+fn global_package_dirs(package_manager: &str) -> Option<(std::path::PathBuf, Option<std::path::PathBuf>)> {
+    use std::path::PathBuf;
+
+    match package_manager {
+        "npm" => Some((
+            PathBuf::from(command_stdout("npm", &["root", "-g"])?),
+            npm_global_prefix().ok().map(|prefix| npm_global_bin(&prefix)),
+        )),
+        "pnpm" => Some((
+            PathBuf::from(command_stdout("pnpm", &["root", "-g"])?),
+            command_stdout("pnpm", &["bin", "-g"]).map(PathBuf::from),
+        )),
+        "yarn" => Some((
+            PathBuf::from(command_stdout("yarn", &["global", "dir"])?).join("node_modules"),
+            command_stdout("yarn", &["global", "bin"]).map(PathBuf::from),
+        )),
+        "bun" => {
+            let bun_home = std::env::var_os("BUN_INSTALL")
+                .map(PathBuf::from)
+                .or_else(|| dirs::home_dir().map(|home| home.join(".bun")))?;
+            Some((
+                bun_home.join("install").join("global").join("node_modules"),
+                command_stdout("bun", &["pm", "bin", "-g"]).map(PathBuf::from),
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn find_cli_install(package_manager: &str, uninstall: &str, active_dir: Option<&std::path::Path>) -> Option<CliInstall> {
+    let (modules, bin_dir) = global_package_dirs(package_manager)?;
+    let package_dir = modules.join(QUEEN_PACKAGE);
+    let manifest = package_dir.join("package.json");
+    if !manifest.exists() {
+        return None;
+    }
+
+    let version = std::fs::read_to_string(&manifest)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|package| package["version"].as_str().map(|v| v.to_string()));
+    let bin_path = bin_dir.as_ref().and_then(|dir| {
+        QUEEN_COMMANDS
+            .iter()
+            .flat_map(|command| [dir.join(command), dir.join(format!("{}.cmd", command))])
+            .find(|path| path.exists())
+    });
+    let canonical = |dir: &std::path::Path| dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    let active = match (bin_dir.as_deref(), active_dir) {
+        (Some(bin_dir), Some(active_dir)) => canonical(bin_dir) == canonical(active_dir),
+        _ => false,
+    };
+
+    Some(CliInstall {
+        package_manager: package_manager.to_string(),
+        version,
+        package_path: package_dir.to_string_lossy().to_string(),
+        bin_path: bin_path.map(|p| p.to_string_lossy().to_string()),
+        active,
+        uninstall_command: format!("{} {}", uninstall, QUEEN_PACKAGE),
+    })
+}
+
+/// Global installs of the Queen CLI from every package manager. More than one
+/// means the copies shadow each other on PATH and versions get confusing.
+#[tauri::command]
+pub async fn detect_multiple_cli_installs() -> Result<CliInstallReport, String> {
+    let installs = tokio::task::spawn_blocking(|| {
+        let active_dir = QUEEN_COMMANDS
+            .iter()
+            .find_map(|command| which::which(command).ok())
+            .and_then(|path| path.parent().map(|dir| dir.to_path_buf()));
+
+        GLOBAL_PACKAGE_MANAGERS
+            .iter()
+            .filter(|(package_manager, _)| which::which(package_manager).is_ok())
+            .filter_map(|(package_manager, uninstall)| {
+                find_cli_install(package_manager, uninstall, active_dir.as_deref())
+            })
+            .collect::<Vec<CliInstall>>()
+    })
+    .await
+    .map_err(|e| format!("Failed to inspect Queen CLI installs: {}", e))?;
+
+    let conflict = installs.len() > 1;
+    // The app installs and updates the CLI through npm, so that's the copy to keep
+    let keep = installs
+        .iter()
+        .find(|i| i.package_manager == "npm")
+        .or_else(|| installs.iter().find(|i| i.active))
+        .or(installs.first())
+        .map(|i| i.package_manager.clone());
+    let recommendation = conflict.then(|| {
+        let extras: Vec<&str> = installs
+            .iter()
+            .filter(|i| Some(&i.package_manager) != keep.as_ref())
+            .map(|i| i.uninstall_command.as_str())
+            .collect();
+        format!(
+            "The Queen CLI is installed {} times. Keep the {} install and remove the others: {}",
+            installs.len(),
+            keep.as_deref().unwrap_or("npm"),
+            extras.join("; ")
+        )
+    });
+
+    Ok(CliInstallReport {
+        installs,
+        conflict,
+        recommendation,
+    })
+}
+
+const SENSITIVE_NAME_PARTS: &[&str] = &[
+    "token",
+    "secret",
+    "password",
+    "passwd",
+    "key",
+    "auth",
+    "credential",
+    "cookie",
+    "session",
+];
+
+fn is_sensitive_name(name: &str) -> bool {
+    let name = name.to_lowercase();
+    SENSITIVE_NAME_PARTS.iter().any(|part| name.contains(part))
+}
+
+/// Blanks values under sensitive names and strips `user:pass@` from URLs such as proxies
+fn redact_value(name: &str, value: &str) -> String {
+    if is_sensitive_name(name) && !value.is_empty() {
+        return "[REDACTED]".to_string();
+    }
+    regex::Regex::new(r"://[^/@\s]+@")
+        .map(|re| re.replace_all(value, "://[REDACTED]@").to_string())
+        .unwrap_or_else(|_| value.to_string())
+}
+
+fn command_version(program: &str) -> Option<String> {
+    Command::new(program)
+        .arg("--version")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+}
+
+fn environment_diagnostics(app: &AppHandle) -> serde_json::Value {
+    let env: BTreeMap<String, String> = std::env::vars()
+        .map(|(name, value)| {
+            let redacted = redact_value(&name, &value);
+            (name, redacted)
+        })
+        .collect();
+
+    serde_json::json!({
+        "app_version": app.package_info().version.to_string(),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "node_version": command_version("node"),
+        "npm_version": command_version("npm"),
+        "npm_global_prefix": npm_global_prefix().ok(),
+        "shell": user_shell_rc().0,
+        "path": std::env::var_os("PATH")
+            .map(|path| {
+                std::env::split_paths(&path)
+                    .map(|p| p.to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default(),
+        "env": env,
+    })
+}
+
+fn write_bundle_file<T: Serialize>(dir: &std::path::Path, name: &str, value: &T) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    std::fs::write(dir.join(name), json).map_err(|e| format!("Failed to write {}: {}", name, e))
+}
+
+/// Writes a folder under `path` with the current scan, environment, Queen CLI status,
+/// install history and settings, with secrets redacted, and returns its location
+#[tauri::command]
+pub async fn create_support_bundle(
+    path: String,
+    app: AppHandle,
+    db: State<'_, AgentDb>,
+    reservations: State<'_, PortReservationState>,
+) -> Result<String, String> {
+    let bundle_dir = std::path::Path::new(&path).join(format!(
+        "queen-support-{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    std::fs::create_dir_all(&bundle_dir)
+        .map_err(|e| format!("Failed to create {}: {}", bundle_dir.display(), e))?;
+
+    // Sections that fail are recorded in the bundle rather than aborting it
+    let scan = match scan_dev_servers(Some(true), app.clone(), db.clone(), reservations).await {
+        Ok(servers) => serde_json::json!(ScanEnvelope::new(servers)),
+        Err(e) => serde_json::json!({ "error": e }),
+    };
+    write_bundle_file(&bundle_dir, "scan.json", &scan)?;
+
+    let diagnostics = {
+        let app = app.clone();
+        tokio::task::spawn_blocking(move || environment_diagnostics(&app))
+            .await
+            .map_err(|e| e.to_string())?
+    };
+    write_bundle_file(&bundle_dir, "environment.json", &diagnostics)?;
+
+    let cli = tokio::task::spawn_blocking(|| {
+        serde_json::json!({
+            "status": queen_cli_status(),
+            "links": check_cli_symlink_integrity().ok(),
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    write_bundle_file(&bundle_dir, "queen-cli.json", &cli)?;
+
+    let (history, settings) = {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        let history = load_install_history(&conn).unwrap_or_default();
+        let settings: BTreeMap<String, String> = conn
+            .prepare("SELECT key, value FROM app_settings ORDER BY key")
+            .and_then(|mut stmt| {
+                stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+                    .collect::<rusqlite::Result<Vec<_>>>()
+            })
+            .map_err(|e| format!("Failed to read settings: {}", e))?
+            .into_iter()
+            .map(|(key, value)| {
+                let redacted = redact_value(&key, &value);
+                (key, redacted)
+            })
+            .collect();
+        (history, settings)
+    };
+    write_bundle_file(&bundle_dir, "install-history.json", &history)?;
+    write_bundle_file(&bundle_dir, "settings.json", &settings)?;
+
+    Ok(bundle_dir.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(entries: &[(&str, &str, &[u8])]) -> GitSnapshot {
+        entries
+            .iter()
+            .map(|(path, code, hash)| (path.to_string(), (code.to_string(), Some(hash.to_vec()))))
+            .collect()
+    }
+
+    fn changes(before: &GitSnapshot, after: &GitSnapshot) -> Vec<(String, String)> {
+        git_snapshot_changes(before, after)
+            .into_iter()
+            .map(|c| (c.path, c.change))
+            .collect()
+    }
+
+    #[test]
+    fn test_github_releases_url() {
+        let expected = Some("https://github.com/KenKaiii/queen-claude/releases".to_string());
+        for repository in [
+            serde_json::json!({ "type": "git", "url": "git+https://github.com/KenKaiii/queen-claude.git" }),
+            serde_json::json!("https://github.com/KenKaiii/queen-claude/"),
+            serde_json::json!({ "url": "git@github.com:KenKaiii/queen-claude.git" }),
+        ] {
+            assert_eq!(github_releases_url(&repository), expected, "{}", repository);
+        }
+
+        assert_eq!(github_releases_url(&serde_json::json!({ "url": "https://gitlab.com/a/b" })), None);
+        assert_eq!(github_releases_url(&serde_json::json!("https://github.com/KenKaiii")), None);
+        assert_eq!(github_releases_url(&serde_json::Value::Null), None);
+    }
+
+    #[test]
+    fn test_parse_release_notes() {
+        let metadata = serde_json::json!({
+            "dist-tags": { "latest": "1.10.0" },
+            "repository": { "url": "git+https://github.com/KenKaiii/queen-claude.git" },
+            "time": { "1.2.0": "2026-01-01T00:00:00Z", "1.10.0": "2026-03-01T00:00:00Z" },
+            "versions": {
+                "1.2.0": { "description": "Queen CLI", "deprecated": "Use 1.10.0" },
+                "1.10.0": { "description": "Queen CLI" },
+            },
+        });
+
+        let notes = parse_release_notes(&metadata, None);
+        assert_eq!(notes.latest_version.as_deref(), Some("1.10.0"));
+        let versions: Vec<&str> = notes.releases.iter().map(|r| r.version.as_str()).collect();
+        assert_eq!(versions, vec!["1.10.0", "1.2.0"]);
+        assert_eq!(notes.releases[1].deprecated.as_deref(), Some("Use 1.10.0"));
+        assert_eq!(notes.releases[0].published_at.as_deref(), Some("2026-03-01T00:00:00Z"));
+        assert_eq!(notes.changelog_url, "https://github.com/KenKaiii/queen-claude/releases");
+
+        let configured = parse_release_notes(&metadata, Some("https://example.com/changelog".to_string()));
+        assert_eq!(configured.changelog_url, "https://example.com/changelog");
+
+        let empty = parse_release_notes(&serde_json::json!({}), None);
+        assert!(empty.releases.is_empty());
+        assert_eq!(empty.changelog_url, npm_package_page());
+    }
+
+    #[test]
+    fn test_cloud_sync_root() {
+        use std::path::{Path, PathBuf};
+
+        assert_eq!(
+            cloud_sync_root(Path::new("/Users/me/Library/CloudStorage/GoogleDrive-me@example.com/My Drive/dev")),
+            Some((
+                "Google Drive".to_string(),
+                PathBuf::from("/Users/me/Library/CloudStorage/GoogleDrive-me@example.com")
+            ))
+        );
+        assert_eq!(
+            cloud_sync_root(Path::new("/Users/me/Library/CloudStorage/SomeSync-me/dev")),
+            Some(("SomeSync".to_string(), PathBuf::from("/Users/me/Library/CloudStorage/SomeSync-me")))
+        );
+        assert_eq!(
+            cloud_sync_root(Path::new("/home/me/OneDrive - Org/projects")),
+            Some(("OneDrive".to_string(), PathBuf::from("/home/me/OneDrive - Org")))
+        );
+        assert_eq!(
+            cloud_sync_root(Path::new("/home/me/Dropbox (Personal)/code/app")),
+            Some(("Dropbox".to_string(), PathBuf::from("/home/me/Dropbox (Personal)")))
+        );
+        assert_eq!(cloud_sync_root(Path::new("/home/me/dev/projects")), None);
+    }
+
+    #[test]
+    fn test_git_snapshot_changes() {
+        let before = snapshot(&[
+            ("CLAUDE.md", " M", b"old"),
+            ("notes.txt", "??", b"notes"),
+            ("src/lib.rs", " M", b"lib"),
+        ]);
+        let after = snapshot(&[
+            (".queen-backup-20260101-120000/CLAUDE.md", "??", b"old"),
+            (".mcp.json", "??", b"mcp"),
+            ("CLAUDE.md", " M", b"new"),
+            ("notes.txt", "??", b"notes"),
+            ("old.md", " D", b""),
+        ]);
+
+        assert_eq!(
+            changes(&before, &after),
+            vec![
+                (".mcp.json".to_string(), "added".to_string()),
+                ("CLAUDE.md".to_string(), "modified".to_string()),
+                ("old.md".to_string(), "deleted".to_string()),
+                ("src/lib.rs".to_string(), "modified".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_git_snapshot_changes_unchanged() {
+        let before = snapshot(&[("CLAUDE.md", " M", b"same")]);
+        assert!(git_snapshot_changes(&before, &before.clone()).is_empty());
+    }
+}
@@ -1,15 +1,194 @@
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
-use std::process::Command;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::sync::{broadcast, watch};
+use super::agents::AgentDb;
+use super::projects::{
+    compose_published_ports, default_framework_port, detect_project_dev_port, detect_project_dev_script,
+    detect_project_package_manager, load_tracked_projects, TrackedProject,
+};
+use super::signatures::{classification_rank, classify_service, classify_service_detailed};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DevServer {
     pub port: u16,
     pub service: String,
+    pub service_info: ServiceInfo,
+    /// Process name without path, version or wrapper suffix, so platform variants group together
     pub process_name: String,
+    /// Process name exactly as the OS reported it
+    pub raw_process_name: String,
     pub pid: u32,
     pub pids: Vec<u32>,
+    /// Working directory with symlinks resolved
+    pub cwd: Option<String>,
+    /// Working directory as reported for the process, before resolving symlinks
+    pub raw_cwd: Option<String>,
+    pub project_root: Option<String>,
+    pub server_id: String,
+    pub labels: Vec<String>,
+    pub bind_addresses: Vec<String>,
+    pub exposure: String,
+    pub priority: Option<i32>,
+    pub uptime_secs: Option<u64>,
+    pub managed_by: Option<String>,
+    pub managed_name: Option<String>,
+    pub compose_service: Option<String>,
+    /// Held by `reserve_port` rather than a real server
+    pub reserved: bool,
+    /// A helper process shipped inside the app bundle
+    pub is_sidecar: bool,
+    /// Virtualenv or conda environment of Python servers
+    pub python_env: Option<String>,
+    /// A reverse proxy (nginx, caddy, traefik, ...) fronting other servers
+    pub is_proxy: bool,
+    /// Local ports the proxy forwards to, when its config could be read
+    pub upstream_ports: Vec<u16>,
+    /// URL that actually reaches the server; uses the bind IP when it isn't on loopback
+    pub url: String,
+    /// Why `localhost` won't connect, for servers bound only to a specific non-loopback IP
+    pub localhost_hint: Option<String>,
+    /// Architecture the process executes as, e.g. `x86_64` under Rosetta on Apple Silicon
+    pub architecture: Option<String>,
+    /// The process isn't running as the host's native architecture
+    pub architecture_mismatch: bool,
+    /// A signature matched on the process, command line or headers, not just a port range
+    pub classified: bool,
+    /// How much of name, command, port and probe backed the service label (0.0-1.0)
+    pub confidence: f32,
+    /// Node inspector port opened by the same process (`--inspect`)
+    pub debug_port: Option<u16>,
+    /// Keeps its data only in memory or on tmpfs, so killing it loses that data
+    pub data_loss_on_kill: bool,
+    pub data_loss_note: Option<String>,
 }
 
+impl DevServer {
+    fn new(port: u16, service: String, process_name: String, pid: u32) -> Self {
+        Self {
+            port,
+            service_info: service_info(&service),
+            service,
+            process_name: canonical_process_name(&process_name),
+            raw_process_name: process_name,
+            pid,
+            pids: vec![pid],
+            cwd: None,
+            raw_cwd: None,
+            project_root: None,
+            server_id: String::new(),
+            labels: Vec::new(),
+            bind_addresses: Vec::new(),
+            exposure: "loopback".to_string(),
+            priority: None,
+            uptime_secs: None,
+            managed_by: None,
+            managed_name: None,
+            compose_service: None,
+            reserved: false,
+            is_sidecar: false,
+            python_env: None,
+            is_proxy: false,
+            upstream_ports: Vec::new(),
+            url: format!("http://localhost:{}", port),
+            localhost_hint: None,
+            architecture: None,
+            architecture_mismatch: false,
+            classified: false,
+            confidence: 0.0,
+            debug_port: None,
+            data_loss_on_kill: false,
+            data_loss_note: None,
+        }
+    }
+
+    fn add_bind_address(&mut self, address: &str) {
+        if !self.bind_addresses.iter().any(|a| a == address) {
+            self.bind_addresses.push(address.to_string());
+        }
+
+        self.exposure = if self.bind_addresses.iter().any(|a| bind_exposure(a) == "all_interfaces") {
+            "all_interfaces"
+        } else if self.bind_addresses.iter().any(|a| bind_exposure(a) == "specific") {
+            "specific"
+        } else {
+            "loopback"
+        }
+        .to_string();
+
+        self.url = server_url(self.port, &self.bind_addresses);
+        self.localhost_hint = specific_bind_host(&self.bind_addresses).map(|host| {
+            format!(
+                "Listening only on {}, not 127.0.0.1, so localhost connections are refused. \
+                 Use {} or bind the server to 0.0.0.0/localhost.",
+                host, self.url
+            )
+        });
+    }
+}
+
+/// The address to connect to when a server listens only on specific non-loopback
+/// IPs (no loopback, no wildcard), bracketed for IPv6
+fn specific_bind_host(bind_addresses: &[String]) -> Option<String> {
+    if bind_addresses.is_empty() || bind_addresses.iter().any(|a| bind_exposure(a) != "specific") {
+        return None;
+    }
+
+    let address = bind_addresses[0].as_str();
+    Some(if address.contains(':') && !address.starts_with('[') {
+        format!("[{}]", address)
+    } else {
+        address.to_string()
+    })
+}
+
+fn server_url(port: u16, bind_addresses: &[String]) -> String {
+    let host = specific_bind_host(bind_addresses).unwrap_or_else(|| "localhost".to_string());
+    format!("http://{}:{}", host, port)
+}
+
+/// Looks up where `port` is listening so links work for servers bound to a LAN IP only
+fn reachable_url(port: u16) -> String {
+    let bind_addresses: Vec<String> = query_port_holders(port)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|h| h.bind_address)
+        .collect();
+    server_url(port, &bind_addresses)
+}
+
+/// The URL to open or copy for the server on `port`
+#[tauri::command]
+pub fn get_server_url(port: u16) -> String {
+    reachable_url(port)
+}
+
+fn bind_exposure(address: &str) -> &'static str {
+    match address {
+        "*" | "0.0.0.0" | "[::]" | "::" => "all_interfaces",
+        "localhost" | "[::1]" | "::1" => "loopback",
+        a if a.starts_with("127.") => "loopback",
+        _ => "specific",
+    }
+}
+
+const PROJECT_ROOT_MARKERS: &[&str] = &[
+    "package.json",
+    "Cargo.toml",
+    "pyproject.toml",
+    "requirements.txt",
+    "go.mod",
+    "Gemfile",
+    "composer.json",
+    "deno.json",
+];
+
 const DEV_PROCESS_NAMES: &[&str] = &[
     "node",
     "bun",
@@ -23,6 +202,9 @@ const DEV_PROCESS_NAMES: &[&str] = &[
     "vite",
     "webpack-dev-server",
     "next-dev",
+    "astro",
+    "nuxi",
+    "docusaurus",
     "parcel",
     "rollup",
     "esbuild",
@@ -37,346 +219,7445 @@ const DEV_PROCESS_NAMES: &[&str] = &[
     "rails",
     "php",
     "dotnet",
+    "postgres",
+    "mysqld",
+    "mongod",
+    "redis-server",
+    // Published container ports are held by Docker's proxy (lsof truncates "com.docker.backend")
+    "docker",
+    "com.docke",
+    "vpnkit",
+    // Reverse proxies fronting dev services
+    "nginx",
+    "caddy",
+    "traefik",
+    "haproxy",
 ];
 
 #[tauri::command]
-pub async fn scan_dev_servers() -> Result<Vec<DevServer>, String> {
-    #[cfg(target_os = "macos")]
-    {
-        scan_dev_servers_macos().await
-    }
-
-    #[cfg(target_os = "linux")]
-    {
-        scan_dev_servers_linux().await
+pub async fn scan_dev_servers(
+    include_sidecars: Option<bool>,
+    app: AppHandle,
+    db: State<'_, AgentDb>,
+    reservations: State<'_, PortReservationState>,
+) -> Result<Vec<DevServer>, String> {
+    let mut servers = scan_listening_servers().await?;
+    tag_sidecars(&app, &mut servers);
+    if !include_sidecars.unwrap_or(false) {
+        servers.retain(|s| !s.is_sidecar);
     }
-
-    #[cfg(target_os = "windows")]
-    {
-        scan_dev_servers_windows().await
+    enrich_servers(&mut servers);
+    apply_compose_services(&db, &mut servers)?;
+    apply_project_labels(&db, &mut servers)?;
+    append_reserved_ports(&mut servers, &reservations);
+    if let Err(e) = persist_scan_backend(&db) {
+        log::warn!("Failed to save scan backend timings: {}", e);
     }
+    Ok(servers)
 }
 
-#[cfg(target_os = "macos")]
-async fn scan_dev_servers_macos() -> Result<Vec<DevServer>, String> {
-    let output = Command::new("lsof")
-        .args(&["-i", "-P", "-n", "-sTCP:LISTEN"])
-        .output()
-        .map_err(|e| format!("Failed to execute lsof: {}", e))?;
+/// Version of the `ScanEnvelope` format. Bump it only for breaking changes; new
+/// `DevServer` fields are added within a version.
+pub const SCAN_SCHEMA_VERSION: u32 = 1;
 
-    if !output.status.success() {
-        return Err("lsof command failed".to_string());
+const SCAN_SCHEMA_CONTRACT: &str = "Fields are only ever added within a schema_version. Removing, renaming \
+    or changing the type of a field bumps schema_version. Consumers should ignore fields they don't know.";
+
+/// Stable, machine-readable scan output for scripts and external tooling
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanEnvelope {
+    pub schema_version: u32,
+    pub contract: String,
+    pub scanned_at: chrono::DateTime<chrono::Utc>,
+    /// `macos`, `linux` or `windows`
+    pub os: String,
+    pub servers: Vec<DevServer>,
+}
+
+impl ScanEnvelope {
+    pub fn new(servers: Vec<DevServer>) -> Self {
+        Self {
+            schema_version: SCAN_SCHEMA_VERSION,
+            contract: SCAN_SCHEMA_CONTRACT.to_string(),
+            scanned_at: chrono::Utc::now(),
+            os: std::env::consts::OS.to_string(),
+            servers,
+        }
     }
+}
 
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    let mut servers: Vec<DevServer> = Vec::new();
+/// `scan_dev_servers` wrapped in a versioned envelope
+#[tauri::command]
+pub async fn scan_dev_servers_versioned(
+    include_sidecars: Option<bool>,
+    app: AppHandle,
+    db: State<'_, AgentDb>,
+    reservations: State<'_, PortReservationState>,
+) -> Result<ScanEnvelope, String> {
+    scan_dev_servers(include_sidecars, app, db, reservations)
+        .await
+        .map(ScanEnvelope::new)
+}
 
+#[tauri::command]
+pub fn get_scan_schema_version() -> u32 {
+    SCAN_SCHEMA_VERSION
+}
 
-    for line in output_str.lines().skip(1) {
-        let parts: Vec<&str> = line.split_whitespace().collect();
+async fn scan_listening_servers() -> Result<Vec<DevServer>, String> {
+    Ok(exclude_app_servers(scan_all_listeners().await?))
+}
 
-        if parts.len() < 10 {
-            continue;
-        }
+/// Every dev listener, including the app's own
+async fn scan_all_listeners() -> Result<Vec<DevServer>, String> {
+    #[cfg(target_os = "macos")]
+    let servers = scan_dev_servers_lsof().await?;
 
-        let process_name = parts[0];
-        let pid = parts[1].parse::<u32>().ok();
+    #[cfg(target_os = "linux")]
+    let servers = scan_dev_servers_adaptive().await?;
 
-        if pid.is_none() {
-            continue;
-        }
+    #[cfg(target_os = "windows")]
+    let servers = scan_dev_servers_windows().await?;
 
-        let is_dev_process = DEV_PROCESS_NAMES.iter().any(|&dev_name| {
-            process_name.to_lowercase().contains(dev_name)
-        });
+    Ok(servers)
+}
 
-        if !is_dev_process {
-            continue;
-        }
+/// Scans between re-running both Linux backends to refresh their timings
+#[cfg(target_os = "linux")]
+const SCAN_REBENCHMARK_INTERVAL: u32 = 50;
 
-        if let Some(addr_part) = parts.iter().find(|p| {
-            p.contains("*:") || p.contains("localhost:") || p.contains("[::1]:") || p.contains("127.0.0.1:")
-        }) {
-            let port_str = if addr_part.contains("[::1]:") {
-                addr_part.strip_prefix("[::1]:").unwrap_or("")
-            } else {
-                addr_part.split(':').last().unwrap_or("")
-            };
+/// Recent per-backend scan times on Linux, used to pick the faster one
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScanBackendTimings {
+    pub lsof_ms: Option<f64>,
+    pub proc_ms: Option<f64>,
+    #[cfg(target_os = "linux")]
+    #[serde(skip)]
+    scans_since_benchmark: u32,
+    /// Set when the timings changed and haven't been saved to settings yet
+    #[serde(skip)]
+    dirty: bool,
+}
 
-            if let Ok(port) = port_str.split_whitespace().next().unwrap_or("").parse::<u16>() {
-                let service = detect_service(port, process_name);
+#[derive(Debug, Clone, Default)]
+struct ScanBackendChoice {
+    timings: ScanBackendTimings,
+    override_backend: Option<String>,
+}
 
-                servers.push(DevServer {
-                    port,
-                    service,
-                    process_name: process_name.to_string(),
-                    pid: pid.unwrap(),
-                    pids: vec![],
-                });
-            }
+static SCAN_BACKEND: Mutex<ScanBackendChoice> = Mutex::new(ScanBackendChoice {
+    timings: ScanBackendTimings {
+        lsof_ms: None,
+        proc_ms: None,
+        #[cfg(target_os = "linux")]
+        scans_since_benchmark: 0,
+        dirty: false,
+    },
+    override_backend: None,
+});
+
+impl ScanBackendTimings {
+    fn preferred(&self) -> &'static str {
+        match (self.lsof_ms, self.proc_ms) {
+            (Some(lsof), Some(proc)) if proc < lsof => "proc",
+            _ => "lsof",
         }
     }
 
-    servers.sort_by_key(|s| s.port);
-
-    let mut port_map: std::collections::HashMap<u16, DevServer> = std::collections::HashMap::new();
-    for server in servers {
-        port_map.entry(server.port)
-            .and_modify(|e| e.pids.push(server.pid))
-            .or_insert_with(|| {
-                let mut new_server = server.clone();
-                new_server.pids = vec![server.pid];
-                new_server
-            });
+    /// Exponential moving average so one slow scan doesn't flip the choice
+    #[cfg(target_os = "linux")]
+    fn record(&mut self, backend: &str, elapsed: Duration) {
+        let ms = elapsed.as_secs_f64() * 1000.0;
+        let slot = if backend == "proc" { &mut self.proc_ms } else { &mut self.lsof_ms };
+        *slot = Some(slot.map_or(ms, |previous| previous * 0.7 + ms * 0.3));
+        self.dirty = true;
     }
-
-    let mut result: Vec<DevServer> = port_map.into_values()
-        .filter(|s| s.port != 1420)
-        .collect();
-    result.sort_by_key(|s| s.port);
-
-    Ok(result)
 }
 
 #[cfg(target_os = "linux")]
-async fn scan_dev_servers_linux() -> Result<Vec<DevServer>, String> {
-    let output = Command::new("lsof")
-        .args(&["-i", "-P", "-n", "-sTCP:LISTEN"])
-        .output()
-        .map_err(|e| format!("Failed to execute lsof: {}", e))?;
+async fn scan_dev_servers_adaptive() -> Result<Vec<DevServer>, String> {
+    let (override_backend, benchmark, preferred) = {
+        let mut choice = SCAN_BACKEND.lock().map_err(|e| e.to_string())?;
+        let override_backend = choice.override_backend.clone();
+        let timings = &mut choice.timings;
+        let benchmark = timings.lsof_ms.is_none()
+            || timings.proc_ms.is_none()
+            || timings.scans_since_benchmark >= SCAN_REBENCHMARK_INTERVAL;
+        timings.scans_since_benchmark = if benchmark { 0 } else { timings.scans_since_benchmark + 1 };
+        (override_backend, benchmark, timings.preferred())
+    };
 
-    if !output.status.success() {
-        return Err("lsof command failed".to_string());
+    if let Some(backend) = override_backend {
+        return if backend == "proc" {
+            Ok(scan_dev_servers_proc().servers)
+        } else {
+            scan_dev_servers_lsof().await
+        };
     }
 
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    let mut servers: Vec<DevServer> = Vec::new();
-
-    for line in output_str.lines().skip(1) {
-        let parts: Vec<&str> = line.split_whitespace().collect();
+    let record = |backend: &str, elapsed: Duration| {
+        if let Ok(mut choice) = SCAN_BACKEND.lock() {
+            choice.timings.record(backend, elapsed);
+        }
+    };
 
-        if parts.len() < 10 {
-            continue;
+    if benchmark || preferred == "lsof" {
+        let started = Instant::now();
+        let lsof = scan_dev_servers_lsof().await;
+        record("lsof", started.elapsed());
+        if !benchmark {
+            return lsof;
         }
 
-        let process_name = parts[0];
-        let pid = parts[1].parse::<u32>().ok();
+        let started = Instant::now();
+        let native = scan_dev_servers_proc();
+        record("proc", started.elapsed());
+        // lsof stays authoritative while benchmarking; fall back to /proc if it failed
+        return lsof.or(Ok(native.servers));
+    }
 
-        if pid.is_none() {
-            continue;
-        }
+    let started = Instant::now();
+    let native = scan_dev_servers_proc();
+    record("proc", started.elapsed());
+    Ok(native.servers)
+}
 
-        let is_dev_process = DEV_PROCESS_NAMES.iter().any(|&dev_name| {
-            process_name.to_lowercase().contains(dev_name)
-        });
+/// Restores the learned timings and any manual override; called once at startup
+pub fn load_scan_backend(db: &AgentDb) -> Result<(), String> {
+    let timings = get_setting(db, "scan_backend_timings")?
+        .filter(|v| !v.is_empty())
+        .and_then(|v| serde_json::from_str::<ScanBackendTimings>(&v).ok())
+        .unwrap_or_default();
+    let override_backend = get_setting(db, "scan_backend")?.filter(|v| !v.is_empty());
 
-        if !is_dev_process {
-            continue;
+    let mut choice = SCAN_BACKEND.lock().map_err(|e| e.to_string())?;
+    choice.timings = timings;
+    choice.override_backend = override_backend;
+    Ok(())
+}
+
+/// Saves learned timings after they change
+fn persist_scan_backend(db: &AgentDb) -> Result<(), String> {
+    let timings = {
+        let mut choice = SCAN_BACKEND.lock().map_err(|e| e.to_string())?;
+        if !choice.timings.dirty {
+            return Ok(());
         }
+        choice.timings.dirty = false;
+        choice.timings.clone()
+    };
+    let json = serde_json::to_string(&timings).map_err(|e| e.to_string())?;
+    set_setting(db, "scan_backend_timings", &json)
+}
 
-        if let Some(addr_part) = parts.iter().find(|p| {
-            p.contains("*:") || p.contains("localhost:") || p.contains("[::1]:") || p.contains("127.0.0.1:")
-        }) {
-            let port_str = if addr_part.contains("[::1]:") {
-                addr_part.strip_prefix("[::1]:").unwrap_or("")
-            } else {
-                addr_part.split(':').last().unwrap_or("")
-            };
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanBackendStatus {
+    /// lsof, proc or netstat
+    pub active: String,
+    pub override_backend: Option<String>,
+    pub timings: ScanBackendTimings,
+}
 
-            if let Ok(port) = port_str.split_whitespace().next().unwrap_or("").parse::<u16>() {
-                let service = detect_service(port, process_name);
+#[tauri::command]
+pub fn get_scan_backend() -> Result<ScanBackendStatus, String> {
+    let choice = SCAN_BACKEND.lock().map_err(|e| e.to_string())?.clone();
 
-                servers.push(DevServer {
-                    port,
-                    service,
-                    process_name: process_name.to_string(),
-                    pid: pid.unwrap(),
-                    pids: vec![],
-                });
-            }
-        }
-    }
+    let active = if cfg!(target_os = "windows") {
+        "netstat".to_string()
+    } else if cfg!(target_os = "linux") {
+        choice
+            .override_backend
+            .clone()
+            .unwrap_or_else(|| choice.timings.preferred().to_string())
+    } else {
+        "lsof".to_string()
+    };
 
-    servers.sort_by_key(|s| s.port);
+    Ok(ScanBackendStatus {
+        active,
+        override_backend: choice.override_backend,
+        timings: choice.timings,
+    })
+}
 
-    let mut port_map: std::collections::HashMap<u16, DevServer> = std::collections::HashMap::new();
-    for server in servers {
-        port_map.entry(server.port)
-            .and_modify(|e| e.pids.push(server.pid))
-            .or_insert_with(|| {
-                let mut new_server = server.clone();
-                new_server.pids = vec![server.pid];
-                new_server
-            });
+/// Pins the Linux scanner to `lsof` or `proc`; `None` goes back to picking the faster one
+#[tauri::command]
+pub fn set_scan_backend_override(backend: Option<String>, db: State<'_, AgentDb>) -> Result<(), String> {
+    let backend = backend.filter(|b| !b.is_empty());
+    if let Some(backend) = &backend {
+        if !cfg!(target_os = "linux") {
+            return Err("Choosing a scan backend is only supported on Linux".to_string());
+        }
+        if backend != "lsof" && backend != "proc" {
+            return Err(format!("Unknown scan backend '{}'; use 'lsof' or 'proc'", backend));
+        }
     }
 
-    let mut result: Vec<DevServer> = port_map.into_values()
-        .filter(|s| s.port != 1420)
-        .collect();
-    result.sort_by_key(|s| s.port);
+    set_setting(&db, "scan_backend", backend.as_deref().unwrap_or(""))?;
+    SCAN_BACKEND.lock().map_err(|e| e.to_string())?.override_backend = backend;
+    Ok(())
+}
 
-    Ok(result)
+#[cfg(target_os = "linux")]
+fn get_process_exe(pid: u32) -> Option<std::path::PathBuf> {
+    std::fs::read_link(format!("/proc/{}/exe", pid)).ok()
+}
+
+#[cfg(target_os = "macos")]
+fn get_process_exe(pid: u32) -> Option<std::path::PathBuf> {
+    // On macOS `comm` is the full executable path
+    let output = Command::new("ps")
+        .args(["-p", &pid.to_string(), "-o", "comm="])
+        .output()
+        .ok()?;
+    let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!path.is_empty()).then(|| std::path::PathBuf::from(path))
 }
 
 #[cfg(target_os = "windows")]
-async fn scan_dev_servers_windows() -> Result<Vec<DevServer>, String> {
-    let output = Command::new("netstat")
-        .args(&["-ano"])
+fn get_process_exe(pid: u32) -> Option<std::path::PathBuf> {
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-Command", &format!("(Get-Process -Id {}).Path", pid)])
         .output()
-        .map_err(|e| format!("Failed to execute netstat: {}", e))?;
+        .ok()?;
+    let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!path.is_empty()).then(|| std::path::PathBuf::from(path))
+}
 
-    if !output.status.success() {
-        return Err("netstat command failed".to_string());
+#[cfg(not(target_os = "macos"))]
+fn normalize_architecture(arch: &str) -> String {
+    match arch.trim().to_lowercase().as_str() {
+        "x86_64" | "amd64" | "x64" => "x86_64".to_string(),
+        "aarch64" | "arm64" => "arm64".to_string(),
+        "x86" | "i386" | "i686" => "x86".to_string(),
+        other => other.to_string(),
     }
+}
 
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    let mut servers: Vec<DevServer> = Vec::new();
-
-    for line in output_str.lines().skip(4) {
-        let parts: Vec<&str> = line.split_whitespace().collect();
+/// The machine's own architecture, which can differ from this build's (an x86_64
+/// app under Rosetta still reports `arm64` here)
+fn native_architecture() -> Option<&'static str> {
+    static NATIVE: std::sync::OnceLock<Option<String>> = std::sync::OnceLock::new();
+    NATIVE
+        .get_or_init(|| {
+            #[cfg(target_os = "macos")]
+            let native = Command::new("sysctl")
+                .args(["-n", "hw.optional.arm64"])
+                .output()
+                .ok()
+                .map(|o| if String::from_utf8_lossy(&o.stdout).trim() == "1" { "arm64" } else { "x86_64" }.to_string());
 
-        if parts.len() < 5 || parts[0] != "TCP" {
-            continue;
-        }
+            #[cfg(target_os = "linux")]
+            let native = Command::new("uname")
+                .arg("-m")
+                .output()
+                .ok()
+                .map(|o| normalize_architecture(&String::from_utf8_lossy(&o.stdout)));
 
-        let state = parts[3];
-        if state != "LISTENING" {
-            continue;
-        }
+            // A 32-bit or emulated process sees the real one in PROCESSOR_ARCHITEW6432
+            #[cfg(target_os = "windows")]
+            let native = std::env::var("PROCESSOR_ARCHITEW6432")
+                .or_else(|_| std::env::var("PROCESSOR_ARCHITECTURE"))
+                .ok()
+                .map(|arch| normalize_architecture(&arch));
 
-        if let Some(addr) = parts.get(1) {
-            if let Some(port_str) = addr.split(':').last() {
-                if let Ok(port) = port_str.parse::<u16>() {
-                    if let Some(pid_str) = parts.get(4) {
-                        if let Ok(pid) = pid_str.parse::<u32>() {
-                            if let Ok(process_name) = get_process_name_windows(pid) {
-                                let is_dev_process = DEV_PROCESS_NAMES.iter().any(|&dev_name| {
-                                    process_name.to_lowercase().contains(dev_name)
-                                });
+            native
+        })
+        .as_deref()
+}
 
-                                if is_dev_process {
-                                    let service = detect_service(port, &process_name);
+/// Rosetta-translated processes carry P_TRANSLATED in their `ps` flags
+#[cfg(target_os = "macos")]
+fn process_architecture(pid: u32) -> Option<String> {
+    const P_TRANSLATED: u64 = 0x0002_0000;
 
-                                    servers.push(DevServer {
-                                        port,
-                                        service,
-                                        process_name,
-                                        pid,
-                                    });
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
+    if native_architecture()? != "arm64" {
+        return Some("x86_64".to_string());
     }
+    let output = Command::new("ps")
+        .args(["-o", "flags=", "-p", &pid.to_string()])
+        .output()
+        .ok()?;
+    let flags = u64::from_str_radix(String::from_utf8_lossy(&output.stdout).trim(), 16).ok()?;
+    Some(if flags & P_TRANSLATED != 0 { "x86_64" } else { "arm64" }.to_string())
+}
 
-    servers.sort_by_key(|s| s.port);
-
-    let mut seen_ports = std::collections::HashSet::new();
-    servers.retain(|s| seen_ports.insert(s.port));
+/// Reads `e_machine` from the executable's ELF header
+#[cfg(target_os = "linux")]
+fn process_architecture(pid: u32) -> Option<String> {
+    use std::io::Read;
 
-    Ok(servers)
+    let mut header = [0u8; 20];
+    std::fs::File::open(format!("/proc/{}/exe", pid)).ok()?.read_exact(&mut header).ok()?;
+    if &header[..4] != b"\x7fELF" {
+        return None;
+    }
+    let machine = match header[5] {
+        2 => u16::from_be_bytes([header[18], header[19]]),
+        _ => u16::from_le_bytes([header[18], header[19]]),
+    };
+    Some(match machine {
+        0x3e => "x86_64".to_string(),
+        0xb7 => "arm64".to_string(),
+        0x03 => "x86".to_string(),
+        0x28 => "arm".to_string(),
+        0xf3 => "riscv64".to_string(),
+        other => format!("elf-{:#x}", other),
+    })
 }
 
+/// Reads the machine field of the executable's PE header, which is what
+/// IsWow64Process would reveal without needing a process handle
 #[cfg(target_os = "windows")]
-fn get_process_name_windows(pid: u32) -> Result<String, String> {
-    let output = Command::new("tasklist")
-        .args(&["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"])
-        .output()
-        .map_err(|e| format!("Failed to execute tasklist: {}", e))?;
+fn process_architecture(pid: u32) -> Option<String> {
+    use std::io::Read;
 
-    if !output.status.success() {
-        return Err("tasklist command failed".to_string());
+    // The PE header sits within the first few hundred bytes; executables are far too big to read whole
+    let mut image = Vec::with_capacity(4096);
+    std::fs::File::open(get_process_exe(pid)?).ok()?.take(4096).read_to_end(&mut image).ok()?;
+    let pe_offset = u32::from_le_bytes(image.get(0x3c..0x40)?.try_into().ok()?) as usize;
+    if image.get(pe_offset..pe_offset + 4)? != b"PE\0\0" {
+        return None;
     }
+    let machine = u16::from_le_bytes(image.get(pe_offset + 4..pe_offset + 6)?.try_into().ok()?);
+    Some(match machine {
+        0x8664 => "x86_64".to_string(),
+        0xaa64 => "arm64".to_string(),
+        0x014c => "x86".to_string(),
+        other => format!("pe-{:#x}", other),
+    })
+}
 
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    if let Some(first_line) = output_str.lines().next() {
-        if let Some(name) = first_line.split(',').next() {
-            return Ok(name.trim_matches('"').to_string());
+/// Per-pid lookups that cost a `ps` or PowerShell spawn on macOS and Windows. An
+/// entry is only trusted while the pid still runs under the same process name.
+#[derive(Default)]
+struct CachedProcessInfo {
+    name: String,
+    exe: Option<Option<std::path::PathBuf>>,
+    architecture: Option<Option<String>>,
+}
+
+static PROCESS_INFO_CACHE: std::sync::OnceLock<Mutex<HashMap<u32, CachedProcessInfo>>> = std::sync::OnceLock::new();
+
+fn process_info_cache() -> std::sync::MutexGuard<'static, HashMap<u32, CachedProcessInfo>> {
+    PROCESS_INFO_CACHE
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Returns the cached value for `pid`, computing and storing it on a miss
+fn cached_process_info<T: Clone>(
+    pid: u32,
+    name: &str,
+    field: fn(&mut CachedProcessInfo) -> &mut Option<T>,
+    lookup: impl FnOnce() -> T,
+) -> T {
+    {
+        let mut cache = process_info_cache();
+        if let Some(value) = cache.get_mut(&pid).filter(|info| info.name == name).and_then(|info| field(info).clone()) {
+            return value;
         }
     }
 
-    Err("Could not parse process name".to_string())
+    let value = lookup();
+    let mut cache = process_info_cache();
+    let info = cache.entry(pid).or_default();
+    if info.name != name {
+        *info = CachedProcessInfo {
+            name: name.to_string(),
+            ..Default::default()
+        };
+    }
+    *field(info) = Some(value.clone());
+    value
 }
 
-fn detect_service(port: u16, process_name: &str) -> String {
-    let process_lower = process_name.to_lowercase();
+fn cached_process_exe(pid: u32, name: &str) -> Option<std::path::PathBuf> {
+    cached_process_info(pid, name, |info| &mut info.exe, || get_process_exe(pid))
+}
+
+/// `process_architecture` once per process instead of a `ps`/PowerShell spawn on every scan
+fn cached_process_architecture(pid: u32, name: &str) -> Option<String> {
+    cached_process_info(pid, name, |info| &mut info.architecture, || process_architecture(pid))
+}
+
+/// Drops entries for processes that have exited so a reused pid starts fresh
+fn prune_process_info_cache(live: &HashMap<u32, &ProcessDetails>) {
+    if !live.is_empty() {
+        process_info_cache().retain(|pid, _| live.contains_key(pid));
+    }
+}
+
+/// The app's own binaries: sidecars declared in `bundle.externalBin`, which Tauri
+/// installs next to the main executable under their bare names, and anything
+/// under the resource directory. The executable's directory alone (often
+/// `/usr/bin`) says nothing, since unrelated tools live there too.
+struct AppBinaries {
+    exe_dir: Option<std::path::PathBuf>,
+    sidecar_names: Vec<String>,
+    resource_dir: Option<std::path::PathBuf>,
+}
+
+fn app_binaries(app: &AppHandle) -> AppBinaries {
+    let canonical = |dir: std::path::PathBuf| dir.canonicalize().unwrap_or(dir);
+    let sidecar_names = app
+        .config()
+        .bundle
+        .external_bin
+        .iter()
+        .flatten()
+        .filter_map(|bin| Path::new(bin).file_name().map(|name| name.to_string_lossy().to_lowercase()))
+        .collect();
+
+    AppBinaries {
+        exe_dir: std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(|p| canonical(p.to_path_buf()))),
+        sidecar_names,
+        resource_dir: app.path().resource_dir().ok().map(canonical),
+    }
+}
 
-    if process_lower.contains("vite") {
-        return "Vite".to_string();
+fn is_app_binary(exe: &Path, binaries: &AppBinaries) -> bool {
+    if binaries.resource_dir.as_deref().is_some_and(|dir| exe.starts_with(dir)) {
+        return true;
     }
-    if process_lower.contains("webpack") {
-        return "Webpack Dev".to_string();
+
+    let name = exe
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let name = name.strip_suffix(".exe").unwrap_or(&name);
+    exe.parent() == binaries.exe_dir.as_deref() && binaries.sidecar_names.iter().any(|sidecar| sidecar == name)
+}
+
+fn tag_sidecars(app: &AppHandle, servers: &mut [DevServer]) {
+    let binaries = app_binaries(app);
+    if binaries.sidecar_names.is_empty() && binaries.resource_dir.is_none() {
+        return;
     }
-    if process_lower.contains("next") {
-        return "Next.js".to_string();
+
+    for server in servers.iter_mut() {
+        server.is_sidecar = cached_process_exe(server.pid, &server.raw_process_name)
+            .map(|exe| exe.canonicalize().unwrap_or(exe))
+            .is_some_and(|exe| is_app_binary(&exe, &binaries));
     }
+}
 
-    match port {
-        1420 => "Tauri Dev".to_string(),
-        3000..=3099 => {
-            if process_lower.contains("bun") {
-                "Bun Server".to_string()
-            } else if process_lower.contains("node") {
-                "React/Next.js".to_string()
-            } else {
-                "Node.js Dev".to_string()
-            }
+/// PIDs belonging to the app itself: its own process plus, in `tauri dev`,
+/// everything spawned by the Tauri CLI (the frontend dev server and friends).
+fn app_process_tree(processes: &[ProcessDetails]) -> (HashSet<u32>, bool) {
+    let own_pid = std::process::id();
+    let parents: HashMap<u32, u32> = processes.iter().map(|p| (p.pid, p.ppid)).collect();
+
+    let mut dev_root = None;
+    let mut current = own_pid;
+    for _ in 0..32 {
+        let Some(&parent) = parents.get(&current) else {
+            break;
+        };
+        if parent == current || parent <= 1 {
+            break;
         }
-        4000..=4099 => "Express/Node".to_string(),
-        5000..=5099 => {
-            if process_lower.contains("python") {
-                "Flask/Python".to_string()
-            } else {
-                "Dev Server".to_string()
-            }
+
+        let is_tauri_cli = processes
+            .iter()
+            .find(|p| p.pid == parent)
+            .map(|p| {
+                let command = p.command.to_lowercase();
+                command.contains("tauri") && command.split_whitespace().any(|arg| arg == "dev")
+            })
+            .unwrap_or(false);
+
+        if is_tauri_cli {
+            dev_root = Some(parent);
+            break;
         }
-        5173 | 5174 => "Vite".to_string(),
-        6006 => "Storybook".to_string(),
-        7000..=7099 => "Custom Dev".to_string(),
-        8000..=8099 => {
-            if process_lower.contains("python") {
-                "Django/Python".to_string()
-            } else {
-                "Dev Server".to_string()
+        current = parent;
+    }
+
+    let mut tree: HashSet<u32> = HashSet::from([own_pid]);
+    let Some(root) = dev_root else {
+        return (tree, false);
+    };
+
+    tree.insert(root);
+    let mut queue = vec![root];
+    while let Some(pid) = queue.pop() {
+        for child in processes.iter().filter(|p| p.ppid == pid) {
+            if tree.insert(child.pid) {
+                queue.push(child.pid);
             }
         }
-        8888 => "Jupyter".to_string(),
-        9000..=9099 => "Go/Dev Server".to_string(),
-        _ => "Development Server".to_string(),
     }
+
+    (tree, true)
 }
 
-#[tauri::command]
-pub async fn kill_dev_server(pids: Vec<u32>) -> Result<(), String> {
-    for pid in pids {
-        #[cfg(not(target_os = "windows"))]
-        {
-            let output = Command::new("kill")
-                .arg("-9")
-                .arg(pid.to_string())
-                .output()
-                .map_err(|e| format!("Failed to kill process {}: {}", pid, e))?;
+/// Ports from vite.config.ts: the frontend dev server and its HMR websocket
+const APP_DEV_SERVER_PORT: u16 = 1420;
+const APP_HMR_PORT: u16 = 1421;
 
-            if !output.status.success() {
+fn current_app_process_tree() -> (HashSet<u32>, bool) {
+    match list_processes() {
+        Ok(processes) => app_process_tree(&processes),
+        Err(e) => {
+            log::warn!("Failed to list processes for self-detection: {}", e);
+            (HashSet::from([std::process::id()]), false)
+        }
+    }
+}
+
+/// Whether a server is the app's own dev infrastructure. Without a detectable
+/// Tauri CLI parent, debug builds fall back to the configured dev and HMR ports.
+fn is_app_server(server: &DevServer, app_pids: &HashSet<u32>, detected: bool) -> bool {
+    server.pids.iter().any(|pid| app_pids.contains(pid))
+        || (!detected && cfg!(debug_assertions) && [APP_DEV_SERVER_PORT, APP_HMR_PORT].contains(&server.port))
+}
+
+fn exclude_app_servers(servers: Vec<DevServer>) -> Vec<DevServer> {
+    let (app_pids, detected) = current_app_process_tree();
+
+    servers
+        .into_iter()
+        .filter(|s| !is_app_server(s, &app_pids, detected))
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppDevPort {
+    pub port: u16,
+    pub pid: u32,
+    /// `dev_server`, `hmr` or `helper`
+    pub role: String,
+    /// Found through the app's process tree rather than the fallback port list
+    pub via_process_tree: bool,
+}
+
+/// The app's own dev ports (frontend server, Vite HMR websocket and any other
+/// helper the Tauri CLI started) that scans hide. Empty in production builds.
+#[tauri::command]
+pub async fn get_app_dev_ports() -> Result<Vec<AppDevPort>, String> {
+    if !cfg!(debug_assertions) {
+        return Ok(Vec::new());
+    }
+
+    let (app_pids, detected) = current_app_process_tree();
+    let own_pid = std::process::id();
+
+    Ok(scan_all_listeners()
+        .await?
+        .into_iter()
+        .filter(|s| is_app_server(s, &app_pids, detected))
+        // The app's own listeners (IPC, proxies) aren't dev infrastructure
+        .filter(|s| s.pids.iter().any(|&pid| pid != own_pid))
+        .map(|s| AppDevPort {
+            port: s.port,
+            pid: s.pid,
+            role: match s.port {
+                APP_DEV_SERVER_PORT => "dev_server",
+                APP_HMR_PORT => "hmr",
+                _ => "helper",
+            }
+            .to_string(),
+            via_process_tree: detected,
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn get_app_self_ports() -> Result<Vec<u16>, String> {
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
+    let servers = scan_dev_servers_lsof().await?;
+
+    #[cfg(target_os = "windows")]
+    let servers = scan_dev_servers_windows().await?;
+
+    let (app_pids, _) = app_process_tree(&list_processes()?);
+
+    Ok(servers
+        .into_iter()
+        .filter(|s| s.pids.iter().any(|pid| app_pids.contains(pid)))
+        .map(|s| s.port)
+        .collect())
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+async fn scan_dev_servers_lsof() -> Result<Vec<DevServer>, String> {
+    let output = Command::new("lsof")
+        .args(["-i", "-P", "-n", "-sTCP:LISTEN"])
+        .output()
+        .map_err(|e| format!("Failed to execute lsof: {}", e))?;
+
+    if !output.status.success() {
+        log::debug!(
+            "lsof exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return Err("lsof command failed".to_string());
+    }
+
+    let output_str = String::from_utf8_lossy(&output.stdout);
+
+    Ok(parse_lsof_listeners(&output_str))
+}
+
+fn parse_lsof_listeners(output_str: &str) -> Vec<DevServer> {
+    let mut servers: Vec<DevServer> = Vec::new();
+
+    for line in output_str.lines().skip(1) {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+
+        if parts.len() < 10 {
+            log::debug!("lsof: skipping line with {} fields: {}", parts.len(), line);
+            continue;
+        }
+
+        let process_name = parts[0];
+        let pid = match parts[1].parse::<u32>() {
+            Ok(pid) => pid,
+            Err(_) => {
+                log::debug!("lsof: skipping line with unparsable PID '{}': {}", parts[1], line);
+                continue;
+            }
+        };
+
+        if !is_dev_process(process_name) {
+            log::trace!("lsof: ignoring non-dev process {} (pid {})", process_name, pid);
+            continue;
+        }
+
+        // NAME column, e.g. `*:3000`, `127.0.0.1:5173` or `[::1]:8080`
+        let Some((host, port_str)) = parts[8].rsplit_once(':') else {
+            log::debug!("lsof: no port in NAME column '{}' for {} (pid {})", parts[8], process_name, pid);
+            continue;
+        };
+
+        // lsof prints the wildcard as `*` for both families; keep IPv6 distinct
+        // so a dual-stack server records both
+        let host = if host == "*" && parts[4] == "IPv6" { "[::]" } else { host };
+
+        match port_str.parse::<u16>() {
+            Ok(port) => {
+                let service = detect_service(port, process_name);
+
+                let mut server = DevServer::new(port, service, process_name.to_string(), pid);
+                server.add_bind_address(host);
+                servers.push(server);
+            }
+            Err(_) => log::debug!("lsof: unparsable port '{}' for {} (pid {})", port_str, process_name, pid),
+        }
+    }
+
+    merge_listeners(servers)
+}
+
+fn is_dev_process(process_name: &str) -> bool {
+    let process_name = process_name.to_lowercase();
+    DEV_PROCESS_NAMES.iter().any(|&dev_name| process_name.contains(dev_name))
+}
+
+/// Folds the platform variants of a process name (`/usr/bin/node`, `node.exe`,
+/// `node (tsx)`, `Python3.11`) into one lowercase name
+fn canonical_process_name(raw: &str) -> String {
+    // lsof escapes spaces in command names
+    let name = raw.replace("\\x20", " ");
+    let name = name.trim().rsplit(['/', '\\']).next().unwrap_or("");
+    // Wrapper indicators such as `node (tsx)` or `node [inspector]`
+    let name = name.split(" (").next().unwrap_or(name);
+    let name = name.split(" [").next().unwrap_or(name).trim();
+    let name = name.to_lowercase();
+    let name = name.strip_suffix(".exe").unwrap_or(&name);
+
+    // Version suffixes: `python3.11`, `node18`, `ruby-3.2`, `node-v20`
+    let unversioned = name.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    let unversioned = if unversioned.len() < name.len() {
+        let unversioned = unversioned.strip_suffix("-v").unwrap_or(unversioned);
+        unversioned.trim_end_matches(['-', '_'])
+    } else {
+        unversioned
+    };
+
+    if unversioned.is_empty() {
+        name.to_string()
+    } else {
+        unversioned.to_string()
+    }
+}
+
+/// Collapses one-entry-per-socket listeners into one server per port. The row
+/// with the most specific classification names the merged server, whatever the row order.
+fn merge_listeners(mut servers: Vec<DevServer>) -> Vec<DevServer> {
+    servers.sort_by_key(|s| s.port);
+
+    let mut port_map: HashMap<u16, DevServer> = HashMap::new();
+    for server in servers {
+        port_map.entry(server.port)
+            .and_modify(|e| {
+                let rank = classification_rank(server.port, &server.raw_process_name);
+                if rank < classification_rank(e.port, &e.raw_process_name) {
+                    e.pid = server.pid;
+                    e.process_name = server.process_name.clone();
+                    e.raw_process_name = server.raw_process_name.clone();
+                    e.service = server.service.clone();
+                    e.service_info = server.service_info.clone();
+                }
+                // IPv4 and IPv6 sockets of one dual-stack process share a pid
+                if !e.pids.contains(&server.pid) {
+                    e.pids.push(server.pid);
+                }
+                for address in &server.bind_addresses {
+                    e.add_bind_address(address);
+                }
+            })
+            .or_insert(server);
+    }
+
+    let mut result: Vec<DevServer> = port_map.into_values().collect();
+    result.sort_by_key(|s| s.port);
+
+    result
+}
+
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, PartialEq)]
+struct ProcListener {
+    host: String,
+    port: u16,
+    inode: u64,
+}
+
+#[cfg(target_os = "linux")]
+/// Decodes a `/proc/net/tcp[6]` address such as `0100007F:0BB8` into lsof-style host and port
+fn decode_proc_address(address: &str) -> Option<(String, u16)> {
+    let (host_hex, port_hex) = address.split_once(':')?;
+    let port = u16::from_str_radix(port_hex, 16).ok()?;
+
+    // The kernel prints each 32-bit word of the address in host byte order
+    let mut bytes = Vec::with_capacity(16);
+    for word in host_hex.as_bytes().chunks(8) {
+        let word = u32::from_str_radix(std::str::from_utf8(word).ok()?, 16).ok()?;
+        bytes.extend_from_slice(&word.to_ne_bytes());
+    }
+
+    let host = match bytes.len() {
+        4 => {
+            let ip = std::net::Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3]);
+            if ip.is_unspecified() { "*".to_string() } else { ip.to_string() }
+        }
+        16 => {
+            let octets: [u8; 16] = bytes.try_into().ok()?;
+            let ip = std::net::Ipv6Addr::from(octets);
+            if ip.is_unspecified() { "[::]".to_string() } else { format!("[{}]", ip) }
+        }
+        _ => return None,
+    };
+
+    Some((host, port))
+}
+
+#[cfg(target_os = "linux")]
+fn parse_proc_net_tcp(contents: &str) -> Vec<ProcListener> {
+    const TCP_LISTEN: &str = "0A";
+
+    contents
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 || fields[3] != TCP_LISTEN {
+                return None;
+            }
+            let (host, port) = decode_proc_address(fields[1])?;
+            let inode = fields[9].parse::<u64>().ok()?;
+            Some(ProcListener { host, port, inode })
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+#[derive(Default)]
+struct ProcSocketScan {
+    sockets: Vec<(u32, String, ProcListener)>,
+    /// Processes whose fds we were not allowed to read
+    inaccessible_pids: usize,
+    /// Listening ports whose socket no readable process owned
+    unresolved_ports: Vec<u16>,
+}
+
+/// Result of the /proc scanner, which can only see other users' sockets with elevated permissions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NativeScan {
+    pub servers: Vec<DevServer>,
+    /// Some listening ports couldn't be traced to a process. Unreadable processes
+    /// that hold no listener (most root daemons) don't count.
+    pub partial: bool,
+    pub inaccessible_pids: usize,
+    pub unresolved_ports: Vec<u16>,
+}
+
+/// Every listening TCP socket visible in /proc, paired with its owning pid and command name
+#[cfg(target_os = "linux")]
+fn proc_listening_sockets() -> ProcSocketScan {
+    let mut by_inode: HashMap<u64, ProcListener> = HashMap::new();
+    for table in ["/proc/net/tcp", "/proc/net/tcp6"] {
+        if let Ok(contents) = std::fs::read_to_string(table) {
+            for listener in parse_proc_net_tcp(&contents) {
+                by_inode.insert(listener.inode, listener);
+            }
+        }
+    }
+
+    let mut scan = ProcSocketScan::default();
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return scan;
+    };
+    let mut resolved: HashSet<u64> = HashSet::new();
+
+    for entry in entries.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|n| n.parse::<u32>().ok()) else {
+            continue;
+        };
+        // Other users' fds are unreadable without privileges; processes may also exit mid-scan
+        let fds = match std::fs::read_dir(entry.path().join("fd")) {
+            Ok(fds) => fds,
+            Err(e) => {
+                if e.kind() == std::io::ErrorKind::PermissionDenied {
+                    scan.inaccessible_pids += 1;
+                }
+                log::trace!("proc: can't read fds of pid {}: {}", pid, e);
+                continue;
+            }
+        };
+
+        let mut name: Option<String> = None;
+        for fd in fds.flatten() {
+            let Ok(target) = std::fs::read_link(fd.path()) else {
+                continue;
+            };
+            let target = target.to_string_lossy();
+            let Some(inode) = target
+                .strip_prefix("socket:[")
+                .and_then(|rest| rest.strip_suffix(']'))
+                .and_then(|inode| inode.parse::<u64>().ok())
+            else {
+                continue;
+            };
+
+            if let Some(listener) = by_inode.get(&inode) {
+                resolved.insert(inode);
+                let name = name.get_or_insert_with(|| {
+                    std::fs::read_to_string(entry.path().join("comm"))
+                        .map(|comm| comm.trim().to_string())
+                        .unwrap_or_default()
+                });
+                scan.sockets.push((pid, name.clone(), listener.clone()));
+            }
+        }
+    }
+
+    let mut unresolved: Vec<u16> = by_inode
+        .iter()
+        .filter(|(inode, _)| !resolved.contains(inode))
+        .map(|(_, listener)| listener.port)
+        .collect();
+    unresolved.sort_unstable();
+    unresolved.dedup();
+    scan.unresolved_ports = unresolved;
+
+    scan
+}
+
+/// Native alternative to the lsof scanner that reads /proc directly
+#[cfg(target_os = "linux")]
+fn scan_dev_servers_proc() -> NativeScan {
+    let scan = proc_listening_sockets();
+    let servers = scan
+        .sockets
+        .into_iter()
+        .filter(|(_, name, _)| is_dev_process(name))
+        .map(|(pid, name, listener)| {
+            let service = detect_service(listener.port, &name);
+            let mut server = DevServer::new(listener.port, service, name, pid);
+            server.add_bind_address(&listener.host);
+            server
+        })
+        .collect();
+
+    NativeScan {
+        servers: merge_listeners(servers),
+        partial: !scan.unresolved_ports.is_empty(),
+        inaccessible_pids: scan.inaccessible_pids,
+        unresolved_ports: scan.unresolved_ports,
+    }
+}
+
+/// Scans with the /proc reader instead of lsof, reporting when some processes couldn't be inspected
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub async fn scan_dev_servers_native(db: State<'_, AgentDb>) -> Result<NativeScan, String> {
+    let mut scan = scan_dev_servers_proc();
+    scan.servers = exclude_app_servers(scan.servers);
+    enrich_servers(&mut scan.servers);
+    apply_compose_services(&db, &mut scan.servers)?;
+    apply_project_labels(&db, &mut scan.servers)?;
+
+    if scan.partial {
+        log::info!(
+            "Native scan couldn't find the process behind ports {:?}; run with elevated permissions to see all servers",
+            scan.unresolved_ports
+        );
+    }
+
+    Ok(scan)
+}
+
+#[cfg(not(target_os = "linux"))]
+#[tauri::command]
+pub async fn scan_dev_servers_native(_db: State<'_, AgentDb>) -> Result<NativeScan, String> {
+    Err("The native scanner is only available on Linux".to_string())
+}
+
+#[cfg(target_os = "windows")]
+async fn scan_dev_servers_windows() -> Result<Vec<DevServer>, String> {
+    // Locked-down machines sometimes block netstat but still allow PowerShell's cmdlets
+    let netstat_error = match scan_dev_servers_netstat() {
+        Ok(servers) => return Ok(servers),
+        Err(e) => e,
+    };
+    log::warn!("{}; falling back to Get-NetTCPConnection", netstat_error);
+
+    scan_dev_servers_net_tcp().map_err(|e| {
+        format!(
+            "Couldn't list listening ports with netstat or PowerShell. netstat: {}. Get-NetTCPConnection: {}",
+            netstat_error, e
+        )
+    })
+}
+
+#[cfg(target_os = "windows")]
+fn scan_dev_servers_net_tcp() -> Result<Vec<DevServer>, String> {
+    let output = Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            "Get-NetTCPConnection -State Listen | ForEach-Object { [pscustomobject]@{ LocalAddress = $_.LocalAddress; LocalPort = $_.LocalPort; OwningProcess = $_.OwningProcess; Name = (Get-Process -Id $_.OwningProcess -ErrorAction SilentlyContinue).ProcessName } } | ConvertTo-Json -Compress",
+        ])
+        .output()
+        .map_err(|e| format!("Failed to execute powershell: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Get-NetTCPConnection failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    parse_net_tcp_connections(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parses `Get-NetTCPConnection` rows (already joined with `Get-Process` names)
+#[cfg(target_os = "windows")]
+fn parse_net_tcp_connections(json: &str) -> Result<Vec<DevServer>, String> {
+    // No listeners at all prints nothing rather than `[]`
+    if json.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let json: serde_json::Value = serde_json::from_str(json)
+        .map_err(|e| format!("Failed to parse Get-NetTCPConnection output: {}", e))?;
+
+    let servers = json
+        .as_array()
+        .map(|rows| rows.iter().collect::<Vec<_>>())
+        .unwrap_or_else(|| vec![&json])
+        .into_iter()
+        .filter_map(|row| {
+            let port = u16::try_from(row["LocalPort"].as_u64()?).ok()?;
+            let pid = u32::try_from(row["OwningProcess"].as_u64()?).ok()?;
+            let process_name = row["Name"].as_str()?;
+            if !is_dev_process(process_name) {
+                return None;
+            }
+
+            let address = row["LocalAddress"].as_str().unwrap_or_default();
+            let host = if address.contains(':') { format!("[{}]", address) } else { address.to_string() };
+            let mut server = DevServer::new(port, detect_service(port, process_name), process_name.to_string(), pid);
+            server.add_bind_address(&host);
+            Some(server)
+        })
+        .collect();
+
+    Ok(merge_listeners(servers))
+}
+
+#[cfg(target_os = "windows")]
+fn scan_dev_servers_netstat() -> Result<Vec<DevServer>, String> {
+    let output = Command::new("netstat")
+        .args(&["-ano"])
+        .output()
+        .map_err(|e| format!("Failed to execute netstat: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("netstat exited with {}", output.status));
+    }
+
+    let output_str = String::from_utf8_lossy(&output.stdout);
+    let mut servers: Vec<DevServer> = Vec::new();
+
+    for line in output_str.lines().skip(4) {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+
+        if parts.len() < 5 || parts[0] != "TCP" {
+            continue;
+        }
+
+        let state = parts[3];
+        if state != "LISTENING" {
+            continue;
+        }
+
+        if let Some(addr) = parts.get(1) {
+            if let Some(port_str) = addr.split(':').last() {
+                if let Ok(port) = port_str.parse::<u16>() {
+                    if let Some(pid_str) = parts.get(4) {
+                        if let Ok(pid) = pid_str.parse::<u32>() {
+                            if let Ok(process_name) = get_process_name_windows(pid) {
+                                let is_dev_process = DEV_PROCESS_NAMES.iter().any(|&dev_name| {
+                                    process_name.to_lowercase().contains(dev_name)
+                                });
+
+                                if is_dev_process {
+                                    let service = detect_service(port, &process_name);
+
+                                    let mut server = DevServer::new(port, service, process_name, pid);
+                                    if let Some((host, _)) = addr.rsplit_once(':') {
+                                        server.add_bind_address(host);
+                                    }
+                                    servers.push(server);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(merge_listeners(servers))
+}
+
+#[cfg(target_os = "windows")]
+fn get_process_name_windows(pid: u32) -> Result<String, String> {
+    let output = Command::new("tasklist")
+        .args(&["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"])
+        .output()
+        .map_err(|e| format!("Failed to execute tasklist: {}", e))?;
+
+    if !output.status.success() {
+        return Err("tasklist command failed".to_string());
+    }
+
+    let output_str = String::from_utf8_lossy(&output.stdout);
+    if let Some(first_line) = output_str.lines().next() {
+        if let Some(name) = first_line.split(',').next() {
+            return Ok(name.trim_matches('"').to_string());
+        }
+    }
+
+    Err("Could not parse process name".to_string())
+}
+
+/// Display metadata for a detected service so the UI doesn't have to re-map labels
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceInfo {
+    pub label: String,
+    /// One of `frontend`, `backend`, `database`, `tooling`, `proxy`
+    pub category: String,
+    /// Stable identifier: vite, webpack, nextjs, react, astro, sveltekit, remix, nuxt, nodejs,
+    /// bun, express, flask, django, fastapi, rails, storybook, docusaurus, jupyter, go, tauri,
+    /// postgresql, mysql, mongodb, redis, docker, nginx, caddy, traefik, haproxy or server
+    pub icon_id: String,
+    pub color: String,
+}
+
+fn service_info(service: &str) -> ServiceInfo {
+    let (category, icon_id, color) = match service {
+        "Vite" => ("frontend", "vite", "#646CFF"),
+        "Webpack Dev" => ("frontend", "webpack", "#8DD6F9"),
+        "Next.js" => ("frontend", "nextjs", "#000000"),
+        "React/Next.js" => ("frontend", "react", "#61DAFB"),
+        "Storybook" => ("tooling", "storybook", "#FF4785"),
+        "Docusaurus" => ("tooling", "docusaurus", "#3ECC5F"),
+        "Astro" => ("frontend", "astro", "#FF5D01"),
+        "SvelteKit" => ("frontend", "sveltekit", "#FF3E00"),
+        "Remix" => ("frontend", "remix", "#121212"),
+        "Nuxt" => ("frontend", "nuxt", "#00DC82"),
+        "Tauri Dev" => ("tooling", "tauri", "#FFC131"),
+        "Jupyter" => ("tooling", "jupyter", "#F37626"),
+        "Bun Server" => ("backend", "bun", "#FBF0DF"),
+        "Node.js Dev" => ("backend", "nodejs", "#339933"),
+        "Express/Node" => ("backend", "express", "#828282"),
+        "Flask/Python" => ("backend", "flask", "#3776AB"),
+        "Django/Python" => ("backend", "django", "#092E20"),
+        "FastAPI" => ("backend", "fastapi", "#009688"),
+        "Rails" => ("backend", "rails", "#CC0000"),
+        "Go/Dev Server" => ("backend", "go", "#00ADD8"),
+        "PostgreSQL" => ("database", "postgresql", "#4169E1"),
+        "MySQL" => ("database", "mysql", "#4479A1"),
+        "MongoDB" => ("database", "mongodb", "#47A248"),
+        "Redis" => ("database", "redis", "#DC382D"),
+        "Docker" => ("tooling", "docker", "#2496ED"),
+        "Nginx" => ("proxy", "nginx", "#009639"),
+        "Caddy" => ("proxy", "caddy", "#1F88C0"),
+        "Traefik" => ("proxy", "traefik", "#24A1C1"),
+        "HAProxy" => ("proxy", "haproxy", "#106DA9"),
+        "Node Inspector" => ("tooling", "nodejs", "#339933"),
+        _ => ("backend", "server", "#6B7280"),
+    };
+
+    ServiceInfo {
+        label: service.to_string(),
+        category: category.to_string(),
+        icon_id: icon_id.to_string(),
+        color: color.to_string(),
+    }
+}
+
+const REVERSE_PROXY_SERVICES: &[&str] = &["Nginx", "Caddy", "Traefik", "HAProxy"];
+
+/// SvelteKit runs as a plain `vite dev`, so only its config file tells it apart
+fn is_sveltekit_project(project_root: &str) -> bool {
+    ["svelte.config.js", "svelte.config.mjs", "svelte.config.ts"]
+        .iter()
+        .any(|config| Path::new(project_root).join(config).exists())
+}
+
+/// Service label from the process name and port alone; `enrich_servers` refines it
+/// with the command line once that is known
+fn detect_service(port: u16, process_name: &str) -> String {
+    classify_service(port, process_name, None, None)
+}
+
+#[cfg(target_os = "linux")]
+fn get_process_cwd(pid: u32) -> Option<String> {
+    std::fs::read_link(format!("/proc/{}/cwd", pid))
+        .ok()
+        .map(|p| p.to_string_lossy().to_string())
+}
+
+#[cfg(target_os = "macos")]
+fn get_process_cwd(pid: u32) -> Option<String> {
+    let output = Command::new("lsof")
+        .args(["-a", "-p", &pid.to_string(), "-d", "cwd", "-Fn"])
+        .output()
+        .inspect_err(|e| log::debug!("Failed to run lsof for the cwd of pid {}: {}", pid, e))
+        .ok()?;
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.strip_prefix('n').map(|p| p.to_string()))
+}
+
+#[cfg(target_os = "windows")]
+fn get_process_cwd(_pid: u32) -> Option<String> {
+    None
+}
+
+/// Working directories of every detected server, for callers that must not disturb them
+pub(crate) async fn running_server_cwds() -> Result<Vec<String>, String> {
+    Ok(scan_listening_servers()
+        .await?
+        .iter()
+        .filter_map(|s| get_process_cwd(s.pid))
+        .map(|cwd| canonicalize_cwd(&cwd))
+        .collect())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunningPackageManager {
+    pub port: u16,
+    pub pid: u32,
+    pub package_manager: String,
+}
+
+/// Package managers that launched the servers running from inside `root`, found by
+/// walking each server's parent processes (`pnpm dev` -> sh -> node)
+pub(crate) async fn running_package_managers(root: &Path) -> Result<Vec<RunningPackageManager>, String> {
+    let servers = scan_listening_servers().await?;
+    let processes = list_processes().unwrap_or_else(|e| {
+        log::debug!("Finding package managers without process details: {}", e);
+        Vec::new()
+    });
+    let by_pid: HashMap<u32, &ProcessDetails> = processes.iter().map(|p| (p.pid, p)).collect();
+
+    Ok(servers
+        .iter()
+        .filter(|s| {
+            get_process_cwd(s.pid)
+                .map(|cwd| Path::new(&canonicalize_cwd(&cwd)).starts_with(root))
+                .unwrap_or(false)
+        })
+        .filter_map(|s| {
+            let package_manager = process_ancestors(s.pid, &by_pid)
+                .find_map(|pid| by_pid.get(&pid).and_then(|p| detect_package_manager(&p.command)))?;
+            Some(RunningPackageManager {
+                port: s.port,
+                pid: s.pid,
+                package_manager: package_manager.to_string(),
+            })
+        })
+        .collect())
+}
+
+/// Resolves symlinks so servers started through a linked path group with the real directory
+fn canonicalize_cwd(cwd: &str) -> String {
+    match std::fs::canonicalize(cwd) {
+        Ok(path) => {
+            let path = path.to_string_lossy().to_string();
+            // Windows returns verbatim `\\?\C:\...` paths
+            path.strip_prefix(r"\\?\").map(String::from).unwrap_or(path)
+        }
+        Err(_) => cwd.to_string(),
+    }
+}
+
+fn find_project_root(cwd: &str) -> String {
+    let cwd_path = Path::new(cwd);
+
+    if let Some(git_root) = cwd_path.ancestors().find(|dir| dir.join(".git").exists()) {
+        return git_root.to_string_lossy().to_string();
+    }
+
+    cwd_path
+        .ancestors()
+        .find(|dir| PROJECT_ROOT_MARKERS.iter().any(|marker| dir.join(marker).exists()))
+        .unwrap_or(cwd_path)
+        .to_string_lossy()
+        .to_string()
+}
+
+fn compute_server_id(server: &DevServer) -> String {
+    let scope = server
+        .project_root
+        .as_deref()
+        .unwrap_or(&server.process_name);
+
+    let mut hasher = Sha256::new();
+    hasher.update(format!("{}:{}", scope, server.port).as_bytes());
+    format!("{:x}", hasher.finalize())[..16].to_string()
+}
+
+/// Fills in the working directory, project root and stable `server_id`
+fn assign_server_id(server: &mut DevServer) {
+    server.raw_cwd = get_process_cwd(server.pid);
+    if server.raw_cwd.is_none() {
+        log::debug!("No cwd for {} (pid {}) on port {}", server.raw_process_name, server.pid, server.port);
+    }
+    server.cwd = server.raw_cwd.as_deref().map(canonicalize_cwd);
+    server.project_root = server.cwd.as_deref().map(find_project_root);
+    server.server_id = compute_server_id(server);
+}
+
+fn enrich_servers(servers: &mut [DevServer]) {
+    let processes = list_processes().unwrap_or_else(|e| {
+        log::debug!("Enriching without process details: {}", e);
+        Vec::new()
+    });
+    let by_pid: HashMap<u32, &ProcessDetails> = processes.iter().map(|p| (p.pid, p)).collect();
+    prune_process_info_cache(&by_pid);
+
+    for server in servers.iter_mut() {
+        assign_server_id(server);
+        server.priority = get_process_priority(server.pid);
+        // Grouped listeners count from their earliest-started process
+        server.uptime_secs = server
+            .pids
+            .iter()
+            .filter_map(|pid| by_pid.get(pid).and_then(|p| p.uptime_secs))
+            .max();
+        let command = by_pid.get(&server.pid).map(|p| p.command.as_str());
+        let mut classification = classify_service_detailed(server.port, &server.raw_process_name, command, None);
+        if classification.service == "Vite" && server.project_root.as_deref().is_some_and(is_sveltekit_project) {
+            classification.service = "SvelteKit".to_string();
+            classification.classified = true;
+        }
+        if classification.service != server.service {
+            server.service_info = service_info(&classification.service);
+            server.service = classification.service;
+        }
+        server.classified = classification.classified;
+        server.confidence = classification.confidence;
+        server.data_loss_note = command.and_then(|command| in_memory_data_note(&server.process_name, command));
+        server.data_loss_on_kill = server.data_loss_note.is_some();
+        apply_process_manager(server, &by_pid);
+        apply_proxy_info(server, &by_pid);
+        server.architecture = cached_process_architecture(server.pid, &server.raw_process_name);
+        server.architecture_mismatch = server
+            .architecture
+            .as_deref()
+            .is_some_and(|arch| Some(arch) != native_architecture());
+        if server.process_name.to_lowercase().contains("python") {
+            server.python_env = detect_python_env(server.pid, &server.raw_process_name);
+        }
+    }
+
+    if servers.iter().any(|s| s.managed_by.as_deref() == Some("pm2")) {
+        let pm2_names = pm2_process_names();
+        for server in servers.iter_mut().filter(|s| s.managed_by.as_deref() == Some("pm2")) {
+            server.managed_name = process_ancestors(server.pid, &by_pid)
+                .find_map(|pid| pm2_names.get(&pid).cloned());
+        }
+    }
+
+    apply_debug_ports(servers, &by_pid);
+}
+
+/// Helpers that run throwaway databases for tests, all of which live in memory or a temp dir
+const EPHEMERAL_DATABASE_TOOLS: &[&str] = &[
+    "mongodb-memory-server",
+    "redis-memory-server",
+    "embedded-postgres",
+    "pg_tmp",
+    "pglite",
+];
+
+/// Why killing this process would lose data nobody can get back, judged from its
+/// name and command line: memory-only stores, persistence switched off, or data on tmpfs
+fn in_memory_data_note(process_name: &str, command: &str) -> Option<String> {
+    let args: Vec<&str> = command.split_whitespace().collect();
+    let has_arg = |flag: &str, value: &str| args.windows(2).any(|pair| pair[0] == flag && pair[1] == value);
+    let program = args.first().map(|arg| canonical_process_name(arg)).unwrap_or_default();
+    let is = |names: &[&str]| names.contains(&process_name) || names.contains(&program.as_str());
+    // An empty `--save ""` argument vanishes from the joined command line
+    let save_disabled = args.iter().position(|arg| *arg == "--save").is_some_and(|i| {
+        args.get(i + 1)
+            .is_none_or(|value| value.starts_with("--") || *value == "\"\"" || *value == "''")
+    });
+
+    if let Some(tool) = EPHEMERAL_DATABASE_TOOLS.iter().find(|tool| command.contains(*tool)) {
+        return Some(format!("Test database started by {}; its data is discarded on exit", tool));
+    }
+    if is(&["memcached"]) {
+        return Some("memcached keeps everything in memory".to_string());
+    }
+    if is(&["redis-server", "valkey-server"]) && save_disabled && !has_arg("--appendonly", "yes") {
+        return Some("Redis persistence is disabled (--save \"\"), so its data only exists in memory".to_string());
+    }
+    if is(&["mongod"]) && has_arg("--storageEngine", "inMemory") {
+        return Some("MongoDB is using the inMemory storage engine".to_string());
+    }
+    if command.contains(":memory:") || command.contains("jdbc:h2:mem:") {
+        return Some("Uses an in-memory database that disappears with the process".to_string());
+    }
+    if let Some(path) = args.iter().find(|arg| arg.starts_with("/dev/shm/") || arg.contains("=/dev/shm/")) {
+        return Some(format!("Stores data on tmpfs ({}), which is lost on reboot", path));
+    }
+
+    None
+}
+
+/// Node's default `--inspect` port
+const NODE_INSPECTOR_PORT: u16 = 9229;
+/// Ports Node falls back to when 9229 is taken by another inspected process
+const NODE_INSPECTOR_PORTS: std::ops::RangeInclusive<u16> = 9229..=9239;
+
+/// Port of `--inspect`, `--inspect-brk` or `--inspect-wait` (`--inspect=9230`,
+/// `--inspect=0.0.0.0:9230`), or the default when the flag has no value
+fn inspect_flag_port(command: &str) -> Option<u16> {
+    command.split_whitespace().find_map(|arg| {
+        let flag = arg.strip_prefix("--inspect")?;
+        let flag = flag
+            .strip_prefix("-brk")
+            .or_else(|| flag.strip_prefix("-wait"))
+            .unwrap_or(flag);
+        if flag.is_empty() {
+            return Some(NODE_INSPECTOR_PORT);
+        }
+        // `--inspect=0` picks a random port, found by the listener fallback instead
+        flag.strip_prefix('=')?.rsplit(':').next()?.parse().ok().filter(|&port| port != 0)
+    })
+}
+
+/// The inspector listener among a process's ports: the `--inspect` port when the
+/// flag is present, else a second port in Node's inspector range
+fn inspector_port(group: &PidPortGroup, command: Option<&str>) -> Option<u16> {
+    if group.ports.len() < 2 {
+        return None;
+    }
+    match command.and_then(inspect_flag_port) {
+        Some(port) => group.ports.contains(&port).then_some(port),
+        None if canonical_process_name(&group.process_name) == "node" => group
+            .ports
+            .iter()
+            .copied()
+            .find(|port| NODE_INSPECTOR_PORTS.contains(port)),
+        None => None,
+    }
+}
+
+/// Points each server at the inspector its process opened and relabels the
+/// inspector listener itself, so it isn't shown as a second app server
+fn apply_debug_ports(servers: &mut [DevServer], by_pid: &HashMap<u32, &ProcessDetails>) {
+    for group in group_by_pid(servers) {
+        let command = by_pid.get(&group.pid).map(|p| p.command.as_str());
+        let Some(debug_port) = inspector_port(&group, command) else {
+            continue;
+        };
+        for server in servers.iter_mut().filter(|s| group.ports.contains(&s.port)) {
+            server.debug_port = Some(debug_port);
+            if server.port == debug_port {
+                server.service = "Node Inspector".to_string();
+                server.service_info = service_info(&server.service);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DevtoolsTarget {
+    pub debug_port: u16,
+    /// `devtools://` URL for the inspector's first target; None when it didn't answer
+    pub devtools_url: Option<String>,
+    pub websocket_url: Option<String>,
+    /// Fallback page listing every inspectable process in Chrome
+    pub inspect_url: String,
+}
+
+/// How to attach Chrome DevTools to the server on `port`, read from its inspector's `/json/list`
+#[tauri::command]
+pub async fn get_devtools_url(port: u16) -> Result<DevtoolsTarget, String> {
+    // Enrich everything, not just `port`, so the inspector listener is grouped with it
+    let mut servers = scan_listening_servers().await?;
+    enrich_servers(&mut servers);
+    let debug_port = servers
+        .iter()
+        .find(|s| s.port == port)
+        .ok_or_else(|| format!("No server is listening on port {}", port))?
+        .debug_port
+        .ok_or_else(|| format!("The server on port {} has no inspector open; start it with --inspect", port))?;
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_millis(HEALTH_CHECK_TIMEOUT_MS))
+        .no_proxy()
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let targets: Vec<serde_json::Value> = match client
+        .get(format!("http://127.0.0.1:{}/json/list", debug_port))
+        .send()
+        .await
+    {
+        Ok(response) => response.json().await.unwrap_or_default(),
+        Err(_) => Vec::new(),
+    };
+    let target = targets.first();
+
+    Ok(DevtoolsTarget {
+        debug_port,
+        devtools_url: target
+            .and_then(|t| t["devtoolsFrontendUrl"].as_str())
+            .map(str::to_string),
+        websocket_url: target
+            .and_then(|t| t["webSocketDebuggerUrl"].as_str())
+            .map(str::to_string),
+        inspect_url: "chrome://inspect".to_string(),
+    })
+}
+
+/// The active virtualenv/conda env, from the environment or else the interpreter's location
+fn detect_python_env(pid: u32, process_name: &str) -> Option<String> {
+    let env = get_process_env(pid);
+    let var = |name: &str| {
+        env.iter()
+            .find(|(key, value)| key == name && !value.is_empty())
+            .map(|(_, value)| value.clone())
+    };
+    if let Some(env_dir) = var("VIRTUAL_ENV").or_else(|| var("CONDA_PREFIX")) {
+        return Some(env_dir);
+    }
+
+    // e.g. /project/.venv/bin/python or ~/miniconda3/envs/api/bin/python
+    let exe = cached_process_exe(pid, process_name)?;
+    exe.ancestors().skip(1).find_map(|dir| {
+        let is_venv = dir.join("pyvenv.cfg").exists();
+        let is_conda_env = dir.join("conda-meta").is_dir();
+        (is_venv || is_conda_env).then(|| dir.to_string_lossy().to_string())
+    })
+}
+
+const PROCESS_MANAGERS: &[&str] = &["pm2", "foreman", "overmind", "honcho", "hivemind"];
+
+/// Yields `pid` followed by its parents, stopping at init
+fn process_ancestors<'a>(
+    pid: u32,
+    by_pid: &'a HashMap<u32, &'a ProcessDetails>,
+) -> impl Iterator<Item = u32> + 'a {
+    let mut current = Some(pid);
+    let mut steps = 0;
+    std::iter::from_fn(move || {
+        let pid = current?;
+        steps += 1;
+        current = by_pid
+            .get(&pid)
+            .map(|p| p.ppid)
+            .filter(|&ppid| ppid > 1 && ppid != pid && steps < 32);
+        Some(pid)
+    })
+}
+
+fn detect_process_manager(command: &str) -> Option<&'static str> {
+    // The pm2 daemon renames itself to "PM2 vX.Y.Z: God Daemon"; the others usually run under ruby/python/node
+    command.split_whitespace().take(2).find_map(|arg| {
+        let name = executable_name(arg).to_lowercase();
+        PROCESS_MANAGERS.iter().find(|&&manager| manager == name).copied()
+    })
+}
+
+fn apply_process_manager(server: &mut DevServer, by_pid: &HashMap<u32, &ProcessDetails>) {
+    let manager = process_ancestors(server.pid, by_pid)
+        .skip(1)
+        .find_map(|pid| by_pid.get(&pid).and_then(|p| detect_process_manager(&p.command)));
+
+    let Some(manager) = manager else {
+        return;
+    };
+    server.managed_by = Some(manager.to_string());
+
+    // Procfile runners export the entry name (e.g. "web.1") as PS
+    if manager != "pm2" {
+        server.managed_name = process_ancestors(server.pid, by_pid).find_map(|pid| {
+            get_process_env(pid)
+                .into_iter()
+                .find(|(key, _)| key == "PS")
+                .map(|(_, value)| value)
+        });
+    }
+}
+
+/// Flag for `-c`/`--config`-style arguments followed by a path, in either
+/// `--flag value` or `--flag=value` form
+fn command_flag_value(command: &str, flags: &[&str]) -> Option<String> {
+    let mut args = command.split_whitespace();
+    while let Some(arg) = args.next() {
+        for flag in flags {
+            if arg == *flag {
+                return args.next().map(String::from);
+            }
+            if let Some(value) = arg.strip_prefix(&format!("{}=", flag)) {
+                return Some(value.to_string());
+            }
+        }
+    }
+    None
+}
+
+/// The proxy's config files: an explicit config flag, else the usual default locations
+fn proxy_config_files(proxy: &str, command: &str, cwd: Option<&str>) -> Vec<std::path::PathBuf> {
+    let explicit = match proxy {
+        "Nginx" | "HAProxy" => command_flag_value(command, &["-c", "-f"]),
+        "Caddy" => command_flag_value(command, &["--config"]),
+        "Traefik" => command_flag_value(command, &["--configFile", "--configfile"]),
+        _ => None,
+    };
+    if let Some(path) = explicit {
+        let path = std::path::PathBuf::from(path);
+        return vec![match cwd {
+            Some(cwd) if path.is_relative() => Path::new(cwd).join(path),
+            _ => path,
+        }];
+    }
+
+    let defaults: &[&str] = match proxy {
+        "Nginx" => &[
+            "/etc/nginx/nginx.conf",
+            "/usr/local/etc/nginx/nginx.conf",
+            "/opt/homebrew/etc/nginx/nginx.conf",
+        ],
+        "Caddy" => &["Caddyfile", "/etc/caddy/Caddyfile"],
+        "Traefik" => &["traefik.yml", "traefik.yaml", "traefik.toml", "/etc/traefik/traefik.yml"],
+        "HAProxy" => &["/etc/haproxy/haproxy.cfg", "/usr/local/etc/haproxy/haproxy.cfg"],
+        _ => &[],
+    };
+    defaults
+        .iter()
+        .map(|path| match cwd {
+            Some(cwd) if Path::new(path).is_relative() => Path::new(cwd).join(path),
+            _ => std::path::PathBuf::from(path),
+        })
+        .collect()
+}
+
+/// Reads a config and, for nginx, the files it `include`s; unreadable files are skipped
+fn read_proxy_configs(files: Vec<std::path::PathBuf>) -> String {
+    let include = regex::Regex::new(r"(?m)^\s*include\s+([^;]+);").expect("valid include regex");
+    let mut pending = files;
+    let mut seen = HashSet::new();
+    let mut content = String::new();
+
+    while let Some(file) = pending.pop() {
+        if seen.len() > 64 || !seen.insert(file.clone()) {
+            continue;
+        }
+        let Ok(text) = std::fs::read_to_string(&file) else {
+            continue;
+        };
+        for capture in include.captures_iter(&text) {
+            let pattern = capture[1].trim().trim_matches('"');
+            let pattern = match file.parent() {
+                Some(dir) if Path::new(pattern).is_relative() => {
+                    dir.join(pattern).to_string_lossy().to_string()
+                }
+                _ => pattern.to_string(),
+            };
+            if let Ok(paths) = glob::glob(&pattern) {
+                pending.extend(paths.filter_map(Result::ok));
+            }
+        }
+        content.push_str(&text);
+        content.push('\n');
+    }
+
+    content
+}
+
+fn local_upstream_ports(config: &str) -> Vec<u16> {
+    let upstream = regex::Regex::new(
+        r"\b(?:proxy_pass|reverse_proxy|server|url)\b[^\n]*?(?:localhost|127\.0\.0\.1|\[::1\]|0\.0\.0\.0|\s):(\d{2,5})\b",
+    )
+    .expect("valid upstream regex");
+
+    let mut ports: Vec<u16> = upstream
+        .captures_iter(config)
+        .filter_map(|c| c[1].parse::<u16>().ok())
+        .collect();
+    ports.sort_unstable();
+    ports.dedup();
+    ports
+}
+
+fn apply_proxy_info(server: &mut DevServer, by_pid: &HashMap<u32, &ProcessDetails>) {
+    let command = by_pid.get(&server.pid).map(|p| p.command.clone()).unwrap_or_default();
+
+    // Node-based dev proxies show up as plain `node` processes
+    if command.contains("local-ssl-proxy") || command.contains("http-proxy") {
+        server.is_proxy = true;
+        if let Some(target) = command_flag_value(&command, &["--target", "-t"]) {
+            server.upstream_ports = target
+                .rsplit(':')
+                .next()
+                .and_then(|port| port.parse().ok())
+                .into_iter()
+                .collect();
+        }
+        return;
+    }
+
+    if !REVERSE_PROXY_SERVICES.contains(&server.service.as_str()) {
+        return;
+    }
+    server.is_proxy = true;
+
+    let files = proxy_config_files(&server.service, &command, server.cwd.as_deref());
+    let port = server.port;
+    server.upstream_ports = local_upstream_ports(&read_proxy_configs(files))
+        .into_iter()
+        .filter(|upstream| *upstream != port)
+        .collect();
+}
+
+fn pm2_process_names() -> HashMap<u32, String> {
+    let Ok(pm2) = which::which("pm2") else {
+        return HashMap::new();
+    };
+    let Ok(output) = Command::new(pm2).arg("jlist").output() else {
+        return HashMap::new();
+    };
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    // Older pm2 versions print update notices before the JSON payload
+    let json = text.find('[').map(|start| &text[start..]).unwrap_or("");
+
+    serde_json::from_str::<Vec<serde_json::Value>>(json)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|entry| {
+            let pid = u32::try_from(entry.get("pid")?.as_u64()?).ok()?;
+            let name = entry.get("name")?.as_str()?.to_string();
+            Some((pid, name))
+        })
+        .collect()
+}
+
+/// Docker-published ports belong to the proxy process, so attribute them to the tracked
+/// project whose compose file publishes that port
+fn apply_compose_services(db: &AgentDb, servers: &mut [DevServer]) -> Result<(), String> {
+    if !servers.iter().any(|s| s.service == "Docker") {
+        return Ok(());
+    }
+
+    let mut published: HashMap<u16, (String, String)> = HashMap::new();
+    for project in load_tracked_projects(db)? {
+        for port in compose_published_ports(Path::new(&project.path)) {
+            published
+                .entry(port.host_port)
+                .or_insert((port.service, project.path.clone()));
+        }
+    }
+
+    for server in servers.iter_mut().filter(|s| s.service == "Docker") {
+        if let Some((service, project_root)) = published.get(&server.port) {
+            server.compose_service = Some(service.clone());
+            server.project_root = Some(project_root.clone());
+            server.server_id = compute_server_id(server);
+        }
+    }
+
+    Ok(())
+}
+
+fn apply_project_labels(db: &AgentDb, servers: &mut [DevServer]) -> Result<(), String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let mut stmt = conn
+        .prepare("SELECT label FROM project_labels WHERE project_root = ?1 ORDER BY label")
+        .map_err(|e| e.to_string())?;
+
+    for server in servers.iter_mut() {
+        if let Some(root) = &server.project_root {
+            server.labels = stmt
+                .query_map(rusqlite::params![root], |row| row.get::<_, String>(0))
+                .map_err(|e| e.to_string())?
+                .filter_map(|label| label.ok())
+                .collect();
+        }
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn label_project_servers(
+    project_root: String,
+    label: String,
+    db: State<'_, AgentDb>,
+) -> Result<Vec<DevServer>, String> {
+    let label = label.trim().to_string();
+    if label.is_empty() {
+        return Err("Label cannot be empty".to_string());
+    }
+
+    {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        conn.execute(
+            "INSERT OR IGNORE INTO project_labels (project_root, label) VALUES (?1, ?2)",
+            rusqlite::params![project_root, label],
+        )
+        .map_err(|e| format!("Failed to save label: {}", e))?;
+    }
+
+    let mut servers = scan_listening_servers().await?;
+    enrich_servers(&mut servers);
+    apply_project_labels(&db, &mut servers)?;
+
+    Ok(servers
+        .into_iter()
+        .filter(|s| s.project_root.as_deref() == Some(project_root.as_str()))
+        .collect())
+}
+
+#[tauri::command]
+pub fn remove_project_label(
+    project_root: String,
+    label: String,
+    db: State<'_, AgentDb>,
+) -> Result<(), String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    conn.execute(
+        "DELETE FROM project_labels WHERE project_root = ?1 AND label = ?2",
+        rusqlite::params![project_root, label],
+    )
+    .map_err(|e| format!("Failed to remove label: {}", e))?;
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessDetails {
+    pub pid: u32,
+    pub ppid: u32,
+    pub name: String,
+    pub command: String,
+    pub uptime_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageManagerProcess {
+    pub pid: u32,
+    pub package_manager: String,
+    pub command: String,
+    pub cwd: Option<String>,
+    pub uptime_secs: Option<u64>,
+    pub is_install: bool,
+}
+
+const PACKAGE_MANAGERS: &[&str] = &["npm", "npx", "pnpm", "yarn", "bun"];
+
+fn parse_etime(etime: &str) -> Option<u64> {
+    let (days, rest) = match etime.split_once('-') {
+        Some((days, rest)) => (days.parse::<u64>().ok()?, rest),
+        None => (0, etime),
+    };
+
+    let mut secs = 0;
+    for part in rest.split(':') {
+        secs = secs * 60 + part.parse::<u64>().ok()?;
+    }
+
+    Some(days * 86_400 + secs)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn list_processes() -> Result<Vec<ProcessDetails>, String> {
+    let output = Command::new("ps")
+        .args(["-axo", "pid=,ppid=,etime=,args="])
+        .output()
+        .map_err(|e| format!("Failed to execute ps: {}", e))?;
+
+    if !output.status.success() {
+        return Err("ps command failed".to_string());
+    }
+
+    let output_str = String::from_utf8_lossy(&output.stdout);
+    let mut processes = Vec::new();
+
+    for line in output_str.lines() {
+        let mut parts = line.split_whitespace();
+        let (Some(pid), Some(ppid), Some(etime)) = (parts.next(), parts.next(), parts.next()) else {
+            continue;
+        };
+        let (Ok(pid), Ok(ppid)) = (pid.parse::<u32>(), ppid.parse::<u32>()) else {
+            continue;
+        };
+
+        let command = parts.collect::<Vec<_>>().join(" ");
+        let name = command
+            .split_whitespace()
+            .next()
+            .map(executable_name)
+            .unwrap_or_default();
+
+        processes.push(ProcessDetails {
+            pid,
+            ppid,
+            name,
+            command,
+            uptime_secs: parse_etime(etime),
+        });
+    }
+
+    Ok(processes)
+}
+
+#[cfg(target_os = "windows")]
+fn list_processes() -> Result<Vec<ProcessDetails>, String> {
+    let output = Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            "Get-CimInstance Win32_Process | Select-Object ProcessId,ParentProcessId,Name,CommandLine,@{n='Age';e={[int]((Get-Date) - $_.CreationDate).TotalSeconds}} | ConvertTo-Json -Compress",
+        ])
+        .output()
+        .map_err(|e| format!("Failed to execute powershell: {}", e))?;
+
+    if !output.status.success() {
+        return Err("Get-CimInstance command failed".to_string());
+    }
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse process list: {}", e))?;
+
+    Ok(json
+        .as_array()
+        .map(|rows| rows.iter().collect::<Vec<_>>())
+        .unwrap_or_else(|| vec![&json])
+        .into_iter()
+        .filter_map(|row| {
+            Some(ProcessDetails {
+                pid: row["ProcessId"].as_u64()? as u32,
+                ppid: row["ParentProcessId"].as_u64().unwrap_or(0) as u32,
+                name: row["Name"].as_str().unwrap_or_default().to_string(),
+                command: row["CommandLine"].as_str().unwrap_or_default().to_string(),
+                uptime_secs: row["Age"].as_u64(),
+            })
+        })
+        .collect())
+}
+
+fn executable_name(path: &str) -> String {
+    let name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    name.strip_suffix(".exe").unwrap_or(name).to_string()
+}
+
+fn detect_package_manager(command: &str) -> Option<&'static str> {
+    // Package managers often run as `node /path/to/npm-cli.js ...`, so look past the interpreter
+    command.split_whitespace().take(2).find_map(|arg| {
+        let name = executable_name(arg);
+        let name = name
+            .trim_end_matches(".js")
+            .trim_end_matches(".cjs")
+            .trim_end_matches("-cli");
+        PACKAGE_MANAGERS.iter().find(|&&pm| pm == name).copied()
+    })
+}
+
+#[tauri::command]
+pub async fn scan_package_manager_processes() -> Result<Vec<PackageManagerProcess>, String> {
+    let own_pid = std::process::id();
+
+    let mut result: Vec<PackageManagerProcess> = list_processes()?
+        .into_iter()
+        .filter(|p| p.pid != own_pid)
+        .filter_map(|p| {
+            let package_manager = detect_package_manager(&p.command)?;
+            let is_install = p.command.split_whitespace().any(|arg| {
+                matches!(arg, "install" | "i" | "add" | "ci" | "update" | "upgrade")
+            });
+
+            Some(PackageManagerProcess {
+                pid: p.pid,
+                package_manager: package_manager.to_string(),
+                cwd: get_process_cwd(p.pid),
+                command: p.command,
+                uptime_secs: p.uptime_secs,
+                is_install,
+            })
+        })
+        .collect();
+
+    result.sort_by_key(|p| std::cmp::Reverse(p.uptime_secs));
+
+    Ok(result)
+}
+
+const REPRODUCE_ENV_VARS: &[&str] = &[
+    "PORT", "HOST", "NODE_ENV", "NODE_OPTIONS", "BROWSER", "FLASK_APP", "FLASK_ENV", "FLASK_DEBUG",
+    "DJANGO_SETTINGS_MODULE", "RAILS_ENV", "RACK_ENV", "APP_ENV",
+];
+const REPRODUCE_ENV_PREFIXES: &[&str] = &["VITE_", "NEXT_PUBLIC_", "REACT_APP_"];
+
+fn shell_quote(value: &str) -> String {
+    let safe = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c));
+
+    if safe {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn get_process_argv(pid: u32) -> Option<Vec<String>> {
+    let raw = std::fs::read(format!("/proc/{}/cmdline", pid)).ok()?;
+    let argv: Vec<String> = raw
+        .split(|b| *b == 0)
+        .filter(|arg| !arg.is_empty())
+        .map(|arg| String::from_utf8_lossy(arg).to_string())
+        .collect();
+
+    (!argv.is_empty()).then_some(argv)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn get_process_argv(_pid: u32) -> Option<Vec<String>> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn get_process_env(pid: u32) -> Vec<(String, String)> {
+    std::fs::read(format!("/proc/{}/environ", pid))
+        .map(|raw| {
+            raw.split(|b| *b == 0)
+                .filter_map(|entry| {
+                    let entry = String::from_utf8_lossy(entry);
+                    let (key, value) = entry.split_once('=')?;
+                    Some((key.to_string(), value.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "macos")]
+fn get_process_env(pid: u32) -> Vec<(String, String)> {
+    // `ps eww` appends the environment to the command line (only for our own user's processes).
+    // Best effort: values containing spaces are re-joined onto the previous variable.
+    let Ok(output) = Command::new("ps")
+        .args(["eww", "-o", "command=", "-p", &pid.to_string()])
+        .output()
+    else {
+        return Vec::new();
+    };
+
+    let is_var = |token: &str| {
+        token.split_once('=').is_some_and(|(key, _)| {
+            !key.is_empty()
+                && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                && !key.starts_with(|c: char| c.is_ascii_digit())
+        })
+    };
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    let mut env: Vec<(String, String)> = Vec::new();
+    for token in text.split_whitespace() {
+        if is_var(token) {
+            let (key, value) = token.split_once('=').unwrap_or((token, ""));
+            env.push((key.to_string(), value.to_string()));
+        } else if let Some((_, value)) = env.last_mut() {
+            value.push(' ');
+            value.push_str(token);
+        }
+    }
+    env
+}
+
+#[cfg(target_os = "windows")]
+fn get_process_env(_pid: u32) -> Vec<(String, String)> {
+    Vec::new()
+}
+
+/// Builds a copy-pasteable shell snippet that starts the process the same way again
+#[tauri::command]
+pub async fn get_reproduce_command(pid: u32) -> Result<String, String> {
+    let cwd = get_process_cwd(pid)
+        .ok_or_else(|| format!("Could not determine the working directory of process {}", pid))?;
+
+    // Prefer the exact argv so arguments containing spaces survive quoting
+    let command_line = match get_process_argv(pid) {
+        Some(argv) => argv.iter().map(|arg| shell_quote(arg)).collect::<Vec<_>>().join(" "),
+        None => list_processes()?
+            .into_iter()
+            .find(|p| p.pid == pid)
+            .map(|p| p.command)
+            .filter(|command| !command.trim().is_empty())
+            .ok_or_else(|| format!("Could not determine the command line of process {}", pid))?,
+    };
+
+    let mut env: Vec<(String, String)> = get_process_env(pid)
+        .into_iter()
+        .filter(|(key, _)| {
+            REPRODUCE_ENV_VARS.contains(&key.as_str())
+                || REPRODUCE_ENV_PREFIXES.iter().any(|prefix| key.starts_with(prefix))
+        })
+        .collect();
+    env.sort();
+
+    let env_prefix: String = env
+        .iter()
+        .map(|(key, value)| format!("{}={} ", key, shell_quote(value)))
+        .collect();
+
+    Ok(format!("cd {} && {}{}", shell_quote(&cwd), env_prefix, command_line))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExposureWarning {
+    pub port: u16,
+    pub service: String,
+    pub process_name: String,
+    pub exposure: String,
+    pub bind_addresses: Vec<String>,
+    pub severity: String,
+}
+
+fn exposure_severity(service: &str) -> &'static str {
+    match service {
+        "PostgreSQL" | "MySQL" | "MongoDB" | "Redis" | "Jupyter" => "high",
+        "Vite" | "Webpack Dev" | "Storybook" | "Tauri Dev" => "low",
+        _ => "medium",
+    }
+}
+
+#[tauri::command]
+pub async fn security_audit() -> Result<Vec<ExposureWarning>, String> {
+    let servers = scan_listening_servers().await?;
+
+    let mut warnings: Vec<ExposureWarning> = servers
+        .into_iter()
+        .filter(|s| s.exposure != "loopback")
+        .map(|s| ExposureWarning {
+            severity: exposure_severity(&s.service).to_string(),
+            port: s.port,
+            service: s.service,
+            process_name: s.process_name,
+            exposure: s.exposure,
+            bind_addresses: s.bind_addresses,
+        })
+        .collect();
+
+    let rank = |severity: &str| match severity {
+        "high" => 0,
+        "medium" => 1,
+        _ => 2,
+    };
+    warnings.sort_by_key(|w| (rank(&w.severity), w.port));
+
+    Ok(warnings)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateServerInstance {
+    pub port: u16,
+    pub pids: Vec<u32>,
+    pub uptime_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateServerWarning {
+    pub project_root: String,
+    pub service: String,
+    pub message: String,
+    /// Ordered oldest first
+    pub instances: Vec<DuplicateServerInstance>,
+    pub oldest_port: u16,
+}
+
+/// Identified servers keyed by project root and service. Generic port-range labels
+/// such as `Dev Server` say nothing about what runs, so sharing one isn't a duplicate.
+fn duplicate_candidates(servers: Vec<DevServer>) -> BTreeMap<(String, String), Vec<DevServer>> {
+    let mut groups: BTreeMap<(String, String), Vec<DevServer>> = BTreeMap::new();
+    for server in servers {
+        if !server.classified {
+            continue;
+        }
+        if let Some(root) = server.project_root.clone() {
+            groups.entry((root, server.service.clone())).or_default().push(server);
+        }
+    }
+    groups
+}
+
+/// Flags projects running more than one server of the same kind, e.g. two Vite
+/// servers left behind after a crash
+#[tauri::command]
+pub async fn detect_duplicate_servers() -> Result<Vec<DuplicateServerWarning>, String> {
+    let mut servers = scan_listening_servers().await?;
+    enrich_servers(&mut servers);
+
+    let warnings = duplicate_candidates(servers)
+        .into_iter()
+        .filter(|(_, group)| group.len() > 1)
+        .map(|((project_root, service), mut group)| {
+            // Unknown uptimes sort last so they are never picked as the one to kill
+            group.sort_by_key(|s| std::cmp::Reverse(s.uptime_secs.unwrap_or(0)));
+
+            let project_name = Path::new(&project_root)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| project_root.clone());
+
+            DuplicateServerWarning {
+                message: format!("{} {} servers are running for {}", group.len(), service, project_name),
+                oldest_port: group[0].port,
+                instances: group
+                    .into_iter()
+                    .map(|s| DuplicateServerInstance {
+                        port: s.port,
+                        pids: s.pids,
+                        uptime_secs: s.uptime_secs,
+                    })
+                    .collect(),
+                project_root,
+                service,
+            }
+        })
+        .collect();
+
+    Ok(warnings)
+}
+
+const DEFAULT_STALE_THRESHOLD_SECS: u64 = 8 * 3600;
+
+/// Servers up for longer than the threshold (8 hours by default), oldest first;
+/// usually leftovers from a previous day
+#[tauri::command]
+pub async fn find_stale_servers(threshold_secs: Option<u64>) -> Result<Vec<DevServer>, String> {
+    let threshold = threshold_secs.unwrap_or(DEFAULT_STALE_THRESHOLD_SECS);
+    let mut servers = scan_listening_servers().await?;
+    enrich_servers(&mut servers);
+
+    servers.retain(|s| s.uptime_secs.is_some_and(|uptime| uptime > threshold));
+    servers.sort_by_key(|s| std::cmp::Reverse(s.uptime_secs));
+    Ok(servers)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerLeaf {
+    pub port: u16,
+    pub pids: Vec<u32>,
+    pub process_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceNode {
+    pub service: String,
+    pub servers: Vec<ServerLeaf>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectNode {
+    pub project_root: Option<String>,
+    pub services: Vec<ServiceNode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanTree {
+    pub projects: Vec<ProjectNode>,
+    pub dot: Option<String>,
+}
+
+fn build_scan_tree(servers: Vec<DevServer>) -> Vec<ProjectNode> {
+    let mut grouped: BTreeMap<Option<String>, BTreeMap<String, Vec<ServerLeaf>>> = BTreeMap::new();
+
+    for server in servers {
+        grouped
+            .entry(server.project_root)
+            .or_default()
+            .entry(server.service)
+            .or_default()
+            .push(ServerLeaf {
+                port: server.port,
+                pids: server.pids,
+                process_name: server.process_name,
+            });
+    }
+
+    grouped
+        .into_iter()
+        .map(|(project_root, services)| ProjectNode {
+            project_root,
+            services: services
+                .into_iter()
+                .map(|(service, servers)| ServiceNode { service, servers })
+                .collect(),
+        })
+        .collect()
+}
+
+fn scan_tree_to_dot(projects: &[ProjectNode]) -> String {
+    let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+    let mut dot = String::from("digraph dev_servers {\n    rankdir=LR;\n    node [shape=box];\n");
+
+    for (p, project) in projects.iter().enumerate() {
+        let root = project.project_root.as_deref().unwrap_or("(unknown project)");
+        dot.push_str(&format!("    project{} [label=\"{}\", shape=folder];\n", p, escape(root)));
+
+        for (s, service) in project.services.iter().enumerate() {
+            dot.push_str(&format!("    service{}_{} [label=\"{}\", shape=ellipse];\n", p, s, escape(&service.service)));
+            dot.push_str(&format!("    project{} -> service{}_{};\n", p, p, s));
+
+            for server in &service.servers {
+                let pids = server.pids.iter().map(|pid| pid.to_string()).collect::<Vec<_>>().join(", ");
+                dot.push_str(&format!(
+                    "    port{} [label=\":{}\\n{} (PID {})\"];\n",
+                    server.port,
+                    server.port,
+                    escape(&server.process_name),
+                    pids
+                ));
+                dot.push_str(&format!("    service{}_{} -> port{};\n", p, s, server.port));
+            }
+        }
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+#[tauri::command]
+pub async fn scan_as_tree(include_dot: Option<bool>) -> Result<ScanTree, String> {
+    let mut servers = scan_listening_servers().await?;
+    enrich_servers(&mut servers);
+
+    let projects = build_scan_tree(servers);
+    let dot = include_dot.unwrap_or(false).then(|| scan_tree_to_dot(&projects));
+
+    Ok(ScanTree { projects, dot })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PidPortGroup {
+    pub pid: u32,
+    pub process_name: String,
+    pub ports: Vec<u16>,
+    pub services: Vec<String>,
+    /// Set when killing this PID would stop more than one port
+    pub kill_warning: Option<String>,
+}
+
+/// The scan regrouped by process, so it's clear when one PID (app + HMR + inspector)
+/// serves several ports and a single kill takes them all down
+#[tauri::command]
+pub async fn scan_grouped_by_pid() -> Result<Vec<PidPortGroup>, String> {
+    let servers = scan_listening_servers().await?;
+    Ok(group_by_pid(&servers))
+}
+
+fn group_by_pid(servers: &[DevServer]) -> Vec<PidPortGroup> {
+    let mut groups: BTreeMap<u32, PidPortGroup> = BTreeMap::new();
+    for server in servers {
+        for &pid in &server.pids {
+            let group = groups.entry(pid).or_insert_with(|| PidPortGroup {
+                pid,
+                process_name: server.process_name.clone(),
+                ports: Vec::new(),
+                services: Vec::new(),
+                kill_warning: None,
+            });
+            if !group.ports.contains(&server.port) {
+                group.ports.push(server.port);
+            }
+            if !group.services.contains(&server.service) {
+                group.services.push(server.service.clone());
+            }
+        }
+    }
+
+    groups
+        .into_values()
+        .map(|mut group| {
+            group.ports.sort_unstable();
+            if group.ports.len() > 1 {
+                let ports: Vec<String> = group.ports.iter().map(|p| p.to_string()).collect();
+                group.kill_warning = Some(format!(
+                    "Killing PID {} will stop ports {}",
+                    group.pid,
+                    ports.join(", ")
+                ));
+            }
+            group
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessNameGroup {
+    pub process_name: String,
+    /// Every name the OS reported for this process, e.g. `node` and `node.exe`
+    pub raw_names: Vec<String>,
+    pub ports: Vec<u16>,
+    pub pids: Vec<u32>,
+}
+
+fn group_by_process_name(servers: &[DevServer]) -> Vec<ProcessNameGroup> {
+    let mut groups: BTreeMap<String, ProcessNameGroup> = BTreeMap::new();
+    for server in servers {
+        let group = groups
+            .entry(server.process_name.clone())
+            .or_insert_with(|| ProcessNameGroup {
+                process_name: server.process_name.clone(),
+                raw_names: Vec::new(),
+                ports: Vec::new(),
+                pids: Vec::new(),
+            });
+        if !group.raw_names.contains(&server.raw_process_name) {
+            group.raw_names.push(server.raw_process_name.clone());
+        }
+        if !group.ports.contains(&server.port) {
+            group.ports.push(server.port);
+        }
+        for &pid in &server.pids {
+            if !group.pids.contains(&pid) {
+                group.pids.push(pid);
+            }
+        }
+    }
+
+    groups
+        .into_values()
+        .map(|mut group| {
+            group.raw_names.sort();
+            group.ports.sort_unstable();
+            group.pids.sort_unstable();
+            group
+        })
+        .collect()
+}
+
+/// The scan regrouped by canonical process name, merging servers the OS reported
+/// under different spellings of the same tool (`node`, `node.exe`, `node (tsx)`)
+#[tauri::command]
+pub async fn scan_grouped_by_process_name() -> Result<Vec<ProcessNameGroup>, String> {
+    let servers = scan_listening_servers().await?;
+    Ok(group_by_process_name(&servers))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortHolder {
+    pub pid: u32,
+    pub process_name: String,
+    pub bind_address: String,
+}
+
+/// Lists every process listening on `port`, not just recognised dev servers
+#[cfg(not(target_os = "windows"))]
+fn query_port_holders(port: u16) -> Result<Vec<PortHolder>, String> {
+    let output = Command::new("lsof")
+        .args(["-nP", &format!("-iTCP:{}", port), "-sTCP:LISTEN"])
+        .output()
+        .map_err(|e| format!("Failed to execute lsof: {}", e))?;
+
+    // lsof exits non-zero when nothing matches
+    let output_str = String::from_utf8_lossy(&output.stdout);
+    let mut holders: Vec<PortHolder> = Vec::new();
+
+    for line in output_str.lines().skip(1) {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 10 {
+            continue;
+        }
+
+        let (Ok(pid), Some((host, _))) = (parts[1].parse::<u32>(), parts[8].rsplit_once(':')) else {
+            continue;
+        };
+
+        if !holders.iter().any(|h| h.pid == pid && h.bind_address == host) {
+            holders.push(PortHolder {
+                pid,
+                process_name: parts[0].to_string(),
+                bind_address: host.to_string(),
+            });
+        }
+    }
+
+    Ok(holders)
+}
+
+#[cfg(target_os = "windows")]
+fn query_port_holders(port: u16) -> Result<Vec<PortHolder>, String> {
+    let output = Command::new("netstat")
+        .args(["-ano", "-p", "TCP"])
+        .output()
+        .map_err(|e| format!("Failed to execute netstat: {}", e))?;
+
+    let output_str = String::from_utf8_lossy(&output.stdout);
+    let mut holders: Vec<PortHolder> = Vec::new();
+
+    for line in output_str.lines() {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 5 || parts[0] != "TCP" || parts[3] != "LISTENING" {
+            continue;
+        }
+
+        let Some((host, port_str)) = parts[1].rsplit_once(':') else {
+            continue;
+        };
+        let (Ok(listen_port), Ok(pid)) = (port_str.parse::<u16>(), parts[4].parse::<u32>()) else {
+            continue;
+        };
+
+        if listen_port == port && !holders.iter().any(|h| h.pid == pid && h.bind_address == host) {
+            holders.push(PortHolder {
+                pid,
+                process_name: get_process_name_windows(pid).unwrap_or_default(),
+                bind_address: host.to_string(),
+            });
+        }
+    }
+
+    Ok(holders)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortAccessDiagnosis {
+    pub port: u16,
+    pub listening: bool,
+    pub holders: Vec<PortHolder>,
+    pub loopback_reachable: bool,
+    pub connect_latency_ms: Option<u64>,
+    pub privileged_port: bool,
+    pub firewall_enabled: Option<bool>,
+    pub firewall_blocks_all: Option<bool>,
+    pub findings: Vec<String>,
+}
+
+#[cfg(target_os = "macos")]
+fn macos_firewall_flag(flag: &str, enabled_marker: &str) -> Option<bool> {
+    let output = Command::new("/usr/libexec/ApplicationFirewall/socketfilterfw")
+        .arg(flag)
+        .output()
+        .ok()?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_lowercase();
+    Some(stdout.contains(enabled_marker))
+}
+
+#[tauri::command]
+pub async fn diagnose_port_access(port: u16) -> Result<PortAccessDiagnosis, String> {
+    let holders = query_port_holders(port)?;
+
+    let started = Instant::now();
+    let loopback_reachable = is_port_listening(port).await;
+    let connect_latency_ms = loopback_reachable.then(|| started.elapsed().as_millis() as u64);
+
+    #[cfg(target_os = "macos")]
+    let (firewall_enabled, firewall_blocks_all) = (
+        macos_firewall_flag("--getglobalstate", "enabled"),
+        macos_firewall_flag("--getblockall", "enabled"),
+    );
+
+    #[cfg(not(target_os = "macos"))]
+    let (firewall_enabled, firewall_blocks_all) = (None, None);
+
+    let mut findings = Vec::new();
+    if holders.is_empty() {
+        findings.push(format!("Nothing is listening on port {}", port));
+        if port < 1024 {
+            findings.push("Ports below 1024 need elevated privileges to bind".to_string());
+        }
+    } else if !loopback_reachable {
+        if holders.iter().all(|h| bind_exposure(&h.bind_address) == "specific") {
+            let addresses: Vec<&str> = holders.iter().map(|h| h.bind_address.as_str()).collect();
+            findings.push(format!(
+                "The server only listens on {}, so localhost cannot reach it",
+                addresses.join(", ")
+            ));
+        } else {
+            findings.push("The server is listening but refused a loopback connection".to_string());
+        }
+    }
+
+    if firewall_blocks_all == Some(true) {
+        findings.push("The macOS firewall is set to block all incoming connections".to_string());
+    } else if firewall_enabled == Some(true) && holders.iter().any(|h| bind_exposure(&h.bind_address) != "loopback") {
+        findings.push("The macOS firewall is on and may prompt for or block connections from other devices".to_string());
+    }
+
+    Ok(PortAccessDiagnosis {
+        port,
+        listening: !holders.is_empty(),
+        holders,
+        loopback_reachable,
+        connect_latency_ms,
+        privileged_port: port < 1024,
+        firewall_enabled,
+        firewall_blocks_all,
+        findings,
+    })
+}
+
+/// System services known to squat on ports developers reach for, keyed by process
+/// name. lsof truncates names to nine characters, so both forms are listed.
+/// `{pid}` in a hint is replaced with the holder's PID.
+const SYSTEM_PORT_HOLDERS: &[(&[&str], &str, &str)] = &[
+    (
+        &["ControlCe", "ControlCenter"],
+        "AirPlay Receiver (macOS Control Center)",
+        "macOS Monterey and later run the AirPlay Receiver on ports 5000 and 7000. Turn it off in \
+         System Settings > General > AirDrop & Handoff, or start your server on another port such as 5001.",
+    ),
+    (
+        &["AirPlayXP", "AirPlayXPCHelper"],
+        "AirPlay Receiver (macOS)",
+        "Turn off AirPlay Receiver in System Settings > General > AirDrop & Handoff, or use another port.",
+    ),
+    (
+        &["rapportd"],
+        "Handoff/Continuity (macOS rapportd)",
+        "rapportd backs Handoff and Universal Clipboard; use another port rather than stopping it.",
+    ),
+    (
+        &["com.docke", "com.docker.backend", "docker-pr", "docker-proxy", "vpnkit"],
+        "Docker",
+        "A container publishes this port. Run `docker ps` to find it and stop it or change its port mapping.",
+    ),
+    (
+        &["svchost"],
+        "Windows service host",
+        "A Windows service owns this port. Run `tasklist /svc /fi \"PID eq {pid}\"` to see which one.",
+    ),
+    (
+        &["System"],
+        "Windows HTTP.sys (IIS or another HTTP service)",
+        "Run `netsh http show servicestate` to see which service registered this port.",
+    ),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortUnavailableExplanation {
+    pub port: u16,
+    pub available: bool,
+    pub holders: Vec<PortHolder>,
+    /// Friendly name of whatever holds the port, e.g. "AirPlay Receiver (macOS Control Center)"
+    pub holder_name: Option<String>,
+    pub system_service: bool,
+    pub hint: String,
+}
+
+/// Windows reserves dynamic port ranges (often for Hyper-V/WSL) that nothing
+/// appears to listen on but that can't be bound either
+#[cfg(target_os = "windows")]
+fn windows_excluded_range(port: u16) -> Option<(u16, u16)> {
+    let output = Command::new("netsh")
+        .args(["int", "ipv4", "show", "excludedportrange", "protocol=tcp"])
+        .output()
+        .ok()?;
+
+    String::from_utf8_lossy(&output.stdout).lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        let start = parts.next()?.parse::<u16>().ok()?;
+        let end = parts.next()?.parse::<u16>().ok()?;
+        (start..=end).contains(&port).then_some((start, end))
+    })
+}
+
+/// Says plainly what is stopping a server from binding `port`, including OS
+/// services such as the macOS AirPlay Receiver on 5000/7000
+#[tauri::command]
+pub async fn explain_port_unavailable(port: u16) -> Result<PortUnavailableExplanation, String> {
+    let holders = query_port_holders(port)?;
+
+    if let Some(holder) = holders.first() {
+        let known = SYSTEM_PORT_HOLDERS
+            .iter()
+            .find(|(names, _, _)| names.contains(&holder.process_name.as_str()));
+
+        let (holder_name, system_service, hint) = match known {
+            Some((_, friendly, hint)) => (
+                friendly.to_string(),
+                true,
+                hint.replace("{pid}", &holder.pid.to_string()),
+            ),
+            None => {
+                let server = scan_listening_servers()
+                    .await
+                    .ok()
+                    .and_then(|servers| servers.into_iter().find(|s| s.port == port));
+                match server {
+                    Some(server) => (
+                        format!("{} ({})", server.service, holder.process_name),
+                        false,
+                        format!(
+                            "Your {} dev server (PID {}) is already running here. Stop it or pick another port.",
+                            server.service, holder.pid
+                        ),
+                    ),
+                    None => (
+                        holder.process_name.clone(),
+                        false,
+                        format!(
+                            "{} (PID {}) is listening on port {}. Stop it or pick another port.",
+                            holder.process_name, holder.pid, port
+                        ),
+                    ),
+                }
+            }
+        };
+
+        return Ok(PortUnavailableExplanation {
+            port,
+            available: false,
+            holders,
+            holder_name: Some(holder_name),
+            system_service,
+            hint,
+        });
+    }
+
+    #[cfg(target_os = "windows")]
+    if let Some((start, end)) = windows_excluded_range(port) {
+        return Ok(PortUnavailableExplanation {
+            port,
+            available: false,
+            holders,
+            holder_name: Some("Windows excluded port range".to_string()),
+            system_service: true,
+            hint: format!(
+                "Windows reserves ports {}-{} (often for Hyper-V or WSL). Pick a port outside that range.",
+                start, end
+            ),
+        });
+    }
+
+    let (available, hint) = match std::net::TcpListener::bind(("0.0.0.0", port)) {
+        Ok(_) => (true, format!("Port {} is free", port)),
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => (
+            false,
+            format!("Port {} needs elevated privileges to bind; use a port above 1023", port),
+        ),
+        Err(e) => (
+            false,
+            format!("Port {} can't be bound ({}), but no listening process was found", port, e),
+        ),
+    };
+
+    Ok(PortUnavailableExplanation {
+        port,
+        available,
+        holders,
+        holder_name: None,
+        system_service: false,
+        hint,
+    })
+}
+
+const MIN_PRIORITY: i32 = -20;
+const MAX_PRIORITY: i32 = 19;
+
+#[cfg(not(target_os = "windows"))]
+fn get_process_priority(pid: u32) -> Option<i32> {
+    let output = Command::new("ps")
+        .args(["-o", "nice=", "-p", &pid.to_string()])
+        .output()
+        .ok()?;
+
+    String::from_utf8_lossy(&output.stdout).trim().parse::<i32>().ok()
+}
+
+#[cfg(target_os = "windows")]
+fn get_process_priority(_pid: u32) -> Option<i32> {
+    // Querying PriorityClass needs a PowerShell round-trip per process, too slow for every scan
+    None
+}
+
+#[cfg(target_os = "windows")]
+fn windows_priority_class(priority: i32) -> &'static str {
+    match priority {
+        i32::MIN..=-10 => "High",
+        -9..=-1 => "AboveNormal",
+        0 => "Normal",
+        1..=9 => "BelowNormal",
+        _ => "Idle",
+    }
+}
+
+/// Sets a process' scheduling priority using Unix niceness semantics:
+/// -20 is the highest priority, 19 the lowest. Windows maps the value onto a PriorityClass.
+#[tauri::command]
+pub async fn set_process_priority(pid: u32, priority: i32) -> Result<Option<i32>, String> {
+    if !(MIN_PRIORITY..=MAX_PRIORITY).contains(&priority) {
+        return Err(format!(
+            "Priority must be between {} (highest) and {} (lowest)",
+            MIN_PRIORITY, MAX_PRIORITY
+        ));
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    let output = Command::new("renice")
+        .args(["-n", &priority.to_string(), "-p", &pid.to_string()])
+        .output()
+        .map_err(|e| format!("Failed to execute renice: {}", e))?;
+
+    #[cfg(target_os = "windows")]
+    let output = Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            &format!(
+                "(Get-Process -Id {} -ErrorAction Stop).PriorityClass = '{}'",
+                pid,
+                windows_priority_class(priority)
+            ),
+        ])
+        .output()
+        .map_err(|e| format!("Failed to execute powershell: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let lower = stderr.to_lowercase();
+        if lower.contains("permission denied")
+            || lower.contains("operation not permitted")
+            || lower.contains("access is denied")
+        {
+            return Err(format!(
+                "Permission denied changing priority of PID {}. Raising priority or changing another user's process requires administrator privileges.",
+                pid
+            ));
+        }
+        return Err(format!("Failed to set priority of PID {}: {}", pid, stderr.trim()));
+    }
+
+    Ok(get_process_priority(pid))
+}
+
+const KILL_CONFIRMATION_WINDOW_SECS: u64 = 30;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KillTarget {
+    pub pid: u32,
+    pub process_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum KillOutcome {
+    Killed {
+        pids: Vec<u32>,
+        /// Zombies left behind by the kill, which can still show up in process lists
+        defunct: Vec<DefunctProcess>,
+    },
+    ConfirmationRequired {
+        token: String,
+        targets: Vec<KillTarget>,
+        expires_in_secs: u64,
+    },
+}
+
+struct PendingKill {
+    pids: Vec<u32>,
+    requested_at: Instant,
+}
+
+/// Kill requests awaiting a `confirmed_kill` call, keyed by confirmation token
+#[derive(Default)]
+pub struct KillConfirmationState {
+    pending: Mutex<HashMap<String, PendingKill>>,
+}
+
+fn get_setting(db: &AgentDb, key: &str) -> Result<Option<String>, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+
+    Ok(conn
+        .query_row(
+            "SELECT value FROM app_settings WHERE key = ?1",
+            rusqlite::params![key],
+            |row| row.get::<_, String>(0),
+        )
+        .ok())
+}
+
+fn set_setting(db: &AgentDb, key: &str, value: &str) -> Result<(), String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "INSERT OR REPLACE INTO app_settings (key, value) VALUES (?1, ?2)",
+        rusqlite::params![key, value],
+    )
+    .map_err(|e| format!("Failed to save {}: {}", key, e))?;
+
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn get_process_name(pid: u32) -> Option<String> {
+    let output = Command::new("ps")
+        .args(["-p", &pid.to_string(), "-o", "comm="])
+        .output()
+        .ok()?;
+
+    let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || name.is_empty() {
+        return None;
+    }
+
+    Some(name)
+}
+
+#[cfg(target_os = "windows")]
+fn get_process_name(pid: u32) -> Option<String> {
+    get_process_name_windows(pid).ok()
+}
+
+fn kill_pids(pids: &[u32]) -> Result<(), String> {
+    for &pid in pids {
+        #[cfg(not(target_os = "windows"))]
+        {
+            let output = Command::new("kill")
+                .arg("-9")
+                .arg(pid.to_string())
+                .output()
+                .map_err(|e| format!("Failed to kill process {}: {}", pid, e))?;
+
+            if !output.status.success() {
+                return Err(format!("Failed to kill PID {}: {}", pid, String::from_utf8_lossy(&output.stderr)));
+            }
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            let output = Command::new("taskkill")
+                .args(&["/F", "/PID", &pid.to_string()])
+                .output()
+                .map_err(|e| format!("Failed to kill process {}: {}", pid, e))?;
+
+            if !output.status.success() {
                 return Err(format!("Failed to kill PID {}: {}", pid, String::from_utf8_lossy(&output.stderr)));
             }
-        }
+        }
+    }
+
+    Ok(())
+}
+
+/// How long to let the kernel tear processes down before looking for zombies
+#[cfg(not(target_os = "windows"))]
+const DEFUNCT_CHECK_DELAY_MS: u64 = 200;
+
+/// A killed process (or one of its children) that has exited but whose parent
+/// hasn't reaped it yet, so it lingers as `<defunct>`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DefunctProcess {
+    pub pid: u32,
+    pub parent_pid: u32,
+    pub parent_name: Option<String>,
+    pub note: String,
+}
+
+#[cfg(not(target_os = "windows"))]
+fn zombie_processes() -> Result<Vec<(u32, u32)>, String> {
+    let output = Command::new("ps")
+        .args(["-axo", "pid=,ppid=,stat="])
+        .output()
+        .map_err(|e| format!("Failed to execute ps: {}", e))?;
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let pid = parts.next()?.parse::<u32>().ok()?;
+            let ppid = parts.next()?.parse::<u32>().ok()?;
+            parts.next()?.starts_with('Z').then_some((pid, ppid))
+        })
+        .collect())
+}
+
+/// Kills `pids` and reports any of them, or their children, left as zombies.
+/// Each zombie's parent gets a SIGCHLD nudge in case it reaps lazily; whatever
+/// survives that is returned so the UI can explain the lingering entries.
+async fn kill_and_verify(pids: &[u32]) -> Result<Vec<DefunctProcess>, String> {
+    #[cfg(target_os = "windows")]
+    {
+        kill_pids(pids)?;
+        Ok(Vec::new())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let processes = list_processes().unwrap_or_default();
+        let mut tree: HashSet<u32> = pids.iter().copied().collect();
+        for &pid in pids {
+            tree.extend(process_descendants(pid, &processes));
+        }
+
+        kill_pids(pids)?;
+        tokio::time::sleep(Duration::from_millis(DEFUNCT_CHECK_DELAY_MS)).await;
+
+        let zombies: Vec<(u32, u32)> = zombie_processes()?
+            .into_iter()
+            .filter(|(pid, _)| tree.contains(pid))
+            .collect();
+        if zombies.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let parents: HashSet<u32> = zombies.iter().map(|&(_, ppid)| ppid).filter(|&ppid| ppid > 1).collect();
+        for parent in &parents {
+            let _ = Command::new("kill").args(["-s", "CHLD", &parent.to_string()]).output();
+        }
+        tokio::time::sleep(Duration::from_millis(DEFUNCT_CHECK_DELAY_MS)).await;
+
+        let remaining: HashSet<u32> = zombie_processes()?.into_iter().map(|(pid, _)| pid).collect();
+        Ok(zombies
+            .into_iter()
+            .filter(|(pid, _)| remaining.contains(pid))
+            .map(|(pid, parent_pid)| {
+                let parent_name = get_process_name(parent_pid);
+                let note = format!(
+                    "PID {} has exited but {} (PID {}) hasn't reaped it. It uses no resources and will \
+                     disappear when that process exits.",
+                    pid,
+                    parent_name.as_deref().unwrap_or("its parent"),
+                    parent_pid
+                );
+                DefunctProcess {
+                    pid,
+                    parent_pid,
+                    parent_name,
+                    note,
+                }
+            })
+            .collect())
+    }
+}
+
+/// Signals servers commonly handle without exiting: SIGHUP usually reloads config,
+/// SIGUSR1 opens the Node inspector, and SIGUSR2 is the usual hook for toggling debug
+/// logging or a graceful reload (nodemon, gunicorn). Anything that terminates by
+/// default goes through `kill_dev_server` instead.
+const ALLOWED_SERVER_SIGNALS: &[&str] = &["HUP", "USR1", "USR2"];
+
+/// Sends one of `ALLOWED_SERVER_SIGNALS` (with or without the `SIG` prefix) to a
+/// process that belongs to a detected dev server
+#[tauri::command]
+pub async fn send_signal_to_server(pid: u32, signal: String) -> Result<(), String> {
+    if cfg!(target_os = "windows") {
+        return Err("Unsupported: Windows has no Unix signals to send".to_string());
+    }
+
+    let name = signal.trim().to_uppercase();
+    let name = name.strip_prefix("SIG").unwrap_or(&name).to_string();
+    if !ALLOWED_SERVER_SIGNALS.contains(&name.as_str()) {
+        return Err(format!(
+            "Signal '{}' is not allowed; use one of SIGHUP, SIGUSR1 or SIGUSR2",
+            signal
+        ));
+    }
+
+    let servers = scan_listening_servers().await?;
+    if !servers.iter().any(|s| s.pids.contains(&pid)) {
+        return Err(format!("PID {} is not a detected dev server", pid));
+    }
+
+    let output = Command::new("kill")
+        .args(["-s", &name, &pid.to_string()])
+        .output()
+        .map_err(|e| format!("Failed to signal process {}: {}", pid, e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to send SIG{} to PID {}: {}",
+            name,
+            pid,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn kill_dev_server(
+    pids: Vec<u32>,
+    db: State<'_, AgentDb>,
+    confirmations: State<'_, KillConfirmationState>,
+    managed: State<'_, ManagedServersState>,
+) -> Result<KillOutcome, String> {
+    // A stale scan can still list one pid several times; signal each process once
+    let mut seen = HashSet::new();
+    let pids: Vec<u32> = pids.into_iter().filter(|pid| seen.insert(*pid)).collect();
+
+    let (app_pids, _) = current_app_process_tree();
+    if let Some(pid) = pids.iter().find(|&pid| app_pids.contains(pid)) {
+        return Err(format!("PID {} belongs to Queen Code itself and can't be killed from here", pid));
+    }
+
+    let require_confirmation = get_setting(&db, "require_kill_confirmation")?
+        .map(|v| v == "true")
+        .unwrap_or(false);
+
+    if !require_confirmation {
+        managed.disarm_autorestart(&pids);
+        let defunct = kill_and_verify(&pids).await?;
+        return Ok(KillOutcome::Killed { pids, defunct });
+    }
+
+    let targets = pids
+        .iter()
+        .map(|&pid| KillTarget {
+            pid,
+            process_name: get_process_name(pid),
+        })
+        .collect();
+
+    let token = uuid::Uuid::new_v4().to_string();
+    let mut pending = confirmations.pending.lock().map_err(|e| e.to_string())?;
+    pending.retain(|_, p| p.requested_at.elapsed().as_secs() < KILL_CONFIRMATION_WINDOW_SECS);
+    pending.insert(
+        token.clone(),
+        PendingKill {
+            pids,
+            requested_at: Instant::now(),
+        },
+    );
+
+    Ok(KillOutcome::ConfirmationRequired {
+        token,
+        targets,
+        expires_in_secs: KILL_CONFIRMATION_WINDOW_SECS,
+    })
+}
+
+#[tauri::command]
+pub async fn confirmed_kill(
+    token: String,
+    confirmations: State<'_, KillConfirmationState>,
+    managed: State<'_, ManagedServersState>,
+) -> Result<KillOutcome, String> {
+    let pending = confirmations
+        .pending
+        .lock()
+        .map_err(|e| e.to_string())?
+        .remove(&token)
+        .ok_or("Unknown or already used confirmation token")?;
+
+    if pending.requested_at.elapsed().as_secs() >= KILL_CONFIRMATION_WINDOW_SECS {
+        return Err("Kill confirmation expired, please try again".to_string());
+    }
+
+    managed.disarm_autorestart(&pending.pids);
+    let defunct = kill_and_verify(&pending.pids).await?;
+
+    Ok(KillOutcome::Killed {
+        pids: pending.pids,
+        defunct,
+    })
+}
+
+#[tauri::command]
+pub fn get_require_kill_confirmation(db: State<'_, AgentDb>) -> Result<bool, String> {
+    Ok(get_setting(&db, "require_kill_confirmation")?
+        .map(|v| v == "true")
+        .unwrap_or(false))
+}
+
+#[tauri::command]
+pub fn set_require_kill_confirmation(enabled: bool, db: State<'_, AgentDb>) -> Result<(), String> {
+    set_setting(&db, "require_kill_confirmation", &enabled.to_string())
+}
+
+/// Config files that commonly proxy requests to another local server:
+/// Vite `server.proxy`, Next.js `rewrites()` and CRA's `setupProxy.js`
+const PROXY_CONFIG_FILES: &[&str] = &[
+    "vite.config.ts",
+    "vite.config.js",
+    "vite.config.mts",
+    "vite.config.mjs",
+    "next.config.js",
+    "next.config.mjs",
+    "next.config.ts",
+    "src/setupProxy.js",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependentServer {
+    pub port: u16,
+    pub service: String,
+    pub project_root: Option<String>,
+    pub config_file: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KillImpact {
+    pub port: u16,
+    pub dependents: Vec<DependentServer>,
+    pub warning: Option<String>,
+}
+
+/// Local ports a project's proxy config forwards to, with the file that names them
+fn proxy_target_ports(project_root: &Path) -> Vec<(String, u16)> {
+    let local_target = regex::Regex::new(r"(?:localhost|127\.0\.0\.1|0\.0\.0\.0|\[::1\]):(\d{2,5})")
+        .expect("valid proxy target regex");
+    let mut targets = Vec::new();
+
+    for file in PROXY_CONFIG_FILES {
+        let path = project_root.join(file);
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        if !content.contains("proxy") && !content.contains("rewrites") {
+            continue;
+        }
+        for capture in local_target.captures_iter(&content) {
+            if let Ok(port) = capture[1].parse::<u16>() {
+                targets.push((path.to_string_lossy().to_string(), port));
+            }
+        }
+    }
+
+    // CRA-style `"proxy": "http://localhost:8000"` in package.json
+    let package_json = project_root.join("package.json");
+    if let Some(proxy) = std::fs::read_to_string(&package_json)
+        .ok()
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+        .and_then(|v| v.get("proxy").and_then(|p| p.as_str()).map(String::from))
+    {
+        for capture in local_target.captures_iter(&proxy) {
+            if let Ok(port) = capture[1].parse::<u16>() {
+                targets.push((package_json.to_string_lossy().to_string(), port));
+            }
+        }
+    }
+
+    targets
+}
+
+/// Best-effort check for running servers that proxy to `port`, e.g. a Vite
+/// frontend forwarding `/api` to the backend about to be killed
+#[tauri::command]
+pub async fn check_kill_dependents(port: u16) -> Result<KillImpact, String> {
+    let mut servers = scan_listening_servers().await?;
+    enrich_servers(&mut servers);
+
+    let mut dependents = Vec::new();
+    let mut checked_roots = HashSet::new();
+    for server in servers.iter().filter(|s| s.port != port) {
+        let Some(root) = server.project_root.as_deref() else {
+            continue;
+        };
+        if !checked_roots.insert(root.to_string()) {
+            continue;
+        }
+        if let Some((config_file, _)) = proxy_target_ports(Path::new(root))
+            .into_iter()
+            .find(|(_, target)| *target == port)
+        {
+            // Every server of that project shares the config
+            dependents.extend(
+                servers
+                    .iter()
+                    .filter(|s| s.port != port && s.project_root.as_deref() == Some(root))
+                    .map(|s| DependentServer {
+                        port: s.port,
+                        service: s.service.clone(),
+                        project_root: s.project_root.clone(),
+                        config_file: config_file.clone(),
+                    }),
+            );
+        }
+    }
+
+    let warning = (!dependents.is_empty()).then(|| {
+        let list = dependents
+            .iter()
+            .map(|d| format!("{} on port {}", d.service, d.port))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("Killing port {} will likely break {}, which proxies to it", port, list)
+    });
+
+    Ok(KillImpact {
+        port,
+        dependents,
+        warning,
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserInfo {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    pub supports_profiles: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserPreference {
+    pub browser: Option<String>,
+    pub profile: Option<String>,
+}
+
+/// (id, display name, candidate locations)
+#[cfg(target_os = "macos")]
+const KNOWN_BROWSERS: &[(&str, &str, &[&str])] = &[
+    ("chrome", "Google Chrome", &["/Applications/Google Chrome.app"]),
+    ("firefox", "Firefox", &["/Applications/Firefox.app"]),
+    ("safari", "Safari", &["/Applications/Safari.app"]),
+    ("edge", "Microsoft Edge", &["/Applications/Microsoft Edge.app"]),
+    ("brave", "Brave", &["/Applications/Brave Browser.app"]),
+    ("arc", "Arc", &["/Applications/Arc.app"]),
+    ("chromium", "Chromium", &["/Applications/Chromium.app"]),
+];
+
+#[cfg(target_os = "linux")]
+const KNOWN_BROWSERS: &[(&str, &str, &[&str])] = &[
+    ("chrome", "Google Chrome", &["google-chrome", "google-chrome-stable"]),
+    ("firefox", "Firefox", &["firefox"]),
+    ("edge", "Microsoft Edge", &["microsoft-edge", "microsoft-edge-stable"]),
+    ("brave", "Brave", &["brave-browser", "brave"]),
+    ("chromium", "Chromium", &["chromium", "chromium-browser"]),
+];
+
+#[cfg(target_os = "windows")]
+const KNOWN_BROWSERS: &[(&str, &str, &[&str])] = &[
+    ("chrome", "Google Chrome", &[
+        r"%ProgramFiles%\Google\Chrome\Application\chrome.exe",
+        r"%ProgramFiles(x86)%\Google\Chrome\Application\chrome.exe",
+        r"%LOCALAPPDATA%\Google\Chrome\Application\chrome.exe",
+    ]),
+    ("firefox", "Firefox", &[
+        r"%ProgramFiles%\Mozilla Firefox\firefox.exe",
+        r"%ProgramFiles(x86)%\Mozilla Firefox\firefox.exe",
+    ]),
+    ("edge", "Microsoft Edge", &[
+        r"%ProgramFiles(x86)%\Microsoft\Edge\Application\msedge.exe",
+        r"%ProgramFiles%\Microsoft\Edge\Application\msedge.exe",
+    ]),
+    ("brave", "Brave", &[
+        r"%ProgramFiles%\BraveSoftware\Brave-Browser\Application\brave.exe",
+        r"%LOCALAPPDATA%\BraveSoftware\Brave-Browser\Application\brave.exe",
+    ]),
+];
+
+#[cfg(target_os = "windows")]
+fn resolve_browser_location(location: &str) -> Option<String> {
+    let mut path = location.to_string();
+    for var in ["ProgramFiles(x86)", "ProgramFiles", "LOCALAPPDATA"] {
+        let pattern = format!("%{}%", var);
+        if path.contains(&pattern) {
+            path = path.replace(&pattern, &std::env::var(var).ok()?);
+        }
+    }
+    Path::new(&path).exists().then_some(path)
+}
+
+#[cfg(target_os = "linux")]
+fn resolve_browser_location(location: &str) -> Option<String> {
+    which::which(location).ok().map(|p| p.to_string_lossy().to_string())
+}
+
+#[cfg(target_os = "macos")]
+fn resolve_browser_location(location: &str) -> Option<String> {
+    Path::new(location).exists().then(|| location.to_string())
+}
+
+fn browser_profile_args(browser_id: &str, profile: &str) -> Vec<String> {
+    match browser_id {
+        "firefox" => vec!["-P".to_string(), profile.to_string()],
+        "safari" => Vec::new(),
+        _ => vec![format!("--profile-directory={}", profile)],
+    }
+}
+
+#[tauri::command]
+pub fn get_available_browsers() -> Vec<BrowserInfo> {
+    KNOWN_BROWSERS
+        .iter()
+        .filter_map(|(id, name, locations)| {
+            let path = locations.iter().find_map(|l| resolve_browser_location(l))?;
+            Some(BrowserInfo {
+                id: id.to_string(),
+                name: name.to_string(),
+                path,
+                supports_profiles: *id != "safari",
+            })
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub fn get_default_browser(db: State<'_, AgentDb>) -> Result<BrowserPreference, String> {
+    let non_empty = |value: Option<String>| value.filter(|v| !v.is_empty());
+
+    Ok(BrowserPreference {
+        browser: non_empty(get_setting(&db, "default_browser")?),
+        profile: non_empty(get_setting(&db, "default_browser_profile")?),
+    })
+}
+
+/// Passing `None` for `browser` reverts to the system default browser
+#[tauri::command]
+pub fn set_default_browser(
+    browser: Option<String>,
+    profile: Option<String>,
+    db: State<'_, AgentDb>,
+) -> Result<(), String> {
+    set_setting(&db, "default_browser", browser.as_deref().unwrap_or(""))?;
+    set_setting(&db, "default_browser_profile", profile.as_deref().unwrap_or(""))
+}
+
+pub(crate) fn open_with_system_default(url: &str) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    let result = Command::new("open").arg(url).spawn();
+
+    #[cfg(target_os = "linux")]
+    let result = Command::new("xdg-open").arg(url).spawn();
+
+    #[cfg(target_os = "windows")]
+    let result = Command::new("cmd").args(["/C", "start", "", url]).spawn();
+
+    result
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open {}: {}", url, e))
+}
+
+fn open_with_browser(browser: &BrowserInfo, profile: Option<&str>, url: &str) -> Result<(), String> {
+    let profile_args = profile
+        .map(|p| browser_profile_args(&browser.id, p))
+        .unwrap_or_default();
+
+    #[cfg(target_os = "macos")]
+    let result = {
+        let mut command = Command::new("open");
+        if profile_args.is_empty() {
+            command.args(["-a", &browser.path, url]);
+        } else {
+            // --args only reaches the browser when it's launched fresh, so force a new instance
+            command.args(["-n", "-a", &browser.path, "--args"]).args(&profile_args).arg(url);
+        }
+        command.spawn()
+    };
+
+    #[cfg(not(target_os = "macos"))]
+    let result = Command::new(&browser.path).args(&profile_args).arg(url).spawn();
+
+    result
+        .map(|_| ())
+        .map_err(|e| format!("Failed to launch {}: {}", browser.name, e))
+}
+
+/// Opens a dev server in the requested browser, the saved default, or the system browser
+#[tauri::command]
+pub fn open_dev_server_in_browser(
+    port: u16,
+    browser: Option<String>,
+    profile: Option<String>,
+    db: State<'_, AgentDb>,
+) -> Result<(), String> {
+    open_in_browser(&reachable_url(port), browser, profile, db)
+}
+
+/// Opens `url` in `browser`, falling back to the saved default browser and then
+/// the system default
+fn open_in_browser(
+    url: &str,
+    browser: Option<String>,
+    profile: Option<String>,
+    db: State<'_, AgentDb>,
+) -> Result<(), String> {
+    let (browser, profile) = match browser {
+        Some(browser) => (Some(browser), profile),
+        None => {
+            let saved = get_default_browser(db)?;
+            (saved.browser, profile.or(saved.profile))
+        }
+    };
+
+    let Some(browser_id) = browser else {
+        return open_with_system_default(url);
+    };
+
+    let installed = get_available_browsers()
+        .into_iter()
+        .find(|b| b.id == browser_id)
+        .ok_or_else(|| format!("Browser '{}' is not installed", browser_id))?;
+
+    open_with_browser(&installed, profile.as_deref(), url)
+}
+
+/// `https` when `address` (the server URL without its scheme, so a server bound
+/// only to a LAN IP is probed there) completes a TLS handshake, self-signed certs
+/// accepted; otherwise `http`
+async fn detect_scheme(address: &str) -> &'static str {
+    let client = match reqwest::Client::builder()
+        .timeout(Duration::from_millis(HEALTH_CHECK_TIMEOUT_MS))
+        .danger_accept_invalid_certs(true)
+        .no_proxy()
+        .build()
+    {
+        Ok(client) => client,
+        Err(_) => return "http",
+    };
+    match client.head(format!("https://{}", address)).send().await {
+        Ok(_) => "https",
+        Err(_) => "http",
+    }
+}
+
+fn serves_pages(server: &DevServer) -> bool {
+    !server.is_proxy
+        && !server.reserved
+        && matches!(server.service_info.category.as_str(), "frontend" | "tooling")
+        && !matches!(server.service.as_str(), "Docker" | "Jupyter")
+}
+
+/// Opens every page-serving server under `project_root`: frontends and tools like
+/// Storybook, skipping APIs, databases, proxies and a process's extra ports (HMR,
+/// inspector). `ports` opens exactly those of the project's ports instead.
+/// Returns the URLs opened.
+#[tauri::command]
+pub async fn open_project_servers_in_browser(
+    project_root: String,
+    ports: Option<Vec<u16>>,
+    db: State<'_, AgentDb>,
+) -> Result<Vec<String>, String> {
+    let root = canonicalize_cwd(&project_root);
+    let mut servers = scan_listening_servers().await?;
+    enrich_servers(&mut servers);
+    servers.retain(|s| {
+        s.project_root.as_deref() == Some(root.as_str())
+            || s.cwd.as_deref().is_some_and(|cwd| Path::new(cwd).starts_with(&root))
+    });
+    servers.sort_by_key(|s| s.port);
+
+    let selected: Vec<DevServer> = match &ports {
+        Some(ports) => servers.into_iter().filter(|s| ports.contains(&s.port)).collect(),
+        None => {
+            let mut seen_pids = HashSet::new();
+            servers
+                .into_iter()
+                .filter(serves_pages)
+                .filter(|s| seen_pids.insert(s.pid))
+                .collect()
+        }
+    };
+    if selected.is_empty() {
+        return Err(format!("No servers to open under {}", project_root));
+    }
+
+    let mut opened = Vec::new();
+    for server in selected {
+        let url = match server.url.strip_prefix("http://") {
+            Some(rest) => format!("{}://{}", detect_scheme(rest).await, rest),
+            None => server.url.clone(),
+        };
+        open_in_browser(&url, None, None, db.clone())?;
+        opened.push(url);
+    }
+    Ok(opened)
+}
+
+#[cfg(target_os = "linux")]
+const LINUX_TERMINALS: &[&str] = &[
+    "x-terminal-emulator",
+    "gnome-terminal",
+    "konsole",
+    "xfce4-terminal",
+    "kitty",
+    "alacritty",
+    "wezterm",
+    "xterm",
+];
+
+#[cfg(target_os = "macos")]
+fn launch_terminal(preferred: Option<&str>, dir: &Path) -> Result<(), String> {
+    // Terminal.app and iTerm both open a new window at a folder passed to `open -a`
+    let app = preferred.unwrap_or("Terminal");
+    let status = Command::new("open")
+        .args(["-a", app])
+        .arg(dir)
+        .status()
+        .map_err(|e| format!("Failed to launch {}: {}", app, e))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("Could not open {}; is it installed?", app))
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn launch_terminal(preferred: Option<&str>, dir: &Path) -> Result<(), String> {
+    let from_env = std::env::var("TERMINAL").ok().filter(|t| !t.is_empty());
+    let candidates = preferred
+        .map(String::from)
+        .into_iter()
+        .chain(from_env)
+        .chain(LINUX_TERMINALS.iter().map(|t| t.to_string()));
+
+    for terminal in candidates {
+        if which::which(&terminal).is_err() {
+            continue;
+        }
+        let mut command = Command::new(&terminal);
+        let name = Path::new(&terminal)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        // Terminals that reuse a running server ignore the spawned cwd
+        match name.as_str() {
+            "gnome-terminal" | "xfce4-terminal" => {
+                command.arg(format!("--working-directory={}", dir.display()));
+            }
+            "konsole" => {
+                command.arg("--workdir").arg(dir);
+            }
+            _ => {}
+        }
+        if command.current_dir(dir).spawn().is_ok() {
+            return Ok(());
+        }
+    }
+
+    Err("No terminal could be launched; set one in settings or the TERMINAL environment variable".to_string())
+}
+
+#[cfg(target_os = "windows")]
+fn launch_terminal(preferred: Option<&str>, dir: &Path) -> Result<(), String> {
+    let use_windows_terminal = match preferred {
+        Some(terminal) => terminal == "wt",
+        None => which::which("wt").is_ok(),
+    };
+
+    let result = if use_windows_terminal {
+        Command::new("wt").arg("-d").arg(dir).spawn()
+    } else {
+        let shell = preferred.unwrap_or("cmd");
+        Command::new("cmd").args(["/C", "start", "", shell]).current_dir(dir).spawn()
+    };
+
+    result
+        .map(|_| ())
+        .map_err(|e| format!("No terminal could be launched: {}", e))
+}
+
+#[tauri::command]
+pub fn get_terminal_preference(db: State<'_, AgentDb>) -> Result<Option<String>, String> {
+    Ok(get_setting(&db, "terminal_app")?.filter(|v| !v.is_empty()))
+}
+
+/// Passing `None` reverts to the platform default terminal
+#[tauri::command]
+pub fn set_terminal_preference(terminal: Option<String>, db: State<'_, AgentDb>) -> Result<(), String> {
+    set_setting(&db, "terminal_app", terminal.as_deref().unwrap_or(""))
+}
+
+/// Opens the preferred (or platform default) terminal in `path`
+#[tauri::command]
+pub fn open_terminal(path: String, db: State<'_, AgentDb>) -> Result<(), String> {
+    let dir = Path::new(&path);
+    if !dir.is_dir() {
+        return Err(format!("Directory does not exist: {}", path));
+    }
+
+    let preferred = get_setting(&db, "terminal_app")?.filter(|v| !v.is_empty());
+    launch_terminal(preferred.as_deref(), dir)
+}
+
+/// Opens a terminal in the working directory of the server listening on `port`
+#[tauri::command]
+pub async fn open_server_terminal(port: u16, db: State<'_, AgentDb>) -> Result<(), String> {
+    let mut servers = scan_listening_servers().await?;
+    servers.retain(|s| s.port == port);
+    enrich_servers(&mut servers);
+
+    let cwd = servers
+        .into_iter()
+        .find_map(|s| s.cwd)
+        .ok_or_else(|| format!("Could not determine the working directory of the server on port {}", port))?;
+
+    open_terminal(cwd, db)
+}
+
+const MANAGED_OUTPUT_LINES: usize = 500;
+const DEFAULT_STARTUP_TIMEOUT_SECS: u64 = 30;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManagedServer {
+    pub pid: u32,
+    pub port: u16,
+    pub command: String,
+    pub args: Vec<String>,
+    pub cwd: String,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub startup_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerStartup {
+    pub pid: u32,
+    pub port: u16,
+    pub startup_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerStartupFailure {
+    pub port: u16,
+    pub command: String,
+    pub error: String,
+    pub stderr: String,
+}
+
+/// Servers spawned by the app, keyed by the port they were expected to listen on
+#[derive(Default)]
+pub struct ManagedServersState {
+    pub servers: Arc<Mutex<HashMap<u16, ManagedServer>>>,
+    pub output: Arc<Mutex<HashMap<u16, VecDeque<String>>>>,
+    /// Live output of each managed server, for followers such as `tail_project_logs`
+    pub log_streams: Arc<Mutex<HashMap<u16, broadcast::Sender<String>>>>,
+    pub log_tails: Arc<Mutex<HashMap<String, watch::Sender<bool>>>>,
+    /// Ports with auto-restart enabled, mapped to their consecutive failed restarts
+    pub autorestart: Arc<Mutex<HashMap<u16, u32>>>,
+}
+
+impl ManagedServersState {
+    /// Stops auto-restart for managed servers the user is deliberately killing
+    fn disarm_autorestart(&self, pids: &[u32]) {
+        let ports: Vec<u16> = match self.servers.lock() {
+            Ok(servers) => servers
+                .values()
+                .filter(|s| pids.contains(&s.pid))
+                .map(|s| s.port)
+                .collect(),
+            Err(_) => return,
+        };
+
+        if let Ok(mut autorestart) = self.autorestart.lock() {
+            for port in ports {
+                autorestart.remove(&port);
+            }
+        }
+    }
+}
+
+fn push_output_line(
+    output: &Arc<Mutex<HashMap<u16, VecDeque<String>>>>,
+    stream: &broadcast::Sender<String>,
+    port: u16,
+    line: String,
+) {
+    let _ = stream.send(line.clone());
+
+    if let Ok(mut output) = output.lock() {
+        let buffer = output.entry(port).or_default();
+        if buffer.len() >= MANAGED_OUTPUT_LINES {
+            buffer.pop_front();
+        }
+        buffer.push_back(line);
+    }
+}
+
+async fn is_port_listening(port: u16) -> bool {
+    matches!(
+        tokio::time::timeout(
+            Duration::from_millis(250),
+            tokio::net::TcpStream::connect(("127.0.0.1", port)),
+        )
+        .await,
+        Ok(Ok(_))
+    )
+}
+
+/// Probes in flight at once for `check_ports_available`
+const PORT_CHECK_CONCURRENCY: usize = 32;
+const MAX_PORT_CHECKS: usize = 1024;
+
+async fn is_port_available(port: u16) -> bool {
+    if port == 0 || is_port_listening(port).await {
+        return false;
+    }
+    std::net::TcpListener::bind(("127.0.0.1", port)).is_ok()
+}
+
+/// Availability of each candidate port, in the order given, probed concurrently
+#[tauri::command]
+pub async fn check_ports_available(ports: Vec<u16>) -> Result<Vec<(u16, bool)>, String> {
+    if ports.len() > MAX_PORT_CHECKS {
+        return Err(format!("Check at most {} ports at a time", MAX_PORT_CHECKS));
+    }
+
+    Ok(futures::stream::iter(ports)
+        .map(|port| async move { (port, is_port_available(port).await) })
+        .buffered(PORT_CHECK_CONCURRENCY)
+        .collect()
+        .await)
+}
+
+#[tauri::command]
+pub async fn launch_dev_server(
+    app: AppHandle,
+    command: String,
+    args: Vec<String>,
+    cwd: String,
+    port: u16,
+    timeout_secs: Option<u64>,
+) -> Result<ServerStartup, String> {
+    start_managed_server(
+        app,
+        command,
+        args,
+        cwd,
+        port,
+        Duration::from_secs(timeout_secs.unwrap_or(DEFAULT_STARTUP_TIMEOUT_SECS)),
+    )
+    .await
+}
+
+async fn start_managed_server(
+    app: AppHandle,
+    command: String,
+    args: Vec<String>,
+    cwd: String,
+    port: u16,
+    timeout: Duration,
+) -> Result<ServerStartup, String> {
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    if is_port_listening(port).await {
+        return Err(format!("Port {} is already in use", port));
+    }
+
+    let state = app.state::<ManagedServersState>();
+    let output = state.output.clone();
+    let servers = state.servers.clone();
+
+    if let Ok(mut output) = output.lock() {
+        output.remove(&port);
+    }
+
+    let spawned_at = Instant::now();
+    let mut spawn = tokio::process::Command::new(&command);
+    spawn
+        .args(&args)
+        .current_dir(&cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    // Its own process group, so stopping it also reaches whatever `npm run dev` started
+    #[cfg(not(target_os = "windows"))]
+    spawn.process_group(0);
+    let mut child = spawn
+        .spawn()
+        .map_err(|e| format!("Failed to spawn {}: {}", command, e))?;
+
+    let pid = child.id().ok_or("Failed to get PID of spawned server")?;
+    log::info!("Spawned dev server '{}' with PID {} for port {}", command, pid, port);
+
+    let stderr_lines: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+    let (stream, _) = broadcast::channel::<String>(MANAGED_OUTPUT_LINES);
+    if let Ok(mut log_streams) = state.log_streams.lock() {
+        log_streams.insert(port, stream.clone());
+    }
+
+    if let Some(stdout) = child.stdout.take() {
+        let output = output.clone();
+        let stream = stream.clone();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stdout).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                push_output_line(&output, &stream, port, line);
+            }
+        });
+    }
+
+    if let Some(stderr) = child.stderr.take() {
+        let output = output.clone();
+        let stream = stream.clone();
+        let stderr_lines = stderr_lines.clone();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if let Ok(mut stderr_lines) = stderr_lines.lock() {
+                    if stderr_lines.len() < MANAGED_OUTPUT_LINES {
+                        stderr_lines.push(line.clone());
+                    }
+                }
+                push_output_line(&output, &stream, port, line);
+            }
+        });
+    }
+
+    let failure = loop {
+        if is_port_listening(port).await {
+            break None;
+        }
+
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(format!("Process exited before listening ({})", status)),
+            Ok(None) => {}
+            Err(e) => break Some(format!("Failed to check process status: {}", e)),
+        }
+
+        if spawned_at.elapsed() >= timeout {
+            let _ = child.kill().await;
+            break Some(format!("Timed out after {}s waiting for port {}", timeout.as_secs(), port));
+        }
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    };
+
+    if let Some(error) = failure {
+        // Give the reader task a moment to collect the final stderr lines
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let stderr = stderr_lines
+            .lock()
+            .map(|lines| lines.join("\n"))
+            .unwrap_or_default();
+
+        let _ = app.emit(
+            "dev-server-failed",
+            &ServerStartupFailure {
+                port,
+                command: command.clone(),
+                error: error.clone(),
+                stderr: stderr.clone(),
+            },
+        );
+
+        return Err(if stderr.is_empty() {
+            error
+        } else {
+            format!("{}\n{}", error, stderr)
+        });
+    }
+
+    let startup_ms = spawned_at.elapsed().as_millis() as u64;
+    log::info!("Dev server on port {} started listening in {}ms", port, startup_ms);
+
+    if let Ok(mut servers) = servers.lock() {
+        servers.insert(
+            port,
+            ManagedServer {
+                pid,
+                port,
+                command: command.clone(),
+                args: args.clone(),
+                cwd: cwd.clone(),
+                started_at: chrono::Utc::now(),
+                startup_ms,
+            },
+        );
+    }
+
+    let app_handle = app.clone();
+    let log_streams = state.log_streams.clone();
+    let listening_at = Instant::now();
+    tokio::spawn(async move {
+        let status = child.wait().await;
+        log::info!("Managed dev server on port {} exited: {:?}", port, status);
+
+        if let Ok(mut servers) = servers.lock() {
+            if servers.get(&port).map(|s| s.pid) == Some(pid) {
+                servers.remove(&port);
+            }
+        }
+
+        if let Ok(mut log_streams) = log_streams.lock() {
+            log_streams.remove(&port);
+        }
+
+        let _ = app_handle.emit("dev-server-exited", port);
+
+        schedule_autorestart(app_handle, command, args, cwd, port, timeout, listening_at.elapsed());
+    });
+
+    let startup = ServerStartup {
+        pid,
+        port,
+        startup_ms,
+    };
+    let _ = app.emit("dev-server-started", &startup);
+
+    Ok(startup)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectServerProgress {
+    pub project_id: i64,
+    /// One of `resolving`, `spawning`, `waiting_for_port`, `listening`, `failed`
+    pub stage: String,
+    pub message: String,
+    pub port: Option<u16>,
+}
+
+fn emit_project_progress(app: &AppHandle, project_id: i64, stage: &str, message: String, port: Option<u16>) {
+    let _ = app.emit(
+        "project-server-progress",
+        &ProjectServerProgress {
+            project_id,
+            stage: stage.to_string(),
+            message,
+            port,
+        },
+    );
+}
+
+/// Starts a tracked project's dev script with its own package manager as a managed
+/// server. `script` overrides the usual dev/start/serve pick and `port` the detected one.
+#[tauri::command]
+pub async fn start_project_dev_server(
+    project_id: i64,
+    script: Option<String>,
+    port: Option<u16>,
+    app: AppHandle,
+    db: State<'_, AgentDb>,
+) -> Result<ServerStartup, String> {
+    let project = load_tracked_projects(&db)?
+        .into_iter()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| format!("Project {} is not tracked", project_id))?;
+    let project_path = Path::new(&project.path);
+    if !project_path.is_dir() {
+        return Err(format!("Project directory '{}' does not exist", project.path));
+    }
+
+    emit_project_progress(&app, project_id, "resolving", format!("Reading {}", project.name), None);
+    let script = detect_project_dev_script(project_path, script.as_deref())?;
+    let package_manager = detect_project_package_manager(project_path);
+    let port = port
+        .or_else(|| detect_project_dev_port(project_path))
+        .or_else(|| default_framework_port(project_path))
+        .ok_or_else(|| format!("Couldn't work out which port {} listens on; pass one explicitly", project.name))?;
+
+    // npm, yarn and pnpm are .cmd shims on Windows, which only cmd can launch
+    let (command, args) = if cfg!(target_os = "windows") {
+        let args = ["/C", package_manager, "run", &script];
+        ("cmd".to_string(), args.iter().map(|a| a.to_string()).collect())
+    } else {
+        (package_manager.to_string(), vec!["run".to_string(), script.clone()])
+    };
+
+    emit_project_progress(
+        &app,
+        project_id,
+        "spawning",
+        format!("Running `{} run {}` in {}", package_manager, script, project.path),
+        Some(port),
+    );
+    emit_project_progress(&app, project_id, "waiting_for_port", format!("Waiting for port {}", port), Some(port));
+
+    let result = start_managed_server(
+        app.clone(),
+        command,
+        args,
+        project.path.clone(),
+        port,
+        Duration::from_secs(DEFAULT_STARTUP_TIMEOUT_SECS),
+    )
+    .await;
+
+    match &result {
+        Ok(startup) => emit_project_progress(
+            &app,
+            project_id,
+            "listening",
+            format!("{} is listening on port {}", project.name, startup.port),
+            Some(startup.port),
+        ),
+        Err(error) => emit_project_progress(&app, project_id, "failed", error.clone(), Some(port)),
+    }
+
+    result
+}
+
+#[tauri::command]
+pub fn list_managed_servers(state: State<'_, ManagedServersState>) -> Result<Vec<ManagedServer>, String> {
+    let servers = state.servers.lock().map_err(|e| e.to_string())?;
+    let mut result: Vec<ManagedServer> = servers.values().cloned().collect();
+    result.sort_by_key(|s| s.port);
+    Ok(result)
+}
+
+/// How long managed servers get to shut down on quit before they're force-killed
+const EXIT_STOP_GRACE_MS: u64 = 3000;
+
+#[tauri::command]
+pub fn get_kill_managed_on_exit(db: State<'_, AgentDb>) -> Result<bool, String> {
+    Ok(get_setting(&db, "kill_managed_on_exit")?
+        .map(|v| v == "true")
+        .unwrap_or(false))
+}
+
+#[tauri::command]
+pub fn set_kill_managed_on_exit(enabled: bool, db: State<'_, AgentDb>) -> Result<(), String> {
+    set_setting(&db, "kill_managed_on_exit", &enabled.to_string())
+}
+
+/// Called when the app exits: with `kill_managed_on_exit` on, stops the servers the
+/// app launched itself. Servers started outside the app are never in
+/// `ManagedServersState`, so they keep running.
+pub fn stop_managed_servers_on_exit(app: &AppHandle) {
+    let enabled = get_setting(&app.state::<AgentDb>(), "kill_managed_on_exit")
+        .ok()
+        .flatten()
+        .is_some_and(|v| v == "true");
+    if !enabled {
+        return;
+    }
+
+    let state = app.state::<ManagedServersState>();
+    let pids: Vec<u32> = match state.servers.lock() {
+        Ok(servers) => servers.values().map(|s| s.pid).collect(),
+        Err(_) => return,
+    };
+    if pids.is_empty() {
+        return;
+    }
+
+    log::info!("Stopping {} managed dev servers on exit", pids.len());
+    state.disarm_autorestart(&pids);
+    stop_gracefully(&pids);
+}
+
+/// Asks each process and everything it started to exit, then force-kills
+/// whatever is still running after `EXIT_STOP_GRACE_MS`
+fn stop_gracefully(pids: &[u32]) {
+    for &pid in pids {
+        signal_process_tree(pid, false);
+    }
+
+    let deadline = Instant::now() + Duration::from_millis(EXIT_STOP_GRACE_MS);
+    while Instant::now() < deadline && pids.iter().any(|&pid| get_process_name(pid).is_some()) {
+        std::thread::sleep(Duration::from_millis(100));
+    }
+
+    let remaining: Vec<u32> = pids
+        .iter()
+        .copied()
+        .filter(|&pid| get_process_name(pid).is_some())
+        .collect();
+    if !remaining.is_empty() {
+        log::warn!("Force-killing managed dev servers that ignored the stop request: {:?}", remaining);
+        for pid in remaining {
+            if !signal_process_tree(pid, true) {
+                log::warn!("Failed to stop managed dev server {} on exit", pid);
+            }
+        }
+    }
+}
+
+/// Signals a managed server's whole process group (they're spawned as group
+/// leaders), falling back to the pid alone for anything that isn't one
+#[cfg(not(target_os = "windows"))]
+fn signal_process_tree(pid: u32, force: bool) -> bool {
+    let signal = if force { "-KILL" } else { "-TERM" };
+    let leads_group = unsafe { libc::getpgid(pid as libc::pid_t) } == pid as libc::pid_t;
+    let target = if leads_group { format!("-{}", pid) } else { pid.to_string() };
+
+    Command::new("kill")
+        .args([signal, "--", &target])
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+#[cfg(target_os = "windows")]
+fn signal_process_tree(pid: u32, force: bool) -> bool {
+    let pid = pid.to_string();
+    let mut args = vec!["/T", "/PID", pid.as_str()];
+    if force {
+        args.insert(0, "/F");
+    }
+
+    Command::new("taskkill")
+        .args(&args)
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+const MAX_AUTORESTART_ATTEMPTS: u32 = 5;
+/// A server that dies sooner than this after listening counts as an immediate failure
+const AUTORESTART_STABLE_SECS: u64 = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestartAttempt {
+    pub port: u16,
+    pub attempt: u32,
+    pub max_attempts: u32,
+    pub delay_secs: u64,
+}
+
+fn schedule_autorestart(
+    app: AppHandle,
+    command: String,
+    args: Vec<String>,
+    cwd: String,
+    port: u16,
+    timeout: Duration,
+    ran_for: Duration,
+) {
+    let autorestart = app.state::<ManagedServersState>().autorestart.clone();
+
+    if let Ok(mut autorestart) = autorestart.lock() {
+        match autorestart.get_mut(&port) {
+            Some(failures) if ran_for.as_secs() >= AUTORESTART_STABLE_SECS => *failures = 0,
+            Some(_) => {}
+            None => return,
+        }
+    }
+
+    tokio::spawn(async move {
+        loop {
+            let attempt = match autorestart.lock() {
+                Ok(mut autorestart) => match autorestart.get_mut(&port) {
+                    Some(failures) => {
+                        *failures += 1;
+                        *failures
+                    }
+                    None => return,
+                },
+                Err(_) => return,
+            };
+
+            if attempt > MAX_AUTORESTART_ATTEMPTS {
+                log::warn!("Giving up restarting dev server on port {} after {} attempts", port, MAX_AUTORESTART_ATTEMPTS);
+                if let Ok(mut autorestart) = autorestart.lock() {
+                    autorestart.remove(&port);
+                }
+                let _ = app.emit("dev-server-restart-abandoned", port);
+                return;
+            }
+
+            let delay_secs = 1u64 << (attempt - 1);
+            let _ = app.emit(
+                "dev-server-restarting",
+                &RestartAttempt {
+                    port,
+                    attempt,
+                    max_attempts: MAX_AUTORESTART_ATTEMPTS,
+                    delay_secs,
+                },
+            );
+            tokio::time::sleep(Duration::from_secs(delay_secs)).await;
+
+            let restart: std::pin::Pin<Box<dyn std::future::Future<Output = Result<ServerStartup, String>> + Send>> =
+                Box::pin(start_managed_server(
+                    app.clone(),
+                    command.clone(),
+                    args.clone(),
+                    cwd.clone(),
+                    port,
+                    timeout,
+                ));
+
+            match restart.await {
+                // The new process' exit watcher takes over from here
+                Ok(_) => return,
+                Err(e) => log::warn!("Restart attempt {} for port {} failed: {}", attempt, port, e),
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub fn enable_autorestart(port: u16, state: State<'_, ManagedServersState>) -> Result<(), String> {
+    let is_managed = state
+        .servers
+        .lock()
+        .map_err(|e| e.to_string())?
+        .contains_key(&port);
+
+    if !is_managed {
+        return Err(format!("No server launched by the app is running on port {}", port));
+    }
+
+    state
+        .autorestart
+        .lock()
+        .map_err(|e| e.to_string())?
+        .insert(port, 0);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn disable_autorestart(port: u16, state: State<'_, ManagedServersState>) -> Result<(), String> {
+    state
+        .autorestart
+        .lock()
+        .map_err(|e| e.to_string())?
+        .remove(&port);
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectLogLine {
+    pub project_root: String,
+    pub port: u16,
+    pub service: String,
+    pub line: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TailedServer {
+    pub port: u16,
+    pub service: String,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectLogTail {
+    pub project_root: String,
+    pub following: Vec<TailedServer>,
+    pub unavailable: Vec<TailedServer>,
+}
+
+#[tauri::command]
+pub async fn tail_project_logs(project_root: String, app: AppHandle) -> Result<ProjectLogTail, String> {
+    let mut servers = scan_listening_servers().await?;
+    enrich_servers(&mut servers);
+    servers.retain(|s| s.project_root.as_deref() == Some(project_root.as_str()));
+
+    if servers.is_empty() {
+        return Err(format!("No running servers found under {}", project_root));
+    }
+
+    let state = app.state::<ManagedServersState>();
+    let (stop_tx, stop_rx) = watch::channel(false);
+    if let Some(previous) = state
+        .log_tails
+        .lock()
+        .map_err(|e| e.to_string())?
+        .insert(project_root.clone(), stop_tx)
+    {
+        let _ = previous.send(true);
+    }
+
+    let mut tail = ProjectLogTail {
+        project_root: project_root.clone(),
+        following: Vec::new(),
+        unavailable: Vec::new(),
+    };
+
+    for server in servers {
+        let stream = state
+            .log_streams
+            .lock()
+            .map_err(|e| e.to_string())?
+            .get(&server.port)
+            .map(|s| s.subscribe());
+
+        let Some(mut receiver) = stream else {
+            tail.unavailable.push(TailedServer {
+                port: server.port,
+                service: server.service,
+                reason: Some("Not launched by the app, output is not readable".to_string()),
+            });
+            continue;
+        };
+
+        let backlog: Vec<String> = state
+            .output
+            .lock()
+            .map_err(|e| e.to_string())?
+            .get(&server.port)
+            .map(|lines| lines.iter().cloned().collect())
+            .unwrap_or_default();
+
+        let app_handle = app.clone();
+        let mut stop_rx = stop_rx.clone();
+        let root = project_root.clone();
+        let port = server.port;
+        let service = server.service.clone();
+        tokio::spawn(async move {
+            let emit = |line: String| {
+                let _ = app_handle.emit(
+                    "project-log",
+                    &ProjectLogLine {
+                        project_root: root.clone(),
+                        port,
+                        service: service.clone(),
+                        line,
+                    },
+                );
+            };
+
+            for line in backlog {
+                emit(line);
+            }
+
+            loop {
+                tokio::select! {
+                    received = receiver.recv() => match received {
+                        Ok(line) => emit(line),
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            emit(format!("... {} lines skipped ...", skipped));
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                    _ = stop_rx.changed() => break,
+                }
+            }
+        });
+
+        tail.following.push(TailedServer {
+            port: server.port,
+            service: server.service,
+            reason: None,
+        });
+    }
+
+    Ok(tail)
+}
+
+#[tauri::command]
+pub fn stop_project_log_tail(project_root: String, state: State<'_, ManagedServersState>) -> Result<(), String> {
+    if let Some(stop) = state
+        .log_tails
+        .lock()
+        .map_err(|e| e.to_string())?
+        .remove(&project_root)
+    {
+        let _ = stop.send(true);
+    }
+
+    Ok(())
+}
+
+const WATCH_IGNORED_DIRS: &[&str] = &["node_modules", ".git", "target", "dist", "build", ".next", ".nuxt", ".svelte-kit", "__pycache__", ".venv"];
+const WATCH_DEBOUNCE_MS: u64 = 300;
+
+/// Active project file watchers, keyed by project root
+#[derive(Default)]
+pub struct FileWatchState {
+    watchers: Mutex<HashMap<String, watch::Sender<bool>>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AffectedServer {
+    pub port: u16,
+    pub service: String,
+    pub cwd: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectFilesChanged {
+    pub project_root: String,
+    pub paths: Vec<String>,
+    pub affected_servers: Vec<AffectedServer>,
+}
+
+fn is_watch_ignored(path: &Path, root: &Path) -> bool {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .any(|c| WATCH_IGNORED_DIRS.iter().any(|ignored| c.as_os_str() == *ignored))
+}
+
+/// Canonical form of a changed path; deleted files resolve through their parent
+fn canonicalize_changed_path(path: &str) -> std::path::PathBuf {
+    let path = Path::new(path);
+    let canonical = match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => path
+            .canonicalize()
+            .or_else(|_| parent.canonicalize().map(|parent| parent.join(name))),
+        _ => path.canonicalize(),
+    };
+    canonical.map(|p| std::path::PathBuf::from(canonicalize_cwd(&p.to_string_lossy()))).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// The server whose cwd most specifically contains `path`, compared by path
+/// component so `/work/app` doesn't claim `/work/app-old`
+fn server_containing_path<'a>(servers: &'a [DevServer], path: &str) -> Option<&'a DevServer> {
+    let path = canonicalize_changed_path(path);
+    servers
+        .iter()
+        .filter(|s| s.cwd.as_deref().is_some_and(|cwd| path.starts_with(cwd)))
+        .max_by_key(|s| s.cwd.as_deref().map(|cwd| Path::new(cwd).components().count()).unwrap_or(0))
+}
+
+/// Picks, for each changed path, the server whose cwd most specifically contains it
+async fn servers_affected_by(project_root: &str, paths: &[String]) -> Vec<AffectedServer> {
+    let Ok(mut servers) = scan_listening_servers().await else {
+        return Vec::new();
+    };
+    enrich_servers(&mut servers);
+    servers.retain(|s| s.project_root.as_deref() == Some(project_root));
+
+    let mut affected: Vec<AffectedServer> = Vec::new();
+    for path in paths {
+        let best = server_containing_path(&servers, path).or_else(|| (servers.len() == 1).then(|| &servers[0]));
+
+        if let Some(server) = best {
+            if !affected.iter().any(|a| a.port == server.port) {
+                affected.push(AffectedServer {
+                    port: server.port,
+                    service: server.service.clone(),
+                    cwd: server.cwd.clone(),
+                });
+            }
+        }
+    }
+
+    affected
+}
+
+#[tauri::command]
+pub async fn watch_project_files(
+    project_root: String,
+    app: AppHandle,
+    state: State<'_, FileWatchState>,
+) -> Result<(), String> {
+    use notify::{RecursiveMode, Watcher};
+
+    let root = std::path::PathBuf::from(&project_root);
+    if !root.is_dir() {
+        return Err(format!("Project directory '{}' does not exist", project_root));
+    }
+
+    let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel::<std::path::PathBuf>();
+    let ignore_root = root.clone();
+    let mut watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+        if let Ok(event) = result {
+            if event.kind.is_access() {
+                return;
+            }
+            for path in event.paths {
+                if !is_watch_ignored(&path, &ignore_root) {
+                    let _ = event_tx.send(path);
+                }
+            }
+        }
+    })
+    .map_err(|e| format!("Failed to create file watcher: {}", e))?;
+
+    watcher
+        .watch(&root, RecursiveMode::Recursive)
+        .map_err(|e| format!("Failed to watch {}: {}", project_root, e))?;
+
+    let (stop_tx, mut stop_rx) = watch::channel(false);
+    if let Some(previous) = state
+        .watchers
+        .lock()
+        .map_err(|e| e.to_string())?
+        .insert(project_root.clone(), stop_tx)
+    {
+        let _ = previous.send(true);
+    }
+
+    tokio::spawn(async move {
+        // Dropping the watcher stops the OS-level watch, so it lives as long as this task
+        let _watcher = watcher;
+
+        loop {
+            let first = tokio::select! {
+                path = event_rx.recv() => match path {
+                    Some(path) => path,
+                    None => break,
+                },
+                _ = stop_rx.changed() => break,
+            };
+
+            // Coalesce bursts (editors often write several files per save)
+            let mut changed = vec![first];
+            let deadline = tokio::time::Instant::now() + Duration::from_millis(WATCH_DEBOUNCE_MS);
+            while let Ok(Some(path)) = tokio::time::timeout_at(deadline, event_rx.recv()).await {
+                changed.push(path);
+            }
+
+            let mut paths: Vec<String> = changed
+                .into_iter()
+                .map(|p| p.to_string_lossy().to_string())
+                .collect();
+            paths.sort();
+            paths.dedup();
+
+            let affected_servers = servers_affected_by(&project_root, &paths).await;
+            let _ = app.emit(
+                "project-files-changed",
+                &ProjectFilesChanged {
+                    project_root: project_root.clone(),
+                    paths,
+                    affected_servers,
+                },
+            );
+        }
+
+        log::info!("Stopped watching {}", project_root);
+    });
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn unwatch_project_files(project_root: String, state: State<'_, FileWatchState>) -> Result<(), String> {
+    if let Some(stop) = state
+        .watchers
+        .lock()
+        .map_err(|e| e.to_string())?
+        .remove(&project_root)
+    {
+        let _ = stop.send(true);
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanDiscrepancy {
+    pub port: u16,
+    pub kind: String,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanMethodComparison {
+    pub lsof_ms: u64,
+    pub proc_ms: u64,
+    pub lsof_servers: usize,
+    pub proc_servers: usize,
+    pub proc_partial: bool,
+    pub proc_inaccessible_pids: usize,
+    pub discrepancies: Vec<ScanDiscrepancy>,
+}
+
+#[cfg(target_os = "linux")]
+fn compare_server_sets(lsof: &[DevServer], native: &[DevServer]) -> Vec<ScanDiscrepancy> {
+    let lsof_by_port: BTreeMap<u16, &DevServer> = lsof.iter().map(|s| (s.port, s)).collect();
+    let native_by_port: BTreeMap<u16, &DevServer> = native.iter().map(|s| (s.port, s)).collect();
+    let ports: std::collections::BTreeSet<u16> =
+        lsof_by_port.keys().chain(native_by_port.keys()).copied().collect();
+
+    let mut discrepancies = Vec::new();
+    let mut report = |port: u16, kind: &str, detail: String| {
+        discrepancies.push(ScanDiscrepancy { port, kind: kind.to_string(), detail });
+    };
+
+    for port in ports {
+        let (a, b) = match (lsof_by_port.get(&port), native_by_port.get(&port)) {
+            (Some(a), Some(b)) => (a, b),
+            (Some(a), None) => {
+                report(port, "missing_from_proc", format!("{} (pid {})", a.process_name, a.pid));
+                continue;
+            }
+            (None, Some(b)) => {
+                report(port, "missing_from_lsof", format!("{} (pid {})", b.process_name, b.pid));
+                continue;
+            }
+            (None, None) => continue,
+        };
+
+        let pids = |s: &DevServer| s.pids.iter().copied().collect::<std::collections::BTreeSet<u32>>();
+        if pids(a) != pids(b) {
+            report(port, "pids", format!("lsof {:?} vs proc {:?}", pids(a), pids(b)));
+        }
+
+        // lsof truncates command names, so only flag names that don't share a prefix
+        if !b.process_name.starts_with(&a.process_name) && !a.process_name.starts_with(&b.process_name) {
+            report(port, "process_name", format!("lsof '{}' vs proc '{}'", a.process_name, b.process_name));
+        }
+
+        let addresses = |s: &DevServer| s.bind_addresses.iter().cloned().collect::<std::collections::BTreeSet<String>>();
+        if addresses(a) != addresses(b) {
+            report(port, "bind_addresses", format!("lsof {:?} vs proc {:?}", addresses(a), addresses(b)));
+        }
+    }
+
+    discrepancies
+}
+
+/// Runs the lsof and /proc scanners side by side to validate the native path
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub async fn compare_scan_methods() -> Result<ScanMethodComparison, String> {
+    let started = Instant::now();
+    let lsof = scan_dev_servers_lsof().await?;
+    let lsof_ms = started.elapsed().as_millis() as u64;
+
+    let started = Instant::now();
+    let native = scan_dev_servers_proc();
+    let proc_ms = started.elapsed().as_millis() as u64;
+
+    let discrepancies = compare_server_sets(&lsof, &native.servers);
+    for discrepancy in &discrepancies {
+        log::warn!(
+            "Scan discrepancy on port {} ({}): {}",
+            discrepancy.port, discrepancy.kind, discrepancy.detail
+        );
+    }
+
+    Ok(ScanMethodComparison {
+        lsof_ms,
+        proc_ms,
+        lsof_servers: lsof.len(),
+        proc_servers: native.servers.len(),
+        proc_partial: native.partial,
+        proc_inaccessible_pids: native.inaccessible_pids,
+        discrepancies,
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+#[tauri::command]
+pub async fn compare_scan_methods() -> Result<ScanMethodComparison, String> {
+    Err("Scan method comparison is only available on Linux".to_string())
+}
+
+const RESOURCE_SAMPLE_LIMIT: usize = 120;
+const MIN_SAMPLE_INTERVAL_MS: u64 = 250;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceSample {
+    pub pid: u32,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Share of one core since the previous sample; None for the first sample
+    pub cpu_percent: Option<f64>,
+    pub memory_bytes: u64,
+    /// `memory_bytes` in binary units, e.g. `312 MiB`
+    pub memory_human: String,
+    /// `cpu_percent` to one decimal, e.g. `4.2%`
+    pub cpu_human: Option<String>,
+}
+
+const BINARY_UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+
+/// Byte count in binary (1024-based) units labelled KiB/MiB/..., so every client
+/// shows the same figure; one decimal below 10 of a unit, whole numbers above
+fn format_memory(bytes: u64) -> String {
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < BINARY_UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else if value < 10.0 {
+        format!("{:.1} {}", value, BINARY_UNITS[unit])
+    } else {
+        format!("{:.0} {}", value, BINARY_UNITS[unit])
+    }
+}
+
+fn format_cpu(percent: f64) -> String {
+    format!("{:.1}%", percent)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceSamplingStopped {
+    pub pid: u32,
+    /// "stopped" when requested, "exited" when the process went away
+    pub reason: String,
+}
+
+#[derive(Default)]
+pub struct ResourceSamplingState {
+    pub samples: Arc<Mutex<HashMap<u32, VecDeque<ResourceSample>>>>,
+    /// Stop handle per pid, tagged with the run that owns it
+    pub samplers: Arc<Mutex<HashMap<u32, (u64, watch::Sender<bool>)>>>,
+    next_run: std::sync::atomic::AtomicU64,
+}
+
+/// Cumulative CPU seconds and resident memory in bytes
+#[cfg(target_os = "linux")]
+fn read_process_usage(pid: u32) -> Option<(f64, u64)> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // The command name may contain spaces, so count fields from the closing paren
+    let fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+    let utime = fields.get(11)?.parse::<f64>().ok()?;
+    let stime = fields.get(12)?.parse::<f64>().ok()?;
+    let rss_pages = fields.get(21)?.parse::<u64>().ok()?;
+
+    let ticks_per_sec = unsafe { libc::sysconf(libc::_SC_CLK_TCK) }.max(1) as f64;
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) }.max(1) as u64;
+
+    Some(((utime + stime) / ticks_per_sec, rss_pages * page_size))
+}
+
+#[cfg(target_os = "macos")]
+fn read_process_usage(pid: u32) -> Option<(f64, u64)> {
+    let output = Command::new("ps")
+        .args(["-o", "time=,rss=", "-p", &pid.to_string()])
+        .output()
+        .ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    let mut parts = text.split_whitespace();
+
+    // `time` is [[dd-]hh:]mm:ss.cc
+    let time = parts.next()?;
+    let (days, rest) = match time.split_once('-') {
+        Some((days, rest)) => (days.parse::<f64>().ok()?, rest),
+        None => (0.0, time),
+    };
+    let mut secs = 0.0;
+    for part in rest.split(':') {
+        secs = secs * 60.0 + part.parse::<f64>().ok()?;
+    }
+    let rss_kb = parts.next()?.parse::<u64>().ok()?;
+
+    Some((days * 86_400.0 + secs, rss_kb * 1024))
+}
+
+#[cfg(target_os = "windows")]
+fn read_process_usage(pid: u32) -> Option<(f64, u64)> {
+    let script = format!(
+        "$p = Get-Process -Id {} -ErrorAction Stop; \"$($p.TotalProcessorTime.TotalSeconds) $($p.WorkingSet64)\"",
+        pid
+    );
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-Command", &script])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    let mut parts = text.split_whitespace();
+    let cpu = parts.next()?.parse::<f64>().ok()?;
+    let memory = parts.next()?.parse::<u64>().ok()?;
+
+    Some((cpu, memory))
+}
+
+#[tauri::command]
+pub fn start_resource_sampling(
+    pid: u32,
+    interval_ms: Option<u64>,
+    app: AppHandle,
+    state: State<'_, ResourceSamplingState>,
+) -> Result<(), String> {
+    if read_process_usage(pid).is_none() {
+        return Err(format!("Process {} is not running or cannot be inspected", pid));
+    }
+
+    let interval = Duration::from_millis(interval_ms.unwrap_or(1000).max(MIN_SAMPLE_INTERVAL_MS));
+    let (stop_tx, mut stop_rx) = watch::channel(false);
+    let run = state.next_run.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    if let Some((_, previous)) = state
+        .samplers
+        .lock()
+        .map_err(|e| e.to_string())?
+        .insert(pid, (run, stop_tx))
+    {
+        let _ = previous.send(true);
+    }
+
+    let samples = state.samples.clone();
+    let samplers = state.samplers.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let mut previous: Option<(f64, Instant)> = None;
+        let mut ticker = tokio::time::interval(interval);
+
+        let reason = loop {
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = stop_rx.changed() => break "stopped",
+            }
+
+            let Some((cpu_secs, memory_bytes)) = read_process_usage(pid) else {
+                break "exited";
+            };
+
+            let now = Instant::now();
+            let cpu_percent = previous.map(|(last_cpu, last_at)| {
+                let wall = now.duration_since(last_at).as_secs_f64().max(f64::EPSILON);
+                ((cpu_secs - last_cpu).max(0.0) / wall * 100.0 * 10.0).round() / 10.0
+            });
+            previous = Some((cpu_secs, now));
+
+            let sample = ResourceSample {
+                pid,
+                timestamp: chrono::Utc::now(),
+                cpu_percent,
+                memory_bytes,
+                memory_human: format_memory(memory_bytes),
+                cpu_human: cpu_percent.map(format_cpu),
+            };
+
+            if let Ok(mut samples) = samples.lock() {
+                let buffer = samples.entry(pid).or_default();
+                if buffer.len() >= RESOURCE_SAMPLE_LIMIT {
+                    buffer.pop_front();
+                }
+                buffer.push_back(sample.clone());
+            }
+            let _ = app.emit("resource-sample", &sample);
+        };
+
+        if reason == "exited" {
+            if let Ok(mut samplers) = samplers.lock() {
+                // A restarted sampler for the same pid owns the entry now
+                if samplers.get(&pid).is_some_and(|(owner, _)| *owner == run) {
+                    samplers.remove(&pid);
+                }
+            }
+        }
+        let _ = app.emit(
+            "resource-sampling-stopped",
+            &ResourceSamplingStopped {
+                pid,
+                reason: reason.to_string(),
+            },
+        );
+    });
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn stop_resource_sampling(pid: u32, state: State<'_, ResourceSamplingState>) -> Result<(), String> {
+    if let Some((_, stop)) = state.samplers.lock().map_err(|e| e.to_string())?.remove(&pid) {
+        let _ = stop.send(true);
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_resource_samples(
+    pid: u32,
+    state: State<'_, ResourceSamplingState>,
+) -> Result<Vec<ResourceSample>, String> {
+    Ok(state
+        .samples
+        .lock()
+        .map_err(|e| e.to_string())?
+        .get(&pid)
+        .map(|buffer| buffer.iter().cloned().collect())
+        .unwrap_or_default())
+}
+
+/// How often idle-kill jobs re-check CPU usage
+const IDLE_CHECK_INTERVAL_SECS: u64 = 30;
+/// CPU time below this across one check interval counts as no activity
+const IDLE_CPU_EPSILON_SECS: f64 = 0.05;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledKill {
+    pub id: String,
+    /// `at` kills everything once at `at`; `idle` keeps killing servers idle for `idle_secs`
+    pub kind: String,
+    pub at: Option<chrono::DateTime<chrono::Utc>>,
+    pub idle_secs: Option<u64>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledKillFired {
+    pub job_id: String,
+    pub ports: Vec<u16>,
+}
+
+struct ScheduledKillJob {
+    job: ScheduledKill,
+    cancel: watch::Sender<bool>,
+}
+
+/// Scheduled kill jobs for this app session; they don't survive a restart
+#[derive(Default)]
+pub struct ScheduledKillState {
+    jobs: Arc<Mutex<HashMap<String, ScheduledKillJob>>>,
+}
+
+fn protected_ports(db: &AgentDb) -> Result<Vec<u16>, String> {
+    Ok(get_setting(db, "protected_ports")?
+        .map(|v| v.split(',').filter_map(|p| p.trim().parse().ok()).collect())
+        .unwrap_or_default())
+}
+
+#[tauri::command]
+pub fn get_protected_ports(db: State<'_, AgentDb>) -> Result<Vec<u16>, String> {
+    protected_ports(&db)
+}
+
+/// Ports that scheduled kills never touch
+#[tauri::command]
+pub fn set_protected_ports(ports: Vec<u16>, db: State<'_, AgentDb>) -> Result<(), String> {
+    let value = ports.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(",");
+    set_setting(&db, "protected_ports", &value)
+}
+
+/// Servers a scheduled kill may stop: not protected, not supervised by a process
+/// manager that would restart them, and not one of the app's own sidecars
+async fn scheduled_kill_candidates(app: &AppHandle) -> Result<Vec<DevServer>, String> {
+    let protected = protected_ports(&app.state::<AgentDb>())?;
+    let mut servers = scan_listening_servers().await?;
+    tag_sidecars(app, &mut servers);
+    enrich_servers(&mut servers);
+    servers.retain(|s| !s.is_sidecar && s.managed_by.is_none() && !protected.contains(&s.port));
+    Ok(servers)
+}
+
+fn kill_scheduled(app: &AppHandle, job_id: &str, servers: &[DevServer]) {
+    if servers.is_empty() {
+        return;
+    }
+
+    let pids: Vec<u32> = servers.iter().flat_map(|s| s.pids.iter().copied()).collect();
+    app.state::<ManagedServersState>().disarm_autorestart(&pids);
+    if let Err(e) = kill_pids(&pids) {
+        log::warn!("Scheduled kill {} failed: {}", job_id, e);
+    }
+
+    let _ = app.emit(
+        "scheduled-kill-fired",
+        &ScheduledKillFired {
+            job_id: job_id.to_string(),
+            ports: servers.iter().map(|s| s.port).collect(),
+        },
+    );
+}
+
+fn register_scheduled_kill(
+    state: &ScheduledKillState,
+    kind: &str,
+    at: Option<chrono::DateTime<chrono::Utc>>,
+    idle_secs: Option<u64>,
+) -> Result<(ScheduledKill, watch::Receiver<bool>), String> {
+    let job = ScheduledKill {
+        id: uuid::Uuid::new_v4().to_string(),
+        kind: kind.to_string(),
+        at,
+        idle_secs,
+        created_at: chrono::Utc::now(),
+    };
+    let (cancel_tx, cancel_rx) = watch::channel(false);
+    state
+        .jobs
+        .lock()
+        .map_err(|e| e.to_string())?
+        .insert(
+            job.id.clone(),
+            ScheduledKillJob {
+                job: job.clone(),
+                cancel: cancel_tx,
+            },
+        );
+    Ok((job, cancel_rx))
+}
+
+/// Kills every dev server once at `at`
+#[tauri::command]
+pub fn schedule_kill_all(
+    at: chrono::DateTime<chrono::Utc>,
+    app: AppHandle,
+    state: State<'_, ScheduledKillState>,
+) -> Result<ScheduledKill, String> {
+    let delay = (at - chrono::Utc::now())
+        .to_std()
+        .map_err(|_| format!("{} is in the past", at.to_rfc3339()))?;
+    let (job, mut cancel_rx) = register_scheduled_kill(&state, "at", Some(at), None)?;
+
+    let jobs = state.jobs.clone();
+    let job_id = job.id.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = cancel_rx.changed() => return,
+        }
+
+        match scheduled_kill_candidates(&app).await {
+            Ok(servers) => kill_scheduled(&app, &job_id, &servers),
+            Err(e) => log::warn!("Scheduled kill {} couldn't scan servers: {}", job_id, e),
+        }
+        if let Ok(mut jobs) = jobs.lock() {
+            jobs.remove(&job_id);
+        }
+    });
+
+    Ok(job)
+}
+
+/// Keeps killing dev servers that have used no CPU for `idle_secs` until cancelled
+#[tauri::command]
+pub fn schedule_kill_idle(
+    idle_secs: u64,
+    app: AppHandle,
+    state: State<'_, ScheduledKillState>,
+) -> Result<ScheduledKill, String> {
+    if idle_secs < IDLE_CHECK_INTERVAL_SECS {
+        return Err(format!("Idle time must be at least {} seconds", IDLE_CHECK_INTERVAL_SECS));
+    }
+    let (job, mut cancel_rx) = register_scheduled_kill(&state, "idle", None, Some(idle_secs))?;
+
+    let job_id = job.id.clone();
+    tauri::async_runtime::spawn(async move {
+        // pid -> (cumulative CPU seconds, when that last changed)
+        let mut activity: HashMap<u32, (f64, Instant)> = HashMap::new();
+        let mut ticker = tokio::time::interval(Duration::from_secs(IDLE_CHECK_INTERVAL_SECS));
+
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = cancel_rx.changed() => break,
+            }
+
+            let servers = match scheduled_kill_candidates(&app).await {
+                Ok(servers) => servers,
+                Err(e) => {
+                    log::warn!("Idle kill {} couldn't scan servers: {}", job_id, e);
+                    continue;
+                }
+            };
+
+            // Forget servers that are gone so a reused pid starts over
+            activity.retain(|pid, _| servers.iter().any(|server| server.pid == *pid));
+
+            let now = Instant::now();
+            let mut idle = Vec::new();
+            for server in servers {
+                let usage: Vec<f64> = server
+                    .pids
+                    .iter()
+                    .filter_map(|&pid| read_process_usage(pid))
+                    .map(|(cpu, _)| cpu)
+                    .collect();
+                // Unreadable usage isn't evidence of idleness
+                if usage.is_empty() {
+                    continue;
+                }
+                let cpu: f64 = usage.iter().sum();
+                let (last_cpu, quiet_since) = activity.entry(server.pid).or_insert((cpu, now));
+                if cpu - *last_cpu > IDLE_CPU_EPSILON_SECS {
+                    *last_cpu = cpu;
+                    *quiet_since = now;
+                } else if now.duration_since(*quiet_since).as_secs() >= idle_secs {
+                    idle.push(server);
+                }
+            }
+
+            for server in &idle {
+                activity.remove(&server.pid);
+            }
+            kill_scheduled(&app, &job_id, &idle);
+        }
+    });
+
+    Ok(job)
+}
+
+#[tauri::command]
+pub fn list_scheduled_kills(state: State<'_, ScheduledKillState>) -> Result<Vec<ScheduledKill>, String> {
+    let mut jobs: Vec<ScheduledKill> = state
+        .jobs
+        .lock()
+        .map_err(|e| e.to_string())?
+        .values()
+        .map(|scheduled| scheduled.job.clone())
+        .collect();
+    jobs.sort_by_key(|job| job.created_at);
+    Ok(jobs)
+}
+
+#[tauri::command]
+pub fn cancel_scheduled_kill(id: String, state: State<'_, ScheduledKillState>) -> Result<(), String> {
+    let scheduled = state
+        .jobs
+        .lock()
+        .map_err(|e| e.to_string())?
+        .remove(&id)
+        .ok_or_else(|| format!("No scheduled kill with id {}", id))?;
+    let _ = scheduled.cancel.send(true);
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionServer {
+    pub service: String,
+    pub port: u16,
+    pub cwd: String,
+    pub argv: Vec<String>,
+    pub labels: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DevSession {
+    pub name: String,
+    pub servers: Vec<SessionServer>,
+    pub created_at: String,
+}
+
+fn load_dev_session(db: &AgentDb, name: &str) -> Result<DevSession, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let (servers, created_at) = conn
+        .query_row(
+            "SELECT servers, created_at FROM dev_sessions WHERE name = ?1",
+            rusqlite::params![name],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+        )
+        .map_err(|_| format!("No saved dev session named '{}'", name))?;
+
+    Ok(DevSession {
+        name: name.to_string(),
+        servers: serde_json::from_str(&servers)
+            .map_err(|e| format!("Saved session '{}' is corrupt: {}", name, e))?,
+        created_at,
+    })
+}
+
+/// Captures the running servers (cwd and command line) under a name, replacing any previous session
+#[tauri::command]
+pub async fn save_dev_session(name: String, db: State<'_, AgentDb>) -> Result<DevSession, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Session name cannot be empty".to_string());
+    }
+
+    let mut servers = scan_listening_servers().await?;
+    enrich_servers(&mut servers);
+    apply_project_labels(&db, &mut servers)?;
+    let processes = list_processes().unwrap_or_default();
+
+    let captured: Vec<SessionServer> = servers
+        .into_iter()
+        .filter_map(|server| {
+            // Without argv boundaries fall back to splitting the ps command line on whitespace
+            let argv = get_process_argv(server.pid).or_else(|| {
+                processes
+                    .iter()
+                    .find(|p| p.pid == server.pid)
+                    .map(|p| p.command.split_whitespace().map(String::from).collect())
+            })?;
+
+            Some(SessionServer {
+                service: server.service,
+                port: server.port,
+                cwd: server.cwd?,
+                argv,
+                labels: server.labels,
+            })
+        })
+        .collect();
+
+    if captured.is_empty() {
+        return Err("No running servers with a recoverable command line to save".to_string());
+    }
+
+    let json = serde_json::to_string(&captured).map_err(|e| e.to_string())?;
+    {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        conn.execute(
+            "INSERT OR REPLACE INTO dev_sessions (name, servers) VALUES (?1, ?2)",
+            rusqlite::params![name, json],
+        )
+        .map_err(|e| format!("Failed to save dev session: {}", e))?;
+    }
+
+    load_dev_session(&db, &name)
+}
+
+#[tauri::command]
+pub fn list_dev_sessions(db: State<'_, AgentDb>) -> Result<Vec<DevSession>, String> {
+    let names: Vec<String> = {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        let mut stmt = conn
+            .prepare("SELECT name FROM dev_sessions ORDER BY name")
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(|e| e.to_string())?;
+        rows.filter_map(|name| name.ok()).collect()
+    };
+
+    names.iter().map(|name| load_dev_session(&db, name)).collect()
+}
+
+#[tauri::command]
+pub fn delete_dev_session(name: String, db: State<'_, AgentDb>) -> Result<(), String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    conn.execute("DELETE FROM dev_sessions WHERE name = ?1", rusqlite::params![name])
+        .map_err(|e| format!("Failed to delete dev session: {}", e))?;
+
+    Ok(())
+}
+
+fn powershell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+fn session_comment(server: &SessionServer) -> String {
+    let mut comment = format!("# {} on port {}", server.service, server.port);
+    if !server.labels.is_empty() {
+        comment.push_str(&format!(" [{}]", server.labels.join(", ")));
+    }
+    // Keep labels from breaking out of the comment line
+    comment.replace(['\r', '\n'], " ")
+}
+
+fn session_script_sh(session: &DevSession) -> String {
+    let mut script = String::from("#!/usr/bin/env sh\n");
+    script.push_str(&format!("# Dev session: {}\n", session.name.replace(['\r', '\n'], " ")));
+    script.push_str(&format!("# Saved {}\n\n", session.created_at));
+
+    for server in &session.servers {
+        let command: Vec<String> = server.argv.iter().map(|arg| shell_quote(arg)).collect();
+        script.push_str(&session_comment(server));
+        script.push('\n');
+        script.push_str(&format!("(cd {} && exec {}) &\n\n", shell_quote(&server.cwd), command.join(" ")));
+    }
+
+    script.push_str("wait\n");
+    script
+}
+
+fn session_script_powershell(session: &DevSession) -> String {
+    let mut script = format!("# Dev session: {}\n", session.name.replace(['\r', '\n'], " "));
+    script.push_str(&format!("# Saved {}\n\n", session.created_at));
+
+    for server in &session.servers {
+        let Some((program, args)) = server.argv.split_first() else {
+            continue;
+        };
+
+        script.push_str(&session_comment(server));
+        script.push('\n');
+        script.push_str(&format!(
+            "Start-Process -FilePath {} -WorkingDirectory {}",
+            powershell_quote(program),
+            powershell_quote(&server.cwd)
+        ));
+        if !args.is_empty() {
+            let args: Vec<String> = args.iter().map(|arg| powershell_quote(arg)).collect();
+            script.push_str(&format!(" -ArgumentList @({})", args.join(", ")));
+        }
+        script.push_str(" -NoNewWindow\n\n");
+    }
+
+    script
+}
+
+/// Renders a saved session as a startup script. `shell` is "sh" or "powershell",
+/// defaulting to the current platform's shell.
+#[tauri::command]
+pub fn export_dev_session_script(
+    name: String,
+    shell: Option<String>,
+    db: State<'_, AgentDb>,
+) -> Result<String, String> {
+    let session = load_dev_session(&db, &name)?;
+    let default_shell = if cfg!(target_os = "windows") { "powershell" } else { "sh" };
+
+    match shell.as_deref().unwrap_or(default_shell) {
+        "sh" | "bash" | "zsh" => Ok(session_script_sh(&session)),
+        "powershell" | "pwsh" => Ok(session_script_powershell(&session)),
+        other => Err(format!("Unsupported shell '{}'; use 'sh' or 'powershell'", other)),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotServer {
+    pub port: u16,
+    pub service: String,
+    pub process_name: String,
+    pub pids: Vec<u32>,
+    pub cwd: Option<String>,
+    pub command: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanSnapshot {
+    pub name: String,
+    pub servers: Vec<SnapshotServer>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotChange {
+    pub port: u16,
+    /// Which of service, command, process_name, pids and cwd differ
+    pub fields: Vec<String>,
+    pub before: SnapshotServer,
+    pub after: SnapshotServer,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    pub from: String,
+    pub to: String,
+    pub added: Vec<SnapshotServer>,
+    pub removed: Vec<SnapshotServer>,
+    pub changed: Vec<SnapshotChange>,
+}
+
+async fn capture_snapshot_servers() -> Result<Vec<SnapshotServer>, String> {
+    let mut servers = scan_listening_servers().await?;
+    enrich_servers(&mut servers);
+    let processes = list_processes().unwrap_or_default();
+    let commands: HashMap<u32, String> = processes.into_iter().map(|p| (p.pid, p.command)).collect();
+
+    Ok(servers
+        .into_iter()
+        .map(|server| SnapshotServer {
+            command: commands.get(&server.pid).cloned(),
+            port: server.port,
+            service: server.service,
+            process_name: server.process_name,
+            pids: server.pids,
+            cwd: server.cwd,
+        })
+        .collect())
+}
+
+fn load_scan_snapshot(db: &AgentDb, name: &str) -> Result<ScanSnapshot, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let (servers, created_at) = conn
+        .query_row(
+            "SELECT servers, created_at FROM scan_snapshots WHERE name = ?1",
+            rusqlite::params![name],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+        )
+        .map_err(|_| format!("No saved snapshot named '{}'", name))?;
+
+    Ok(ScanSnapshot {
+        name: name.to_string(),
+        servers: serde_json::from_str(&servers)
+            .map_err(|e| format!("Saved snapshot '{}' is corrupt: {}", name, e))?,
+        created_at,
+    })
+}
+
+/// Records the current scan under a name, replacing any previous snapshot
+#[tauri::command]
+pub async fn save_scan_snapshot(name: String, db: State<'_, AgentDb>) -> Result<ScanSnapshot, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Snapshot name cannot be empty".to_string());
+    }
+
+    let servers = capture_snapshot_servers().await?;
+    let json = serde_json::to_string(&servers).map_err(|e| e.to_string())?;
+    {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        conn.execute(
+            "INSERT OR REPLACE INTO scan_snapshots (name, servers) VALUES (?1, ?2)",
+            rusqlite::params![name, json],
+        )
+        .map_err(|e| format!("Failed to save snapshot: {}", e))?;
+    }
+
+    load_scan_snapshot(&db, &name)
+}
+
+#[tauri::command]
+pub fn list_scan_snapshots(db: State<'_, AgentDb>) -> Result<Vec<ScanSnapshot>, String> {
+    let names: Vec<String> = {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        let mut stmt = conn
+            .prepare("SELECT name FROM scan_snapshots ORDER BY created_at DESC")
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(|e| e.to_string())?;
+        rows.filter_map(|name| name.ok()).collect()
+    };
+
+    names.iter().map(|name| load_scan_snapshot(&db, name)).collect()
+}
+
+#[tauri::command]
+pub fn delete_scan_snapshot(name: String, db: State<'_, AgentDb>) -> Result<(), String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    conn.execute("DELETE FROM scan_snapshots WHERE name = ?1", rusqlite::params![name])
+        .map_err(|e| format!("Failed to delete snapshot: {}", e))?;
+
+    Ok(())
+}
+
+type SnapshotDelta = (Vec<SnapshotServer>, Vec<SnapshotServer>, Vec<SnapshotChange>);
+
+fn diff_snapshot_servers(before: &[SnapshotServer], after: &[SnapshotServer]) -> SnapshotDelta {
+    let before_by_port: BTreeMap<u16, &SnapshotServer> = before.iter().map(|s| (s.port, s)).collect();
+    let after_by_port: BTreeMap<u16, &SnapshotServer> = after.iter().map(|s| (s.port, s)).collect();
+
+    let added = after_by_port
+        .iter()
+        .filter(|(port, _)| !before_by_port.contains_key(port))
+        .map(|(_, s)| (*s).clone())
+        .collect();
+    let removed = before_by_port
+        .iter()
+        .filter(|(port, _)| !after_by_port.contains_key(port))
+        .map(|(_, s)| (*s).clone())
+        .collect();
+
+    let changed = before_by_port
+        .iter()
+        .filter_map(|(port, a)| {
+            let b = after_by_port.get(port)?;
+            let mut fields = Vec::new();
+            if a.service != b.service {
+                fields.push("service");
+            }
+            if a.command != b.command {
+                fields.push("command");
+            }
+            if a.process_name != b.process_name {
+                fields.push("process_name");
+            }
+            if a.pids != b.pids {
+                fields.push("pids");
+            }
+            if a.cwd != b.cwd {
+                fields.push("cwd");
+            }
+            (!fields.is_empty()).then(|| SnapshotChange {
+                port: *port,
+                fields: fields.into_iter().map(String::from).collect(),
+                before: (*a).clone(),
+                after: (*b).clone(),
+            })
+        })
+        .collect();
+
+    (added, removed, changed)
+}
+
+/// Servers added, removed or changed between snapshot `a` and snapshot `b`, or
+/// the live scan when `b` is omitted
+#[tauri::command]
+pub async fn diff_snapshots(
+    a: String,
+    b: Option<String>,
+    db: State<'_, AgentDb>,
+) -> Result<SnapshotDiff, String> {
+    let before = load_scan_snapshot(&db, &a)?;
+    let (to, after) = match b {
+        Some(name) => {
+            let snapshot = load_scan_snapshot(&db, &name)?;
+            (snapshot.name, snapshot.servers)
+        }
+        None => ("live".to_string(), capture_snapshot_servers().await?),
+    };
+
+    let (added, removed, changed) = diff_snapshot_servers(&before.servers, &after);
+    Ok(SnapshotDiff {
+        from: before.name,
+        to,
+        added,
+        removed,
+        changed,
+    })
+}
+
+const DEFAULT_HEALTH_PATH: &str = "/health";
+const HEALTH_CHECK_TIMEOUT_MS: u64 = 2000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerHealth {
+    pub port: u16,
+    pub path: String,
+    pub healthy: bool,
+    pub status_code: Option<u16>,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+fn health_path_key(port: u16) -> String {
+    format!("health_path:{}", port)
+}
+
+fn normalize_health_path(path: &str) -> String {
+    let path = path.trim();
+    if path.starts_with('/') {
+        path.to_string()
+    } else {
+        format!("/{}", path)
+    }
+}
+
+/// Stores the health endpoint used for `port`; `None` restores the default `/health`
+#[tauri::command]
+pub fn set_server_health_path(
+    port: u16,
+    path: Option<String>,
+    db: State<'_, AgentDb>,
+) -> Result<(), String> {
+    let path = path.map(|p| normalize_health_path(&p)).unwrap_or_default();
+    set_setting(&db, &health_path_key(port), &path)
+}
+
+/// GETs the server's health endpoint; any 2xx counts as healthy
+#[tauri::command]
+pub async fn check_server_health(
+    port: u16,
+    path: Option<String>,
+    db: State<'_, AgentDb>,
+) -> Result<ServerHealth, String> {
+    let path = match path {
+        Some(path) => normalize_health_path(&path),
+        None => get_setting(&db, &health_path_key(port))?
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| DEFAULT_HEALTH_PATH.to_string()),
+    };
+
+    // Local dev servers must never be routed through a configured proxy
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_millis(HEALTH_CHECK_TIMEOUT_MS))
+        .no_proxy()
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let started = Instant::now();
+    let response = client
+        .get(format!("http://127.0.0.1:{}{}", port, path))
+        .send()
+        .await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    Ok(match response {
+        Ok(response) => ServerHealth {
+            port,
+            path,
+            healthy: response.status().is_success(),
+            status_code: Some(response.status().as_u16()),
+            latency_ms: Some(latency_ms),
+            error: None,
+        },
+        Err(e) => ServerHealth {
+            port,
+            path,
+            healthy: false,
+            status_code: None,
+            latency_ms: None,
+            error: Some(if e.is_timeout() {
+                format!("No response within {}ms", HEALTH_CHECK_TIMEOUT_MS)
+            } else {
+                format!("Unreachable: {}", e)
+            }),
+        },
+    })
+}
+
+/// Re-identifies the server on `port` using its response headers as well as its
+/// command line, for servers the scan could only label by port
+#[tauri::command]
+pub async fn identify_service(port: u16) -> Result<DevServer, String> {
+    let mut servers = scan_listening_servers().await?;
+    servers.retain(|s| s.port == port);
+    enrich_servers(&mut servers);
+    let mut server = servers
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("No dev server is listening on port {}", port))?;
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_millis(HEALTH_CHECK_TIMEOUT_MS))
+        .no_proxy()
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let headers: Vec<(String, String)> = match client.get(format!("http://127.0.0.1:{}/", port)).send().await {
+        Ok(response) => response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    let command = list_processes()
+        .unwrap_or_default()
+        .into_iter()
+        .find(|p| p.pid == server.pid)
+        .map(|p| p.command);
+    let classification = classify_service_detailed(port, &server.raw_process_name, command.as_deref(), Some(&headers));
+    server.service_info = service_info(&classification.service);
+    server.service = classification.service;
+    server.classified = classification.classified;
+    server.confidence = classification.confidence;
+
+    Ok(server)
+}
+
+/// Listening servers no signature recognised, least certain first, so unexpected
+/// listeners stand out and can be turned into new signatures
+#[tauri::command]
+pub async fn scan_unclassified() -> Result<Vec<DevServer>, String> {
+    let mut servers = scan_listening_servers().await?;
+    enrich_servers(&mut servers);
+    servers.retain(|s| !s.classified && !s.reserved);
+    servers.sort_by(|a, b| a.confidence.total_cmp(&b.confidence).then(a.port.cmp(&b.port)));
+    Ok(servers)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortReservation {
+    pub id: String,
+    pub port: u16,
+    pub reserved_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Ports claimed by `reserve_port`; dropping a sender releases its sockets
+#[derive(Default)]
+pub struct PortReservationState {
+    reservations: Mutex<HashMap<String, (PortReservation, tokio::sync::oneshot::Sender<()>)>>,
+}
+
+impl PortReservationState {
+    fn list(&self) -> Vec<PortReservation> {
+        self.reservations
+            .lock()
+            .map(|reservations| reservations.values().map(|(r, _)| r.clone()).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Reserved ports are held by the app itself, which the scan excludes, so add them back marked as such
+fn append_reserved_ports(servers: &mut Vec<DevServer>, reservations: &PortReservationState) {
+    for reservation in reservations.list() {
+        if servers.iter().any(|s| s.port == reservation.port) {
+            continue;
+        }
+        let mut server = DevServer::new(
+            reservation.port,
+            "Reserved".to_string(),
+            "queen-code".to_string(),
+            std::process::id(),
+        );
+        server.add_bind_address("127.0.0.1");
+        server.reserved = true;
+        servers.push(server);
+    }
+    servers.sort_by_key(|s| s.port);
+}
+
+/// Claims a port on loopback (IPv4 and, where available, IPv6) until `release_port` is called
+#[tauri::command]
+pub fn reserve_port(port: u16, state: State<'_, PortReservationState>) -> Result<PortReservation, String> {
+    if port == 0 {
+        return Err("Port 0 cannot be reserved".to_string());
+    }
+
+    let bind_error = |e: std::io::Error| {
+        if e.kind() == std::io::ErrorKind::AddrInUse {
+            format!("Port {} is already in use", port)
+        } else {
+            format!("Failed to reserve port {}: {}", port, e)
+        }
+    };
+
+    let ipv4 = std::net::TcpListener::bind(("127.0.0.1", port)).map_err(bind_error)?;
+    // Hosts without IPv6 can't bind ::1, which is fine; if something already holds it, the port isn't free
+    let ipv6 = match std::net::TcpListener::bind(("::1", port)) {
+        Ok(listener) => Some(listener),
+        Err(e) if e.kind() == std::io::ErrorKind::AddrInUse => return Err(bind_error(e)),
+        Err(_) => None,
+    };
+
+    let reservation = PortReservation {
+        id: uuid::Uuid::new_v4().to_string(),
+        port,
+        reserved_at: chrono::Utc::now(),
+    };
+
+    let (release_tx, release_rx) = tokio::sync::oneshot::channel::<()>();
+    tauri::async_runtime::spawn(async move {
+        let _sockets = (ipv4, ipv6);
+        // Resolves on release or when the sender is dropped
+        let _ = release_rx.await;
+        log::info!("Released reserved port {}", port);
+    });
+
+    state
+        .reservations
+        .lock()
+        .map_err(|e| e.to_string())?
+        .insert(reservation.id.clone(), (reservation.clone(), release_tx));
+
+    Ok(reservation)
+}
+
+#[tauri::command]
+pub fn release_port(id: String, state: State<'_, PortReservationState>) -> Result<(), String> {
+    let (_, release) = state
+        .reservations
+        .lock()
+        .map_err(|e| e.to_string())?
+        .remove(&id)
+        .ok_or_else(|| format!("No port reservation with id {}", id))?;
+    let _ = release.send(());
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_port_reservations(state: State<'_, PortReservationState>) -> Vec<PortReservation> {
+    let mut reservations = state.list();
+    reservations.sort_by_key(|r| r.port);
+    reservations
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileLockHolder {
+    pub pid: u32,
+    pub process_name: String,
+    pub command: Option<String>,
+    /// Open files under the requested path
+    pub files: Vec<String>,
+}
+
+#[cfg(not(target_os = "windows"))]
+fn parse_lsof_field_output(output: &str) -> Vec<FileLockHolder> {
+    let mut holders: Vec<FileLockHolder> = Vec::new();
+
+    for line in output.lines() {
+        let (field, value) = line.split_at(line.len().min(1));
+        match field {
+            "p" => {
+                if let Ok(pid) = value.parse::<u32>() {
+                    holders.push(FileLockHolder {
+                        pid,
+                        process_name: String::new(),
+                        command: None,
+                        files: Vec::new(),
+                    });
+                }
+            }
+            "c" => {
+                if let Some(holder) = holders.last_mut() {
+                    holder.process_name = value.to_string();
+                }
+            }
+            "n" => {
+                if let Some(holder) = holders.last_mut() {
+                    if !holder.files.iter().any(|f| f == value) {
+                        holder.files.push(value.to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    holders
+}
+
+#[cfg(not(target_os = "windows"))]
+fn query_file_lock_holders(path: &Path) -> Result<Vec<FileLockHolder>, String> {
+    let path_str = path.to_string_lossy().to_string();
+    // +D walks a directory tree; a plain path only matches that exact file
+    let mut command = Command::new("lsof");
+    command.arg("-Fpcn");
+    if path.is_dir() {
+        command.args(["+D", &path_str]);
+    } else {
+        command.args(["--", &path_str]);
+    }
+
+    let output = command
+        .output()
+        .map_err(|e| format!("Failed to execute lsof: {}", e))?;
+
+    // lsof exits 1 both when nothing holds the path and on some warnings, so rely on stdout
+    Ok(parse_lsof_field_output(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg(target_os = "windows")]
+fn query_file_lock_holders(path: &Path) -> Result<Vec<FileLockHolder>, String> {
+    // Windows has no built-in equivalent of lsof; Sysinternals handle.exe is the usual tool
+    let handle = which::which("handle64")
+        .or_else(|_| which::which("handle"))
+        .map_err(|_| "Finding file locks on Windows requires Sysinternals handle.exe on PATH".to_string())?;
+
+    let output = Command::new(handle)
+        .args(["-accepteula", "-nobanner", &path.to_string_lossy()])
+        .output()
+        .map_err(|e| format!("Failed to execute handle: {}", e))?;
+
+    // e.g. `node.exe  pid: 1234  type: File  1A4: C:\app\.next\trace`
+    let line_re = regex::Regex::new(r"^(\S+)\s+pid:\s*(\d+)\s+type:\s*\w+\s+[0-9A-Fa-f]+:\s*(.+)$")
+        .map_err(|e| e.to_string())?;
+
+    let mut holders: Vec<FileLockHolder> = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Some(caps) = line_re.captures(line.trim()) else {
+            continue;
+        };
+        let Ok(pid) = caps[2].parse::<u32>() else {
+            continue;
+        };
+        let file = caps[3].trim().to_string();
+
+        match holders.iter_mut().find(|h| h.pid == pid) {
+            Some(holder) => holder.files.push(file),
+            None => holders.push(FileLockHolder {
+                pid,
+                process_name: caps[1].to_string(),
+                command: None,
+                files: vec![file],
+            }),
+        }
+    }
+
+    Ok(holders)
+}
+
+/// Processes holding files open under `path`, for clearing EBUSY/locked-file build errors.
+/// Kill them with `kill_dev_server` so the usual confirmation applies.
+#[tauri::command]
+pub async fn find_file_lock_holders(path: String) -> Result<Vec<FileLockHolder>, String> {
+    let target = Path::new(&path);
+    if !target.exists() {
+        return Err(format!("'{}' does not exist", path));
+    }
+
+    let mut holders = query_file_lock_holders(target)?;
+    let own_pid = std::process::id();
+    holders.retain(|h| h.pid != own_pid);
+
+    let processes = list_processes().unwrap_or_default();
+    for holder in holders.iter_mut() {
+        holder.command = processes
+            .iter()
+            .find(|p| p.pid == holder.pid)
+            .map(|p| p.command.clone());
+    }
+    holders.sort_by_key(|h| h.pid);
+
+    Ok(holders)
+}
+
+const SHELL_NAMES: &[&str] = &["sh", "bash", "zsh", "fish", "dash", "cmd", "powershell", "pwsh"];
+
+/// Deep enough for npm -> sh -> node chains under an IDE terminal, while still
+/// bounding a corrupt or cyclic parent table
+const MAX_ANCESTRY_DEPTH: usize = 32;
+
+const TERMINAL_NAMES: &[&str] = &[
+    "terminal", "iterm2", "gnome-terminal-server", "konsole", "alacritty", "kitty", "wezterm-gui",
+    "windowsterminal", "conhost", "tmux", "screen", "xterm", "ghostty", "warp",
+];
+const IDE_NAMES: &[&str] = &[
+    "code", "code helper", "cursor", "cursor helper", "idea", "webstorm", "pycharm", "rider", "zed",
+    "nvim", "vim", "emacs",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AncestorProcess {
+    pub pid: u32,
+    pub name: String,
+    pub command: String,
+    /// `queen-code`, `process_manager`, `ide`, `terminal` or `shell` when recognised
+    pub role: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessAncestry {
+    pub pid: u32,
+    /// The process itself first, then each parent up to the session root
+    pub chain: Vec<AncestorProcess>,
+    /// Role of the nearest ancestor that explains who started the process
+    pub launched_by: Option<String>,
+    /// The chain was cut off at `MAX_ANCESTRY_DEPTH` or by a parent cycle
+    pub truncated: bool,
+}
+
+fn ancestor_role(process: &ProcessDetails, own_pid: u32) -> Option<&'static str> {
+    let name = process.name.to_lowercase();
+    let command = process.command.to_lowercase();
+
+    if process.pid == own_pid {
+        Some("queen-code")
+    } else if detect_process_manager(&process.command).is_some() {
+        Some("process_manager")
+    } else if IDE_NAMES.contains(&name.as_str())
+        || ["visual studio code.app", "cursor.app", "jetbrains", "zed.app"].iter().any(|h| command.contains(h))
+    {
+        Some("ide")
+    } else if TERMINAL_NAMES.contains(&name.as_str())
+        || ["terminal.app", "iterm.app", "ghostty.app", "warp.app"].iter().any(|h| command.contains(h))
+    {
+        Some("terminal")
+    } else if SHELL_NAMES.contains(&name.trim_start_matches('-')) {
+        Some("shell")
+    } else {
+        None
+    }
+}
+
+/// Walks a process's parents so it's clear whether a terminal, an IDE, a process
+/// manager or the app itself started it
+#[tauri::command]
+pub fn get_process_ancestry(pid: u32) -> Result<ProcessAncestry, String> {
+    let processes = list_processes()?;
+    let by_pid: HashMap<u32, &ProcessDetails> = processes.iter().map(|p| (p.pid, p)).collect();
+    if !by_pid.contains_key(&pid) {
+        return Err(format!("Process {} is not running", pid));
+    }
+
+    let own_pid = std::process::id();
+    let mut chain = Vec::new();
+    let mut seen = HashSet::new();
+    let mut current = by_pid.get(&pid).copied();
+    let mut truncated = false;
+
+    while let Some(process) = current {
+        if !seen.insert(process.pid) || chain.len() >= MAX_ANCESTRY_DEPTH {
+            truncated = true;
+            break;
+        }
+
+        chain.push(AncestorProcess {
+            pid: process.pid,
+            name: process.name.clone(),
+            command: process.command.clone(),
+            role: ancestor_role(process, own_pid).map(str::to_string),
+        });
+
+        // Stop at init/launchd, or when the parent has already exited
+        current = (process.ppid > 1 && process.ppid != process.pid)
+            .then(|| by_pid.get(&process.ppid).copied())
+            .flatten();
+    }
+
+    let launched_by = chain
+        .iter()
+        .skip(1)
+        .filter_map(|p| p.role.as_deref())
+        .find(|role| *role != "shell")
+        .map(str::to_string);
+
+    Ok(ProcessAncestry {
+        pid,
+        chain,
+        launched_by,
+        truncated,
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KillStrategy {
+    pub pid: u32,
+    /// One of `graceful`, `kill_tree`, `kill_wrapper_tree`, `stop_via_manager`
+    pub strategy: String,
+    pub explanation: String,
+    /// The processes the strategy would terminate, root first
+    pub target_pids: Vec<u32>,
+    pub managed_by: Option<String>,
+    /// Command that stops the process through its manager, when one applies
+    pub manager_command: Option<String>,
+}
+
+/// Package-manager scripts and `sh -c` style shells; interactive shells (the user's
+/// terminal) are deliberately not wrappers so a tree kill never reaches them
+fn is_wrapper_process(process: &ProcessDetails) -> bool {
+    let mut args = process.command.split_whitespace();
+    let name = executable_name(args.next().unwrap_or("")).to_lowercase();
+    let is_command_shell = SHELL_NAMES.contains(&name.trim_start_matches('-'))
+        && args.any(|arg| matches!(arg, "-c" | "/c" | "/C" | "-Command"));
+
+    is_command_shell || detect_package_manager(&process.command).is_some()
+}
+
+fn process_descendants(pid: u32, processes: &[ProcessDetails]) -> Vec<u32> {
+    let mut descendants = Vec::new();
+    let mut queue = vec![pid];
+    while let Some(parent) = queue.pop() {
+        for child in processes.iter().filter(|p| p.ppid == parent && p.pid != parent) {
+            if !descendants.contains(&child.pid) {
+                descendants.push(child.pid);
+                queue.push(child.pid);
+            }
+        }
+    }
+    descendants
+}
+
+/// Recommends how to stop `pid` without leaving the real server running or
+/// having a supervisor immediately restart it
+#[tauri::command]
+pub fn suggest_kill_strategy(pid: u32) -> Result<KillStrategy, String> {
+    let processes = list_processes()?;
+    let by_pid: HashMap<u32, &ProcessDetails> = processes.iter().map(|p| (p.pid, p)).collect();
+    let process = by_pid
+        .get(&pid)
+        .ok_or_else(|| format!("Process {} is not running", pid))?;
+    let name = executable_name(process.command.split_whitespace().next().unwrap_or(""));
+
+    let manager = process_ancestors(pid, &by_pid)
+        .skip(1)
+        .find_map(|ancestor| by_pid.get(&ancestor).and_then(|p| detect_process_manager(&p.command)));
+    if let Some(manager) = manager {
+        let logical_name = if manager == "pm2" {
+            process_ancestors(pid, &by_pid).find_map(|ancestor| pm2_process_names().remove(&ancestor))
+        } else {
+            None
+        };
+        let manager_command = match (manager, &logical_name) {
+            ("pm2", Some(name)) => Some(format!("pm2 stop {}", name)),
+            ("pm2", None) => Some("pm2 list".to_string()),
+            (other, _) => Some(format!("stop the {} process that started it", other)),
+        };
+
+        return Ok(KillStrategy {
+            pid,
+            strategy: "stop_via_manager".to_string(),
+            explanation: format!(
+                "{} is supervised by {}, which will restart it if it's killed directly. Stop it through {} instead.",
+                name, manager, manager
+            ),
+            target_pids: vec![pid],
+            managed_by: Some(manager.to_string()),
+            manager_command,
+        });
+    }
+
+    // `npm run dev` -> sh -c -> node: killing only the listener leaves the wrappers,
+    // killing only a wrapper can orphan the listener, so take down the whole chain
+    let wrapper_root = process_ancestors(pid, &by_pid)
+        .skip(1)
+        .take_while(|ancestor| by_pid.get(ancestor).map(|p| is_wrapper_process(p)).unwrap_or(false))
+        .last();
+    if let Some(root) = wrapper_root {
+        let mut target_pids = vec![root];
+        target_pids.extend(process_descendants(root, &processes));
+        let wrapper = by_pid.get(&root).map(|p| p.command.clone()).unwrap_or_default();
+
+        return Ok(KillStrategy {
+            pid,
+            strategy: "kill_wrapper_tree".to_string(),
+            explanation: format!(
+                "{} was started through `{}`. Kill the wrapper and everything under it so nothing is left behind.",
+                name, wrapper
+            ),
+            target_pids,
+            managed_by: None,
+            manager_command: None,
+        });
+    }
+
+    let descendants = process_descendants(pid, &processes);
+    if !descendants.is_empty() {
+        let mut target_pids = vec![pid];
+        target_pids.extend(&descendants);
+
+        let explanation = if is_wrapper_process(process) {
+            format!(
+                "{} is a wrapper; the real server runs in its {} child process(es). Kill the whole tree.",
+                name,
+                descendants.len()
+            )
+        } else {
+            format!(
+                "{} has {} child process(es) (workers or watchers) that could outlive it. Kill the whole tree.",
+                name,
+                descendants.len()
+            )
+        };
+
+        return Ok(KillStrategy {
+            pid,
+            strategy: "kill_tree".to_string(),
+            explanation,
+            target_pids,
+            managed_by: None,
+            manager_command: None,
+        });
+    }
+
+    let service = detect_service(0, &name);
+    let explanation = if matches!(service.as_str(), "PostgreSQL" | "MySQL" | "MongoDB" | "Redis") {
+        format!("{} is a database; stop it gracefully so it can flush data to disk.", name)
+    } else {
+        format!("{} has no children or supervisor; a graceful stop is enough.", name)
+    };
+
+    Ok(KillStrategy {
+        pid,
+        strategy: "graceful".to_string(),
+        explanation,
+        target_pids: vec![pid],
+        managed_by: None,
+        manager_command: None,
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueenProjectServer {
+    pub project_id: i64,
+    pub project_name: String,
+    pub project_path: String,
+    pub template: Option<String>,
+    #[serde(flatten)]
+    pub server: DevServer,
+}
+
+/// Running servers that belong to a tracked Queen project, matched by the server's
+/// cwd lying under the project path; everything else is left out
+#[tauri::command]
+pub async fn scan_queen_project_servers(db: State<'_, AgentDb>) -> Result<Vec<QueenProjectServer>, String> {
+    let projects: Vec<(std::path::PathBuf, TrackedProject)> = load_tracked_projects(&db)?
+        .into_iter()
+        .map(|p| (std::path::PathBuf::from(canonicalize_cwd(&p.path)), p))
+        .collect();
+    if projects.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut servers = scan_listening_servers().await?;
+    enrich_servers(&mut servers);
+    apply_compose_services(&db, &mut servers)?;
+    apply_project_labels(&db, &mut servers)?;
+
+    Ok(servers
+        .into_iter()
+        .filter_map(|server| {
+            let location = server.cwd.clone().or_else(|| server.project_root.clone())?;
+            let location = Path::new(&location);
+            // The deepest match wins when tracked projects are nested
+            let (_, project) = projects
+                .iter()
+                .filter(|(path, _)| location.starts_with(path))
+                .max_by_key(|(path, _)| path.components().count())?;
+
+            Some(QueenProjectServer {
+                project_id: project.id,
+                project_name: project.name.clone(),
+                project_path: project.path.clone(),
+                template: project.template.clone(),
+                server,
+            })
+        })
+        .collect())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectImportCandidate {
+    pub path: String,
+    pub name: String,
+    pub ports: Vec<u16>,
+    pub services: Vec<String>,
+}
+
+/// Project roots of running servers that aren't tracked yet, for the user to confirm
+/// and import with `add_tracked_project`
+#[tauri::command]
+pub async fn import_running_as_projects(db: State<'_, AgentDb>) -> Result<Vec<ProjectImportCandidate>, String> {
+    let tracked: HashSet<String> = load_tracked_projects(&db)?
+        .into_iter()
+        .map(|p| canonicalize_cwd(&p.path))
+        .collect();
+
+    let mut servers = scan_listening_servers().await?;
+    enrich_servers(&mut servers);
+
+    let mut candidates: BTreeMap<String, ProjectImportCandidate> = BTreeMap::new();
+    for server in servers {
+        let Some(root) = server.project_root else {
+            continue;
+        };
+        // find_project_root falls back to the bare cwd; only offer real projects
+        let root_path = Path::new(&root);
+        let is_project = root_path.join(".git").exists()
+            || PROJECT_ROOT_MARKERS.iter().any(|marker| root_path.join(marker).exists());
+        if !is_project || tracked.contains(&root) {
+            continue;
+        }
+
+        let candidate = candidates.entry(root.clone()).or_insert_with(|| ProjectImportCandidate {
+            name: root_path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| root.clone()),
+            path: root.clone(),
+            ports: Vec::new(),
+            services: Vec::new(),
+        });
+        candidate.ports.push(server.port);
+        if !candidate.services.contains(&server.service) {
+            candidate.services.push(server.service);
+        }
+    }
+
+    Ok(candidates.into_values().collect())
+}
+
+/// Per-server preferences with a typed view of the common ones. Keys are free-form
+/// so the UI can add its own; anything not covered by a field lands in `extra`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ServerPrefs {
+    pub server_id: String,
+    pub pinned: bool,
+    pub muted: bool,
+    pub preferred_browser: Option<String>,
+    pub extra: BTreeMap<String, String>,
+}
+
+impl ServerPrefs {
+    fn from_rows(server_id: &str, rows: Vec<(String, String)>) -> Self {
+        let mut prefs = ServerPrefs {
+            server_id: server_id.to_string(),
+            ..Default::default()
+        };
+        for (key, value) in rows {
+            match key.as_str() {
+                "pinned" => prefs.pinned = value == "true",
+                "muted" => prefs.muted = value == "true",
+                "preferred_browser" => prefs.preferred_browser = Some(value),
+                _ => {
+                    prefs.extra.insert(key, value);
+                }
+            }
+        }
+        prefs
+    }
+}
+
+fn load_server_prefs(db: &AgentDb, server_id: &str) -> Result<ServerPrefs, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let mut stmt = conn
+        .prepare("SELECT key, value FROM server_prefs WHERE server_id = ?1")
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(rusqlite::params![server_id], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<(String, String)>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(ServerPrefs::from_rows(server_id, rows))
+}
+
+#[tauri::command]
+pub fn get_server_prefs(server_id: String, db: State<'_, AgentDb>) -> Result<ServerPrefs, String> {
+    load_server_prefs(&db, &server_id)
+}
+
+/// Stores one preference for a server, keyed on its stable `server_id` so it
+/// survives restarts. An empty value removes the preference.
+#[tauri::command]
+pub fn set_server_pref(
+    server_id: String,
+    key: String,
+    value: String,
+    db: State<'_, AgentDb>,
+) -> Result<ServerPrefs, String> {
+    let key = key.trim();
+    if server_id.trim().is_empty() || key.is_empty() {
+        return Err("Server id and preference key are required".to_string());
+    }
+    if matches!(key, "pinned" | "muted") && !matches!(value.as_str(), "true" | "false" | "") {
+        return Err(format!("'{}' must be true or false", key));
+    }
+
+    {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        if value.is_empty() {
+            conn.execute(
+                "DELETE FROM server_prefs WHERE server_id = ?1 AND key = ?2",
+                rusqlite::params![server_id, key],
+            )
+        } else {
+            conn.execute(
+                "INSERT OR REPLACE INTO server_prefs (server_id, key, value, updated_at)
+                 VALUES (?1, ?2, ?3, CURRENT_TIMESTAMP)",
+                rusqlite::params![server_id, key, value],
+            )
+        }
+        .map_err(|e| format!("Failed to save server preference: {}", e))?;
+    }
+
+    load_server_prefs(&db, &server_id)
+}
+
+const DEFAULT_WATCH_INTERVAL_SECS: u64 = 5;
+/// Load per core above which the watcher starts backing off, and below which it
+/// returns to its normal cadence. The gap keeps it from flapping around one value.
+const HIGH_LOAD_PER_CORE: f64 = 1.0;
+const NORMAL_LOAD_PER_CORE: f64 = 0.7;
+const MAX_WATCH_BACKOFF: u64 = 8;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScanIntervalStatus {
+    pub running: bool,
+    pub base_interval_secs: u64,
+    pub effective_interval_secs: u64,
+    /// 1-minute load average (Unix) or processor queue length (Windows) per core
+    pub load_per_core: Option<f64>,
+    pub throttled: bool,
+}
+
+/// Background rescans that emit `dev-servers-changed` when the server list changes
+#[derive(Default)]
+pub struct ServerWatchState {
+    stop: Mutex<Option<watch::Sender<bool>>>,
+    status: Arc<Mutex<ScanIntervalStatus>>,
+    /// The watcher allowed to update `status`; bumped under the status lock on each start
+    current_run: Arc<std::sync::atomic::AtomicU64>,
+}
+
+fn cpu_count() -> f64 {
+    std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1) as f64
+}
+
+#[cfg(not(target_os = "windows"))]
+fn system_load_per_core() -> Option<f64> {
+    let mut loads = [0f64; 3];
+    let read = unsafe { libc::getloadavg(loads.as_mut_ptr(), 3) };
+    (read >= 1).then(|| loads[0] / cpu_count())
+}
+
+/// Windows has no load average; the processor queue length is the closest
+/// equivalent, and a queue of two threads per core already means contention
+#[cfg(target_os = "windows")]
+fn system_load_per_core() -> Option<f64> {
+    let output = Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            "(Get-CimInstance Win32_PerfFormattedData_PerfOS_System).ProcessorQueueLength",
+        ])
+        .output()
+        .ok()?;
+    let queue = String::from_utf8_lossy(&output.stdout).trim().parse::<f64>().ok()?;
+    Some(queue / 2.0 / cpu_count())
+}
+
+/// Doubles the backoff while the machine stays loaded and resets it once load is back to normal
+fn next_watch_backoff(backoff: u64, load_per_core: Option<f64>) -> u64 {
+    match load_per_core {
+        Some(load) if load > HIGH_LOAD_PER_CORE => (backoff * 2).min(MAX_WATCH_BACKOFF),
+        Some(load) if load < NORMAL_LOAD_PER_CORE => 1,
+        _ => backoff,
+    }
+}
+
+#[tauri::command]
+pub fn start_server_watch(
+    interval_secs: Option<u64>,
+    app: AppHandle,
+    state: State<'_, ServerWatchState>,
+) -> Result<ScanIntervalStatus, String> {
+    let base = interval_secs.unwrap_or(DEFAULT_WATCH_INTERVAL_SECS).max(1);
+    let (stop_tx, mut stop_rx) = watch::channel(false);
+    if let Some(previous) = state.stop.lock().map_err(|e| e.to_string())?.replace(stop_tx) {
+        let _ = previous.send(true);
+    }
+
+    let status = state.status.clone();
+    let current_run = state.current_run.clone();
+    let initial = ScanIntervalStatus {
+        running: true,
+        base_interval_secs: base,
+        effective_interval_secs: base,
+        load_per_core: system_load_per_core(),
+        throttled: false,
+    };
+    let run = {
+        let mut status = status.lock().map_err(|e| e.to_string())?;
+        *status = initial.clone();
+        current_run.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1
+    };
+    // A replaced watcher may still be winding down; only the latest run reports status
+    let is_current = move || current_run.load(std::sync::atomic::Ordering::SeqCst) == run;
+
+    tauri::async_runtime::spawn(async move {
+        let mut backoff = 1;
+        let mut last_seen: Option<Vec<(u16, u32)>> = None;
+        let mut up: Option<HashMap<String, (u16, u32)>> = None;
+
+        loop {
+            let load = system_load_per_core();
+            backoff = next_watch_backoff(backoff, load);
+            let effective = base * backoff;
+            if let Ok(mut status) = status.lock() {
+                if is_current() {
+                    status.effective_interval_secs = effective;
+                    status.load_per_core = load;
+                    status.throttled = backoff > 1;
+                }
+            }
 
-        #[cfg(target_os = "windows")]
-        {
-            let output = Command::new("taskkill")
-                .args(&["/F", "/PID", &pid.to_string()])
-                .output()
-                .map_err(|e| format!("Failed to kill process {}: {}", pid, e))?;
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_secs(effective)) => {}
+                _ = stop_rx.changed() => break,
+            }
 
-            if !output.status.success() {
-                return Err(format!("Failed to kill PID {}: {}", pid, String::from_utf8_lossy(&output.stderr)));
+            let Ok(mut servers) = scan_listening_servers().await else {
+                continue;
+            };
+            let mut seen: Vec<(u16, u32)> = servers.iter().map(|s| (s.port, s.pid)).collect();
+            seen.sort_unstable();
+            if last_seen.as_ref() != Some(&seen) {
+                servers.iter_mut().for_each(assign_server_id);
+                let current: HashMap<String, (u16, u32)> = servers
+                    .iter()
+                    .filter(|s| !s.reserved)
+                    .map(|s| (s.server_id.clone(), (s.port, s.pid)))
+                    .collect();
+                let db = app.state::<AgentDb>();
+                if let Err(e) = record_server_transitions(&db, up.as_ref(), &current) {
+                    log::warn!("Failed to record server events: {}", e);
+                }
+                up = Some(current);
+
+                if last_seen.is_some() {
+                    let _ = app.emit("dev-servers-changed", &servers);
+                }
+                last_seen = Some(seen);
             }
         }
+
+        if let Ok(mut status) = status.lock() {
+            if is_current() {
+                status.running = false;
+            }
+        }
+    });
+
+    Ok(initial)
+}
+
+#[tauri::command]
+pub fn stop_server_watch(state: State<'_, ServerWatchState>) -> Result<(), String> {
+    if let Some(stop) = state.stop.lock().map_err(|e| e.to_string())?.take() {
+        let _ = stop.send(true);
+    }
+    Ok(())
+}
+
+/// The watcher's current cadence, which stretches up to `MAX_WATCH_BACKOFF` times
+/// the base interval while the system is under load
+#[tauri::command]
+pub fn get_scan_interval(state: State<'_, ServerWatchState>) -> Result<ScanIntervalStatus, String> {
+    Ok(state.status.lock().map_err(|e| e.to_string())?.clone())
+}
+
+/// Server events kept in total, and the age after which they're pruned regardless
+const MAX_SERVER_EVENTS: i64 = 20_000;
+const SERVER_EVENT_RETENTION_DAYS: i64 = 30;
+
+/// Servers whose latest recorded event is `up`, so a watcher starting after a
+/// restart of the app picks up where the last one stopped
+fn open_server_intervals(conn: &rusqlite::Connection) -> rusqlite::Result<HashMap<String, (u16, u32)>> {
+    let mut stmt = conn.prepare(
+        "SELECT e.server_id, e.port, e.pid FROM server_events e
+         WHERE e.event = 'up'
+           AND e.id = (SELECT MAX(id) FROM server_events WHERE server_id = e.server_id)",
+    )?;
+    let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, (row.get(1)?, row.get(2)?))))?;
+    rows.collect()
+}
+
+/// Logs `down` for servers that vanished (or came back under another PID) and `up`
+/// for new ones since `previous`, then prunes old rows
+fn record_server_transitions(
+    db: &AgentDb,
+    previous: Option<&HashMap<String, (u16, u32)>>,
+    current: &HashMap<String, (u16, u32)>,
+) -> Result<(), String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let loaded;
+    let previous = match previous {
+        Some(previous) => previous,
+        None => {
+            loaded = open_server_intervals(&conn).map_err(|e| e.to_string())?;
+            &loaded
+        }
+    };
+
+    let insert = |server_id: &str, (port, pid): (u16, u32), event: &str| {
+        conn.execute(
+            "INSERT INTO server_events (server_id, port, pid, event) VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![server_id, port, pid, event],
+        )
+        .map(|_| ())
+        .map_err(|e| format!("Failed to record server event: {}", e))
+    };
+
+    for (server_id, &was) in previous {
+        if current.get(server_id) != Some(&was) {
+            insert(server_id, was, "down")?;
+        }
+    }
+    for (server_id, &now) in current {
+        if previous.get(server_id) != Some(&now) {
+            insert(server_id, now, "up")?;
+        }
     }
 
+    conn.execute(
+        "DELETE FROM server_events
+         WHERE created_at < datetime('now', ?1)
+            OR id <= (SELECT MAX(id) FROM server_events) - ?2",
+        rusqlite::params![format!("-{} days", SERVER_EVENT_RETENTION_DAYS), MAX_SERVER_EVENTS],
+    )
+    .map_err(|e| format!("Failed to prune server events: {}", e))?;
     Ok(())
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UptimeInterval {
+    pub port: u16,
+    pub pid: u32,
+    pub started_at: String,
+    /// None while the server is still up
+    pub ended_at: Option<String>,
+    pub duration_secs: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerUptimeHistory {
+    pub server_id: String,
+    pub intervals: Vec<UptimeInterval>,
+    /// Times the server went down and came back
+    pub restarts: usize,
+}
+
+fn seconds_between(start: &str, end: &str) -> Option<i64> {
+    let parse = |t: &str| chrono::NaiveDateTime::parse_from_str(t, "%Y-%m-%d %H:%M:%S").ok();
+    Some((parse(end)? - parse(start)?).num_seconds())
+}
+
+/// Intervals the server with `server_id` was up, oldest first, as seen by the
+/// server watcher
+#[tauri::command]
+pub fn get_server_uptime_history(
+    server_id: String,
+    db: State<'_, AgentDb>,
+) -> Result<ServerUptimeHistory, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let mut stmt = conn
+        .prepare("SELECT port, pid, event, created_at FROM server_events WHERE server_id = ?1 ORDER BY id")
+        .map_err(|e| e.to_string())?;
+    let events = stmt
+        .query_map(rusqlite::params![server_id], |row| {
+            Ok((row.get::<_, u16>(0)?, row.get::<_, u32>(1)?, row.get::<_, String>(2)?, row.get::<_, String>(3)?))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read server events: {}", e))?;
+
+    let mut intervals: Vec<UptimeInterval> = Vec::new();
+    for (port, pid, event, at) in events {
+        match event.as_str() {
+            "up" => intervals.push(UptimeInterval {
+                port,
+                pid,
+                started_at: at,
+                ended_at: None,
+                duration_secs: None,
+            }),
+            _ => {
+                if let Some(open) = intervals.last_mut().filter(|i| i.ended_at.is_none()) {
+                    open.duration_secs = seconds_between(&open.started_at, &at);
+                    open.ended_at = Some(at);
+                }
+            }
+        }
+    }
+
+    Ok(ServerUptimeHistory {
+        server_id,
+        restarts: intervals.len().saturating_sub(1),
+        intervals,
+    })
+}
+
+/// Share of `max_user_watches` in use before HMR breakage becomes likely
+#[cfg(target_os = "linux")]
+const INOTIFY_WARN_RATIO: f64 = 0.8;
+/// Commonly recommended limit for machines running several JS dev servers
+#[cfg(target_os = "linux")]
+const INOTIFY_RECOMMENDED_WATCHES: u64 = 524_288;
+#[cfg(target_os = "linux")]
+const INOTIFY_TOP_PROCESSES: usize = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InotifyWatcher {
+    pub pid: u32,
+    pub name: String,
+    pub watches: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InotifyLimits {
+    pub max_user_watches: u64,
+    pub max_user_instances: Option<u64>,
+    /// Watches held by processes this user can inspect; others' aren't counted
+    pub current_watches: u64,
+    pub near_limit: bool,
+    /// Value to raise `max_user_watches` to, when it's worth raising
+    pub suggested_max_user_watches: Option<u64>,
+    pub top_watchers: Vec<InotifyWatcher>,
+    pub hint: Option<String>,
+}
+
+#[cfg(target_os = "linux")]
+fn read_proc_number(path: &str) -> Option<u64> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Counts `inotify wd:` entries across every readable fd of every process
+#[cfg(target_os = "linux")]
+fn inotify_watchers() -> Vec<InotifyWatcher> {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let pid: u32 = entry.file_name().to_str()?.parse().ok()?;
+            let fdinfo = std::fs::read_dir(entry.path().join("fdinfo")).ok()?;
+            let watches: u64 = fdinfo
+                .flatten()
+                .filter_map(|fd| std::fs::read_to_string(fd.path()).ok())
+                .map(|info| info.lines().filter(|l| l.starts_with("inotify wd:")).count() as u64)
+                .sum();
+            (watches > 0).then(|| InotifyWatcher {
+                pid,
+                name: std::fs::read_to_string(entry.path().join("comm"))
+                    .map(|n| n.trim().to_string())
+                    .unwrap_or_default(),
+                watches,
+            })
+        })
+        .collect()
+}
+
+/// inotify watch usage against the per-user limit; running out makes Vite and
+/// webpack fail with ENOSPC and silently lose HMR
+#[tauri::command]
+pub async fn check_inotify_limits() -> Result<InotifyLimits, String> {
+    #[cfg(target_os = "linux")]
+    {
+        let max_user_watches = read_proc_number("/proc/sys/fs/inotify/max_user_watches")
+            .ok_or("Couldn't read /proc/sys/fs/inotify/max_user_watches")?;
+        let max_user_instances = read_proc_number("/proc/sys/fs/inotify/max_user_instances");
+
+        let mut watchers = tokio::task::spawn_blocking(inotify_watchers)
+            .await
+            .map_err(|e| format!("Failed to count inotify watches: {}", e))?;
+        let current_watches = watchers.iter().map(|w| w.watches).sum::<u64>();
+        watchers.sort_by_key(|w| std::cmp::Reverse(w.watches));
+        watchers.truncate(INOTIFY_TOP_PROCESSES);
+
+        let near_limit = current_watches as f64 >= max_user_watches as f64 * INOTIFY_WARN_RATIO;
+        let suggested_max_user_watches = (near_limit || max_user_watches < INOTIFY_RECOMMENDED_WATCHES)
+            .then(|| INOTIFY_RECOMMENDED_WATCHES.max(max_user_watches.saturating_mul(2)));
+        let hint = near_limit.then(|| {
+            format!(
+                "{} of {} inotify watches are in use; dev servers may fail with ENOSPC. Raise the limit with: sudo sysctl fs.inotify.max_user_watches={}",
+                current_watches,
+                max_user_watches,
+                suggested_max_user_watches.unwrap_or(INOTIFY_RECOMMENDED_WATCHES)
+            )
+        });
+
+        Ok(InotifyLimits {
+            max_user_watches,
+            max_user_instances,
+            current_watches,
+            near_limit,
+            suggested_max_user_watches,
+            top_watchers: watchers,
+            hint,
+        })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        Err("Unsupported: inotify limits only exist on Linux".to_string())
+    }
+}
+
+/// Soft `RLIMIT_NOFILE` below which several watchers in one stack start failing with EMFILE
+#[cfg(any(unix, test))]
+const FD_LIMIT_WARN: u64 = 4096;
+/// Soft limit worth raising to for monorepos and large dev stacks
+#[cfg(any(unix, test))]
+const FD_LIMIT_RECOMMENDED: u64 = 65_536;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FdLimits {
+    /// None when unlimited
+    pub soft_limit: Option<u64>,
+    /// None when unlimited
+    pub hard_limit: Option<u64>,
+    pub low: bool,
+    pub recommended_soft_limit: u64,
+    pub hint: Option<String>,
+}
+
+#[cfg(any(unix, test))]
+fn fd_limits_from(soft_limit: Option<u64>, hard_limit: Option<u64>) -> FdLimits {
+    let low = soft_limit.is_some_and(|soft| soft < FD_LIMIT_WARN);
+    let hint = low.then(|| {
+        let mut hint = format!(
+            "Only {} file descriptors per process; large dev stacks may fail with EMFILE (too many open files). ",
+            soft_limit.unwrap_or_default()
+        );
+        if cfg!(target_os = "macos") {
+            hint.push_str(&format!(
+                "Add `ulimit -n {}` to your shell profile, or for apps launched from the Dock: sudo launchctl limit maxfiles {} unlimited",
+                FD_LIMIT_RECOMMENDED, FD_LIMIT_RECOMMENDED
+            ));
+        } else {
+            hint.push_str(&format!(
+                "Add `ulimit -n {}` to your shell profile, or raise `nofile` in /etc/security/limits.conf",
+                FD_LIMIT_RECOMMENDED
+            ));
+        }
+        if hard_limit.is_some_and(|hard| hard < FD_LIMIT_RECOMMENDED) {
+            hint.push_str(&format!(
+                "; the hard limit of {} must be raised as root first",
+                hard_limit.unwrap_or_default()
+            ));
+        }
+        hint
+    });
+
+    FdLimits {
+        soft_limit,
+        hard_limit,
+        low,
+        recommended_soft_limit: FD_LIMIT_RECOMMENDED,
+        hint,
+    }
+}
+
+/// Per-process open file limit of the app, which servers it launches inherit
+#[tauri::command]
+pub async fn check_fd_limits() -> Result<FdLimits, String> {
+    #[cfg(unix)]
+    {
+        let mut limit = libc::rlimit { rlim_cur: 0, rlim_max: 0 };
+        if unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) } != 0 {
+            return Err(format!("Failed to read RLIMIT_NOFILE: {}", std::io::Error::last_os_error()));
+        }
+        let finite = |value: libc::rlim_t| (value != libc::RLIM_INFINITY).then_some(value);
+
+        Ok(fd_limits_from(finite(limit.rlim_cur), finite(limit.rlim_max)))
+    }
+
+    #[cfg(not(unix))]
+    {
+        Err("Unsupported: file descriptor limits only exist on Unix".to_string())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvironmentReadiness {
+    /// None where the platform has no such limit or it couldn't be read
+    pub fd_limits: Option<FdLimits>,
+    pub inotify: Option<InotifyLimits>,
+    pub ready: bool,
+    pub warnings: Vec<String>,
+}
+
+/// OS limits that large dev stacks run into, gathered in one place
+#[tauri::command]
+pub async fn check_environment_readiness() -> Result<EnvironmentReadiness, String> {
+    let fd_limits = check_fd_limits().await.ok();
+    let inotify = check_inotify_limits().await.ok();
+
+    let warnings: Vec<String> = fd_limits
+        .iter()
+        .filter_map(|l| l.hint.clone())
+        .chain(inotify.iter().filter_map(|l| l.hint.clone()))
+        .collect();
+
+    Ok(EnvironmentReadiness {
+        fd_limits,
+        inotify,
+        ready: warnings.is_empty(),
+        warnings,
+    })
+}
+
+/// Low ports tried in turn for the throwaway bind, skipping any already in use
+const PRIVILEGED_PROBE_PORTS: &[u16] = &[1023, 1013, 999, 973, 911, 80, 443];
+#[cfg(target_os = "linux")]
+const CAP_NET_BIND_SERVICE: u32 = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivilegedPortCapability {
+    /// Result of the throwaway bind; None when every probe port was taken
+    pub can_bind: Option<bool>,
+    pub probed_port: Option<u16>,
+    /// root, or an elevated Administrator on Windows
+    pub elevated: bool,
+    /// Linux only: whether this process holds CAP_NET_BIND_SERVICE
+    pub has_bind_capability: Option<bool>,
+    /// Linux only: `net.ipv4.ip_unprivileged_port_start`
+    pub unprivileged_port_start: Option<u16>,
+    pub guidance: Vec<String>,
+}
+
+/// Binds and immediately releases a port below 1024 on loopback
+fn probe_privileged_bind() -> (Option<bool>, Option<u16>) {
+    for &port in PRIVILEGED_PROBE_PORTS {
+        match std::net::TcpListener::bind(("127.0.0.1", port)) {
+            Ok(_) => return (Some(true), Some(port)),
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => return (Some(false), Some(port)),
+            Err(_) => continue,
+        }
+    }
+    (None, None)
+}
+
+#[cfg(target_os = "linux")]
+fn has_effective_capability(capability: u32) -> Option<bool> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let mask = status.lines().find_map(|l| l.strip_prefix("CapEff:"))?.trim();
+    let mask = u64::from_str_radix(mask, 16).ok()?;
+    Some(mask & (1 << capability) != 0)
+}
+
+#[cfg(unix)]
+fn is_elevated() -> bool {
+    unsafe { libc::geteuid() == 0 }
+}
+
+#[cfg(target_os = "windows")]
+fn is_elevated() -> bool {
+    // `net session` is refused unless the shell is elevated
+    Command::new("net")
+        .arg("session")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+/// Whether this user can bind ports below 1024 and, when not, how to get a server
+/// onto 80/443 anyway
+#[tauri::command]
+pub async fn check_privileged_port_capability() -> Result<PrivilegedPortCapability, String> {
+    let (can_bind, probed_port) = tokio::task::spawn_blocking(probe_privileged_bind)
+        .await
+        .map_err(|e| format!("Failed to probe privileged ports: {}", e))?;
+    let elevated = tokio::task::spawn_blocking(is_elevated).await.unwrap_or(false);
+
+    #[cfg(target_os = "linux")]
+    let (has_bind_capability, unprivileged_port_start) = (
+        has_effective_capability(CAP_NET_BIND_SERVICE),
+        std::fs::read_to_string("/proc/sys/net/ipv4/ip_unprivileged_port_start")
+            .ok()
+            .and_then(|v| v.trim().parse::<u16>().ok()),
+    );
+    #[cfg(not(target_os = "linux"))]
+    let (has_bind_capability, unprivileged_port_start) = (None, None);
+
+    let mut guidance = Vec::new();
+    match can_bind {
+        Some(true) => guidance.push("Ports below 1024 can be bound; a server failing on 80/443 has another cause, such as the port already being in use.".to_string()),
+        Some(false) => {
+            if cfg!(target_os = "linux") {
+                guidance.push("Grant the server binary the capability instead of running it as root: sudo setcap 'cap_net_bind_service=+ep' $(readlink -f $(which node))".to_string());
+                guidance.push("Or lower the threshold for all users: sudo sysctl net.ipv4.ip_unprivileged_port_start=80".to_string());
+            } else if cfg!(target_os = "macos") {
+                guidance.push("macOS only restricts ports below 1024 on specific addresses; bind to 0.0.0.0 or run the server with sudo.".to_string());
+            } else {
+                guidance.push("Run the server from an elevated (Administrator) terminal, or check whether a firewall policy blocks the port.".to_string());
+            }
+            guidance.push("Alternatively run on a high port such as 8080 and forward 80/443 to it with a reverse proxy.".to_string());
+        }
+        None => guidance.push("Every probe port below 1024 was already in use, so binding couldn't be tested.".to_string()),
+    }
+
+    Ok(PrivilegedPortCapability {
+        can_bind,
+        probed_port,
+        elevated,
+        has_bind_capability,
+        unprivileged_port_start,
+        guidance,
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MacosPermissionStatus {
+    /// `granted`, `restricted`, or `unknown` when no other process was listening to probe
+    pub status: String,
+    /// Whether the TCC database is readable, which only Full Disk Access allows
+    pub full_disk_access: Option<bool>,
+    pub processes_checked: usize,
+    /// Detected servers whose working directory or environment couldn't be read
+    pub unreadable_pids: Vec<u32>,
+    pub guidance: Vec<String>,
+}
+
+/// Whether enrichment can read the cwd and environment of detected servers, which
+/// explains empty project roots and Python envs on macOS
+#[tauri::command]
+pub async fn check_macos_permissions() -> Result<MacosPermissionStatus, String> {
+    #[cfg(target_os = "macos")]
+    {
+        let own_pid = std::process::id();
+        let pids: Vec<u32> = scan_listening_servers()
+            .await?
+            .into_iter()
+            .map(|s| s.pid)
+            .filter(|&pid| pid != own_pid)
+            .collect::<HashSet<u32>>()
+            .into_iter()
+            .collect();
+
+        let probe_pids = pids.clone();
+        let mut unreadable_pids = tokio::task::spawn_blocking(move || {
+            probe_pids
+                .into_iter()
+                .filter(|&pid| get_process_cwd(pid).is_none() || get_process_env(pid).is_empty())
+                .collect::<Vec<u32>>()
+        })
+        .await
+        .map_err(|e| format!("Failed to probe process info: {}", e))?;
+        unreadable_pids.sort_unstable();
+
+        let full_disk_access = dirs::home_dir()
+            .map(|home| home.join("Library/Application Support/com.apple.TCC/TCC.db"))
+            .filter(|db| db.exists())
+            .map(|db| std::fs::File::open(db).is_ok());
+
+        let status = if pids.is_empty() {
+            "unknown"
+        } else if unreadable_pids.is_empty() {
+            "granted"
+        } else {
+            "restricted"
+        };
+
+        let mut guidance = Vec::new();
+        if !unreadable_pids.is_empty() {
+            guidance.push(format!(
+                "{} of {} servers couldn't be inspected, so their project, working directory or environment shows as empty.",
+                unreadable_pids.len(),
+                pids.len()
+            ));
+            if full_disk_access != Some(true) {
+                guidance.push("Open System Settings > Privacy & Security > Full Disk Access, enable Queen Code, then restart the app.".to_string());
+            }
+            guidance.push("Processes owned by another user (e.g. started with sudo) stay unreadable without running the app as that user.".to_string());
+        }
+
+        Ok(MacosPermissionStatus {
+            status: status.to_string(),
+            full_disk_access,
+            processes_checked: pids.len(),
+            unreadable_pids,
+            guidance,
+        })
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        Err("Unsupported: permission checks only apply to macOS".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinked_cwd_resolves_to_real_project_root() {
+        let temp = tempfile::tempdir().unwrap();
+        let real = temp.path().join("real-app");
+        std::fs::create_dir_all(real.join("src")).unwrap();
+        std::fs::write(real.join("package.json"), "{}").unwrap();
+        let link = temp.path().join("linked-app");
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+
+        let via_link = canonicalize_cwd(&link.join("src").to_string_lossy());
+        let via_real = canonicalize_cwd(&real.join("src").to_string_lossy());
+        assert_eq!(via_link, via_real);
+        assert_eq!(find_project_root(&via_link), find_project_root(&via_real));
+        assert_eq!(
+            Path::new(&find_project_root(&via_link)),
+            std::fs::canonicalize(&real).unwrap()
+        );
+
+        // Paths that no longer exist are kept as-is
+        let missing = temp.path().join("gone").to_string_lossy().to_string();
+        assert_eq!(canonicalize_cwd(&missing), missing);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_decode_proc_address() {
+        if cfg!(target_endian = "little") {
+            assert_eq!(decode_proc_address("0100007F:0BB8"), Some(("127.0.0.1".to_string(), 3000)));
+            assert_eq!(
+                decode_proc_address("00000000000000000000000001000000:1F90"),
+                Some(("[::1]".to_string(), 8080))
+            );
+        }
+        assert_eq!(decode_proc_address("00000000:1435"), Some(("*".to_string(), 5173)));
+        assert_eq!(
+            decode_proc_address("00000000000000000000000000000000:0050"),
+            Some(("[::]".to_string(), 80))
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_proc_net_tcp_keeps_only_listeners() {
+        let contents = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000:1435 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 41234 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:0BB8 0100007F:D2F0 01 00000000:00000000 00:00000000 00000000  1000        0 41235 1 0000000000000000 20 4 30 10 -1
+";
+        let listeners = parse_proc_net_tcp(contents);
+        assert_eq!(
+            listeners,
+            vec![ProcListener { host: "*".to_string(), port: 5173, inode: 41234 }]
+        );
+    }
+
+    #[test]
+    fn test_canonical_process_name_folds_platform_variants() {
+        let node_variants = [
+            "node",
+            "node.exe",
+            "/usr/local/bin/node",
+            "C:\\Program Files\\nodejs\\node.exe",
+            "node (tsx)",
+            "node18",
+            "node-v20.11.0",
+        ];
+        for raw in node_variants {
+            assert_eq!(canonical_process_name(raw), "node", "{}", raw);
+        }
+        for raw in ["python3", "Python", "python3.11", "/opt/homebrew/bin/python3.12", "python.exe"] {
+            assert_eq!(canonical_process_name(raw), "python", "{}", raw);
+        }
+        assert_eq!(canonical_process_name("ruby-3.2"), "ruby");
+        assert_eq!(canonical_process_name("webpack-dev-server"), "webpack-dev-server");
+        assert_eq!(canonical_process_name("node\\x20(tsx)"), "node");
+    }
+
+    #[test]
+    fn test_group_by_process_name_merges_variants() {
+        let mut mac = DevServer::new(3000, "Next.js".to_string(), "node".to_string(), 10);
+        mac.pids.push(11);
+        let windows = DevServer::new(5173, "Vite".to_string(), "node.exe".to_string(), 20);
+        let wrapped = DevServer::new(4000, "Unknown".to_string(), "node (tsx)".to_string(), 30);
+        let api = DevServer::new(8000, "Django".to_string(), "python3.11".to_string(), 40);
+        assert_eq!(windows.raw_process_name, "node.exe");
+
+        let groups = group_by_process_name(&[mac, windows, wrapped, api]);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].process_name, "node");
+        assert_eq!(groups[0].raw_names, vec!["node", "node (tsx)", "node.exe"]);
+        assert_eq!(groups[0].ports, vec![3000, 4000, 5173]);
+        assert_eq!(groups[0].pids, vec![10, 11, 20, 30]);
+        assert_eq!(groups[1].process_name, "python");
+        assert_eq!(groups[1].raw_names, vec!["python3.11"]);
+    }
+
+    #[test]
+    fn test_fd_limits_warn_when_low() {
+        let low = fd_limits_from(Some(256), Some(1024));
+        assert!(low.low);
+        let hint = low.hint.unwrap();
+        assert!(hint.contains("256"));
+        assert!(hint.contains("hard limit of 1024"));
+
+        let ample = fd_limits_from(Some(FD_LIMIT_RECOMMENDED), None);
+        assert!(!ample.low);
+        assert!(ample.hint.is_none());
+        assert!(!fd_limits_from(None, None).low);
+    }
+
+    #[test]
+    fn test_inspect_flag_port() {
+        assert_eq!(inspect_flag_port("node --inspect server.js"), Some(9229));
+        assert_eq!(inspect_flag_port("node --inspect-brk=9230 server.js"), Some(9230));
+        assert_eq!(inspect_flag_port("node --inspect=0.0.0.0:9331 server.js"), Some(9331));
+        assert_eq!(inspect_flag_port("node --inspect=0 server.js"), None);
+        assert_eq!(inspect_flag_port("node server.js"), None);
+    }
+
+    #[test]
+    fn test_apply_debug_ports_links_inspector_to_its_server() {
+        let app = DevServer::new(3000, "Express/Node".to_string(), "node".to_string(), 10);
+        let inspector = DevServer::new(9229, "Unknown".to_string(), "node".to_string(), 10);
+        let other = DevServer::new(5173, "Vite".to_string(), "node".to_string(), 20);
+        let mut servers = vec![app, inspector, other];
+
+        apply_debug_ports(&mut servers, &HashMap::new());
+        assert_eq!(servers[0].debug_port, Some(9229));
+        assert_eq!(servers[1].service, "Node Inspector");
+        assert_eq!(servers[2].debug_port, None);
+    }
+
+    #[test]
+    fn test_format_memory_boundaries() {
+        assert_eq!(format_memory(0), "0 B");
+        assert_eq!(format_memory(1023), "1023 B");
+        assert_eq!(format_memory(1024), "1.0 KiB");
+        assert_eq!(format_memory(1536), "1.5 KiB");
+        assert_eq!(format_memory(312 * 1024 * 1024), "312 MiB");
+        assert_eq!(format_memory(3 * 1024_u64.pow(4)), "3.0 TiB");
+        assert_eq!(format_memory(u64::MAX), "16384 PiB");
+        assert_eq!(format_cpu(4.24), "4.2%");
+        assert_eq!(format_cpu(0.0), "0.0%");
+    }
+
+    #[test]
+    fn test_in_memory_data_note() {
+        assert!(in_memory_data_note("redis", "redis-server --port 6380 --save \"\"").is_some());
+        assert!(in_memory_data_note("redis", "redis-server --save  --port 6380").is_some());
+        assert!(in_memory_data_note("redis", "redis-server --save \"\" --appendonly yes").is_none());
+        assert!(in_memory_data_note("redis", "redis-server /usr/local/etc/redis.conf").is_none());
+        assert!(in_memory_data_note("mongod", "mongod --storageEngine inMemory --port 27018").is_some());
+        assert!(in_memory_data_note("node", "node node_modules/.bin/mongodb-memory-server").is_some());
+        assert!(in_memory_data_note("sqlite", "sqlite3 :memory:").is_some());
+        assert!(in_memory_data_note("postgres", "postgres -D /dev/shm/pgdata").is_some());
+        assert!(in_memory_data_note("postgres", "postgres -D /var/lib/postgresql/data").is_none());
+        assert!(in_memory_data_note("memcached", "memcached -p 11211").is_some());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_compare_server_sets_reports_differences() {
+        let mut vite = DevServer::new(5173, "Vite".to_string(), "node".to_string(), 10);
+        vite.add_bind_address("*");
+        let api = DevServer::new(8000, "Django".to_string(), "python3".to_string(), 20);
+
+        let mut vite_native = vite.clone();
+        vite_native.pids.push(11);
+
+        let discrepancies = compare_server_sets(&[vite, api], &[vite_native]);
+        let kinds: Vec<(u16, &str)> = discrepancies.iter().map(|d| (d.port, d.kind.as_str())).collect();
+        assert_eq!(kinds, vec![(5173, "pids"), (8000, "missing_from_proc")]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_proc_scan_matches_lsof_for_own_listener() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let own_pid = std::process::id();
+
+        let native = proc_listening_sockets();
+        assert!(native
+            .sockets
+            .iter()
+            .any(|(pid, _, l)| *pid == own_pid && l.port == port && l.host == "127.0.0.1"));
+
+        let Ok(output) = Command::new("lsof")
+            .args(["-a", "-p", &own_pid.to_string(), "-i", "-P", "-n", "-sTCP:LISTEN"])
+            .output()
+        else {
+            return;
+        };
+        let lsof_ports: Vec<String> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .skip(1)
+            .filter_map(|line| line.split_whitespace().nth(8).map(|name| name.to_string()))
+            .collect();
+        if output.status.success() {
+            assert!(lsof_ports.contains(&format!("127.0.0.1:{}", port)));
+        }
+    }
+
+    #[test]
+    fn test_parse_lsof_listeners_merges_dual_stack_rows() {
+        let output = "COMMAND   PID USER   FD   TYPE             DEVICE SIZE/OFF NODE NAME
+node    4242 dev   23u  IPv4 0x1a2b3c4d5e6f7081      0t0  TCP *:3000 (LISTEN)
+node    4242 dev   24u  IPv6 0x1a2b3c4d5e6f7082      0t0  TCP *:3000 (LISTEN)
+";
+        let servers = parse_lsof_listeners(output);
+
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].port, 3000);
+        assert_eq!(servers[0].pids, vec![4242]);
+        assert_eq!(servers[0].bind_addresses, vec!["*".to_string(), "[::]".to_string()]);
+        assert_eq!(servers[0].exposure, "all_interfaces");
+    }
+
+    #[test]
+    fn test_parse_lsof_listeners_does_not_repeat_pid() {
+        // One process holding several listening fds on the same port
+        let output = "COMMAND   PID USER   FD   TYPE             DEVICE SIZE/OFF NODE NAME
+node    5151 dev   21u  IPv4 0x1a2b3c4d5e6f7091      0t0  TCP 127.0.0.1:5173 (LISTEN)
+node    5151 dev   22u  IPv4 0x1a2b3c4d5e6f7092      0t0  TCP 127.0.0.1:5173 (LISTEN)
+node    5151 dev   23u  IPv6 0x1a2b3c4d5e6f7093      0t0  TCP [::1]:5173 (LISTEN)
+node    5152 dev   21u  IPv4 0x1a2b3c4d5e6f7094      0t0  TCP 127.0.0.1:5173 (LISTEN)
+";
+        let servers = parse_lsof_listeners(output);
+
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].pids, vec![5151, 5152]);
+    }
+
+    #[test]
+    fn test_merge_listeners_prefers_most_specific_service() {
+        let generic = DevServer::new(3000, detect_service(3000, "node"), "node".to_string(), 700);
+        let specific = DevServer::new(3000, detect_service(3000, "next-dev"), "next-dev".to_string(), 701);
+
+        for rows in [vec![generic.clone(), specific.clone()], vec![specific, generic]] {
+            let merged = merge_listeners(rows);
+            assert_eq!(merged.len(), 1);
+            assert_eq!(merged[0].service, "Next.js");
+            assert_eq!(merged[0].pid, 701);
+            assert_eq!(merged[0].raw_process_name, "next-dev");
+            assert_eq!(merged[0].pids.len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_is_app_server_matches_process_tree_and_fallback_ports() {
+        let hmr = DevServer::new(APP_HMR_PORT, "Vite".to_string(), "node".to_string(), 900);
+        let other = DevServer::new(5173, "Vite".to_string(), "node".to_string(), 901);
+        let app_pids = HashSet::from([901]);
+
+        // With the Tauri CLI found, only its process tree counts
+        assert!(!is_app_server(&hmr, &app_pids, true));
+        assert!(is_app_server(&other, &app_pids, true));
+
+        // Otherwise debug builds treat the configured dev ports as the app's
+        assert_eq!(is_app_server(&hmr, &HashSet::new(), false), cfg!(debug_assertions));
+        assert!(!is_app_server(&other, &HashSet::new(), false));
+    }
+
+    #[tokio::test]
+    async fn test_check_ports_available_reports_each_port_in_order() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let taken = listener.local_addr().unwrap().port();
+        let free = {
+            let probe = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            probe.local_addr().unwrap().port()
+        };
+
+        let results = check_ports_available(vec![taken, free, 0]).await.unwrap();
+        assert_eq!(results, vec![(taken, false), (free, true), (0, false)]);
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn test_parse_net_tcp_connections() {
+        let json = r#"[{"LocalAddress":"::","LocalPort":5173,"OwningProcess":4100,"Name":"node"},{"LocalAddress":"0.0.0.0","LocalPort":5173,"OwningProcess":4100,"Name":"node"},{"LocalAddress":"0.0.0.0","LocalPort":445,"OwningProcess":4,"Name":"System"}]"#;
+        let servers = parse_net_tcp_connections(json).unwrap();
+
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].port, 5173);
+        assert_eq!(servers[0].pids, vec![4100]);
+        assert_eq!(servers[0].bind_addresses, vec!["[::]".to_string(), "0.0.0.0".to_string()]);
+
+        let single = r#"{"LocalAddress":"127.0.0.1","LocalPort":8000,"OwningProcess":512,"Name":"python"}"#;
+        assert_eq!(parse_net_tcp_connections(single).unwrap()[0].port, 8000);
+        assert!(parse_net_tcp_connections("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_is_sveltekit_project_checks_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_string_lossy().to_string();
+        assert!(!is_sveltekit_project(&root));
+
+        std::fs::write(dir.path().join("svelte.config.js"), "export default {};").unwrap();
+        assert!(is_sveltekit_project(&root));
+    }
+
+    #[test]
+    fn test_is_app_binary_ignores_unrelated_tools_beside_the_executable() {
+        let binaries = AppBinaries {
+            exe_dir: Some(std::path::PathBuf::from("/usr/bin")),
+            sidecar_names: vec!["queen-helper".to_string()],
+            resource_dir: Some(std::path::PathBuf::from("/usr/lib/queen-code")),
+        };
+
+        assert!(!is_app_binary(Path::new("/usr/bin/node"), &binaries));
+        assert!(!is_app_binary(Path::new("/usr/bin/python3"), &binaries));
+        assert!(is_app_binary(Path::new("/usr/bin/queen-helper"), &binaries));
+        assert!(is_app_binary(Path::new("/usr/lib/queen-code/bin/server"), &binaries));
+        assert!(!is_app_binary(Path::new("/opt/tools/queen-helper"), &binaries));
+    }
+
+    #[test]
+    fn test_duplicate_candidates_skip_unclassified_servers() {
+        let server = |port, service: &str, root: &str, classified| {
+            let mut server = DevServer::new(port, service.to_string(), "node".to_string(), port as u32);
+            server.project_root = Some(root.to_string());
+            server.classified = classified;
+            server
+        };
+        let groups = duplicate_candidates(vec![
+            server(5173, "Vite", "/work/web", true),
+            server(5174, "Vite", "/work/web", true),
+            server(5175, "Vite", "/work/docs", true),
+            server(8000, "Dev Server", "/work/web", false),
+            server(8001, "Dev Server", "/work/web", false),
+        ]);
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[&("/work/web".to_string(), "Vite".to_string())].len(), 2);
+        assert!(!groups.keys().any(|(_, service)| service == "Dev Server"));
+    }
+
+    #[test]
+    fn test_server_containing_path_matches_whole_components() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        for sub in ["app", "app-old", "app/packages/web"] {
+            std::fs::create_dir_all(root.join(sub)).unwrap();
+        }
+        let server = |port, sub: &str| {
+            let mut server = DevServer::new(port, "Vite".to_string(), "node".to_string(), port as u32);
+            server.cwd = Some(canonicalize_cwd(&root.join(sub).to_string_lossy()));
+            server
+        };
+        let servers = vec![server(5173, "app"), server(5174, "app/packages/web")];
+        let at = |sub: &str| root.join(sub).to_string_lossy().to_string();
+
+        assert!(server_containing_path(&servers, &at("app-old/src/main.ts")).is_none());
+        assert_eq!(server_containing_path(&servers, &at("app/src/deleted.ts")).map(|s| s.port), Some(5173));
+        assert_eq!(server_containing_path(&servers, &at("app/packages/web/index.html")).map(|s| s.port), Some(5174));
+    }
+}
@@ -0,0 +1,418 @@
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::sync::{OnceLock, RwLock};
+use tauri::State;
+use super::agents::AgentDb;
+
+/// Built-in service signatures. The dataset carries its own version so it can be
+/// refreshed without touching the matching code.
+const BUILTIN_SIGNATURES: &str = include_str!("service_signatures.json");
+
+/// Label used when no signature matches
+pub const DEFAULT_SERVICE: &str = "Development Server";
+
+/// One rule mapping a running server to a service label. Every condition that is
+/// set must match; signatures are tried in order and the first match wins.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceSignature {
+    pub service: String,
+    /// Regex matched case-insensitively against the process name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub process: Option<String>,
+    /// Regex matched case-insensitively against the full command line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Ports or inclusive ranges such as `"3000-3099"`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ports: Vec<String>,
+    /// A response header as `name: value-regex`, only checked when headers were fetched
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureSet {
+    pub version: u32,
+    pub signatures: Vec<ServiceSignature>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceSignatures {
+    pub version: u32,
+    pub builtin: Vec<ServiceSignature>,
+    /// User rules, consulted before the built-in ones
+    pub custom: Vec<ServiceSignature>,
+}
+
+struct CompiledSignature {
+    service: String,
+    process: Option<Regex>,
+    command: Option<Regex>,
+    ports: Vec<(u16, u16)>,
+    header: Option<(String, Regex)>,
+}
+
+fn compile_regex(pattern: &str) -> Result<Regex, String> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .map_err(|e| format!("Invalid pattern '{}': {}", pattern, e))
+}
+
+fn parse_port_range(range: &str) -> Result<(u16, u16), String> {
+    let parse = |value: &str| {
+        value
+            .trim()
+            .parse::<u16>()
+            .map_err(|_| format!("Invalid port '{}'", range))
+    };
+    match range.split_once('-') {
+        Some((start, end)) => Ok((parse(start)?, parse(end)?)),
+        None => parse(range).map(|port| (port, port)),
+    }
+}
+
+fn compile_signature(signature: &ServiceSignature) -> Result<CompiledSignature, String> {
+    let header = match &signature.header {
+        Some(header) => {
+            let (name, value) = header.split_once(':').ok_or_else(|| {
+                format!("Header signature '{}' must look like 'name: pattern'", header)
+            })?;
+            Some((name.trim().to_lowercase(), compile_regex(value.trim())?))
+        }
+        None => None,
+    };
+
+    Ok(CompiledSignature {
+        service: signature.service.clone(),
+        process: signature.process.as_deref().map(compile_regex).transpose()?,
+        command: signature.command.as_deref().map(compile_regex).transpose()?,
+        ports: signature
+            .ports
+            .iter()
+            .map(|p| parse_port_range(p))
+            .collect::<Result<_, _>>()?,
+        header,
+    })
+}
+
+fn compile_signatures(signatures: &[ServiceSignature]) -> Result<Vec<CompiledSignature>, String> {
+    signatures.iter().map(compile_signature).collect()
+}
+
+fn builtin_set() -> SignatureSet {
+    serde_json::from_str(BUILTIN_SIGNATURES).expect("embedded service signatures are valid JSON")
+}
+
+fn builtin_signatures() -> &'static [CompiledSignature] {
+    static BUILTIN: OnceLock<Vec<CompiledSignature>> = OnceLock::new();
+    BUILTIN.get_or_init(|| {
+        compile_signatures(&builtin_set().signatures).expect("embedded service signatures compile")
+    })
+}
+
+static CUSTOM_SIGNATURES: RwLock<Vec<CompiledSignature>> = RwLock::new(Vec::new());
+
+impl CompiledSignature {
+    fn matches(
+        &self,
+        port: u16,
+        process_name: &str,
+        command: Option<&str>,
+        headers: Option<&[(String, String)]>,
+    ) -> bool {
+        if let Some(process) = &self.process {
+            if !process.is_match(process_name) {
+                return false;
+            }
+        }
+        if let Some(pattern) = &self.command {
+            if !command.is_some_and(|c| pattern.is_match(c)) {
+                return false;
+            }
+        }
+        let in_range = |(start, end): &(u16, u16)| (*start..=*end).contains(&port);
+        if !self.ports.is_empty() && !self.ports.iter().any(in_range) {
+            return false;
+        }
+        if let Some((name, value)) = &self.header {
+            let found = headers.is_some_and(|headers| {
+                headers
+                    .iter()
+                    .any(|(key, v)| key.eq_ignore_ascii_case(name) && value.is_match(v))
+            });
+            if !found {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// A service label along with how much evidence backed it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Classification {
+    pub service: String,
+    /// The matching signature looked at the process, command line or headers;
+    /// a port-range rule alone is only a guess
+    pub classified: bool,
+    /// 0.0-1.0, from which of name, command, port and probe agreed on the match
+    pub confidence: f32,
+}
+
+impl CompiledSignature {
+    fn identifies_process(&self) -> bool {
+        self.process.is_some() || self.command.is_some() || self.header.is_some()
+    }
+
+    fn confidence(&self) -> f32 {
+        let mut score = 0.0;
+        if self.command.is_some() {
+            score += 0.5;
+        }
+        if self.header.is_some() {
+            score += 0.4;
+        }
+        if self.process.is_some() {
+            score += 0.3;
+        }
+        if !self.ports.is_empty() {
+            score += 0.2;
+        }
+        f32::min(score, 1.0)
+    }
+}
+
+fn classify_detailed_with(
+    sets: &[&[CompiledSignature]],
+    port: u16,
+    process_name: &str,
+    command: Option<&str>,
+    headers: Option<&[(String, String)]>,
+) -> Classification {
+    let matched = sets
+        .iter()
+        .flat_map(|set| set.iter())
+        .find(|signature| signature.matches(port, process_name, command, headers));
+
+    match matched {
+        Some(signature) => Classification {
+            service: signature.service.clone(),
+            classified: signature.identifies_process(),
+            confidence: signature.confidence(),
+        },
+        None => Classification {
+            service: DEFAULT_SERVICE.to_string(),
+            classified: false,
+            confidence: 0.0,
+        },
+    }
+}
+
+fn classify_with(
+    sets: &[&[CompiledSignature]],
+    port: u16,
+    process_name: &str,
+    command: Option<&str>,
+    headers: Option<&[(String, String)]>,
+) -> String {
+    classify_detailed_with(sets, port, process_name, command, headers).service
+}
+
+/// Labels a server from whatever is known about it; signatures needing a command
+/// line or response headers are skipped when those aren't supplied
+pub fn classify_service(
+    port: u16,
+    process_name: &str,
+    command: Option<&str>,
+    headers: Option<&[(String, String)]>,
+) -> String {
+    let custom = CUSTOM_SIGNATURES.read().unwrap_or_else(|e| e.into_inner());
+    classify_with(&[&custom, builtin_signatures()], port, process_name, command, headers)
+}
+
+/// `classify_service`, also reporting whether the label is a real match
+pub fn classify_service_detailed(
+    port: u16,
+    process_name: &str,
+    command: Option<&str>,
+    headers: Option<&[(String, String)]>,
+) -> Classification {
+    let custom = CUSTOM_SIGNATURES.read().unwrap_or_else(|e| e.into_inner());
+    classify_detailed_with(&[&custom, builtin_signatures()], port, process_name, command, headers)
+}
+
+/// Position of the signature that labels this process in match order. Signatures
+/// are listed most specific first, so a lower rank is the better label.
+pub fn classification_rank(port: u16, process_name: &str) -> usize {
+    let custom = CUSTOM_SIGNATURES.read().unwrap_or_else(|e| e.into_inner());
+    custom
+        .iter()
+        .chain(builtin_signatures())
+        .position(|signature| signature.matches(port, process_name, None, None))
+        .unwrap_or(usize::MAX)
+}
+
+fn read_custom_signatures(db: &AgentDb) -> Result<Vec<ServiceSignature>, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let json = conn
+        .query_row(
+            "SELECT value FROM app_settings WHERE key = 'custom_service_signatures'",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .ok()
+        .filter(|v| !v.is_empty());
+
+    match json {
+        Some(json) => serde_json::from_str(&json)
+            .map_err(|e| format!("Saved service signatures are corrupt: {}", e)),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Installs the user's saved signatures; called once at startup
+pub fn load_custom_signatures(db: &AgentDb) -> Result<(), String> {
+    let compiled = compile_signatures(&read_custom_signatures(db)?)?;
+    *CUSTOM_SIGNATURES.write().map_err(|e| e.to_string())? = compiled;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_service_signatures(db: State<'_, AgentDb>) -> Result<ServiceSignatures, String> {
+    let builtin = builtin_set();
+    Ok(ServiceSignatures {
+        version: builtin.version,
+        builtin: builtin.signatures,
+        custom: read_custom_signatures(&db)?,
+    })
+}
+
+/// Replaces the user's signatures, which take precedence over the built-in set
+#[tauri::command]
+pub fn set_custom_service_signatures(
+    signatures: Vec<ServiceSignature>,
+    db: State<'_, AgentDb>,
+) -> Result<(), String> {
+    if let Some(empty) = signatures.iter().find(|s| s.service.trim().is_empty()) {
+        return Err(format!("Signature {:?} has no service label", empty));
+    }
+    let compiled = compile_signatures(&signatures)?;
+
+    let json = serde_json::to_string(&signatures).map_err(|e| e.to_string())?;
+    {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        conn.execute(
+            "INSERT OR REPLACE INTO app_settings (key, value) VALUES (?1, ?2)",
+            rusqlite::params!["custom_service_signatures", json],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    *CUSTOM_SIGNATURES.write().map_err(|e| e.to_string())? = compiled;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classify(process_name: &str, command: &str, port: u16) -> String {
+        classify_with(&[builtin_signatures()], port, process_name, Some(command), None)
+    }
+
+    #[test]
+    fn test_builtin_dataset_compiles() {
+        let set = builtin_set();
+        assert!(set.version >= 1);
+        assert_eq!(builtin_signatures().len(), set.signatures.len());
+    }
+
+    #[test]
+    fn test_classifies_representative_command_lines() {
+        let cases = [
+            ("node", "node /app/node_modules/.bin/vite --port 5180", 5180, "Vite"),
+            ("node", "node /app/node_modules/.bin/next dev", 3000, "Next.js"),
+            ("next-server", "next-server (v14.1.0)", 3000, "Next.js"),
+            ("node", "node node_modules/.bin/webpack serve --mode development", 8080, "Webpack Dev"),
+            ("node", "node node_modules/.bin/storybook dev -p 6007", 6007, "Storybook"),
+            ("node", "node /site/node_modules/.bin/docusaurus start --port 3000", 3000, "Docusaurus"),
+            ("node", "node /app/node_modules/.bin/astro dev", 4322, "Astro"),
+            ("node", "node /app/node_modules/.bin/svelte-kit dev", 5173, "SvelteKit"),
+            ("node", "node /app/node_modules/.bin/remix vite:dev", 5173, "Remix"),
+            ("node", "node /app/node_modules/nuxi/bin/nuxi.mjs dev", 3000, "Nuxt"),
+            ("node", "node node_modules/.bin/tauri dev", 1421, "Tauri Dev"),
+            ("python3", "python3 manage.py runserver 0.0.0.0:8000", 8000, "Django/Python"),
+            ("python", "python -m flask run --port 5001", 5001, "Flask/Python"),
+            ("python3", "/venv/bin/python3 /venv/bin/uvicorn app.main:app --reload", 8000, "FastAPI"),
+            ("python3", "/usr/bin/python3 /usr/bin/jupyter-lab --no-browser", 8890, "Jupyter"),
+            ("ruby", "puma 6.4.0 (tcp://localhost:3000) [app]", 3000, "Rails"),
+            ("nginx", "nginx: master process nginx -g daemon off;", 80, "Nginx"),
+            ("postgres", "/usr/lib/postgresql/16/bin/postgres -D /var/lib/postgresql", 5432, "PostgreSQL"),
+            ("com.docke", "/Applications/Docker.app/Contents/MacOS/com.docker.backend", 5432, "Docker"),
+            ("node", "node server.js", 3000, "React/Next.js"),
+            ("bun", "bun run server.ts", 3001, "Bun Server"),
+            ("node", "node server.js", 4000, "Express/Node"),
+            ("node", "node index.js", 12345, DEFAULT_SERVICE),
+        ];
+
+        for (process_name, command, port, expected) in cases {
+            assert_eq!(classify(process_name, command, port), expected, "{}", command);
+        }
+    }
+
+    #[test]
+    fn test_port_range_matches_are_unclassified() {
+        let detailed = |port, name| classify_detailed_with(&[builtin_signatures()], port, name, None, None);
+
+        for (port, service) in [(4000, "Express/Node"), (9000, "Go/Dev Server"), (1420, "Tauri Dev"), (6006, "Storybook")] {
+            let classification = detailed(port, "mystery-daemon");
+            assert_eq!(classification.service, service);
+            assert!(!classification.classified, "{} on {}", service, port);
+        }
+        assert!(detailed(5173, "vite").classified);
+        assert!(detailed(3000, "node").classified);
+    }
+
+    #[test]
+    fn test_name_only_classification_matches_port_heuristics() {
+        let classify = |port, name| classify_with(&[builtin_signatures()], port, name, None, None);
+        assert_eq!(classify(3000, "node"), "React/Next.js");
+        assert_eq!(classify(5173, "node"), "Vite");
+        assert_eq!(classify(8000, "python3"), "Django/Python");
+        assert_eq!(classify(8000, "ruby"), "Dev Server");
+        assert_eq!(classify(0, "redis-server"), "Redis");
+    }
+
+    #[test]
+    fn test_headers_and_custom_signatures() {
+        let headers = vec![("X-Powered-By".to_string(), "Express".to_string())];
+        assert_eq!(
+            classify_with(&[builtin_signatures()], 3500, "node", Some("node app.js"), Some(&headers)),
+            "Express/Node"
+        );
+
+        let custom = compile_signatures(&[ServiceSignature {
+            service: "Remix".to_string(),
+            process: None,
+            command: Some(r"\bremix\s+dev\b".to_string()),
+            ports: Vec::new(),
+            header: None,
+        }])
+        .unwrap();
+        let command = Some("node node_modules/.bin/remix dev");
+        assert_eq!(
+            classify_with(&[&custom, builtin_signatures()], 3000, "node", command, None),
+            "Remix"
+        );
+
+        assert!(compile_signatures(&[ServiceSignature {
+            service: "Broken".to_string(),
+            process: Some("(".to_string()),
+            command: None,
+            ports: vec!["70000".to_string()],
+            header: None,
+        }])
+        .is_err());
+    }
+}
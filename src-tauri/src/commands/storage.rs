@@ -449,6 +449,20 @@ pub async fn storage_reset_database(app: AppHandle) -> Result<(), String> {
             .map_err(|e| format!("Failed to drop agents table: {}", e))?;
         conn.execute("DROP TABLE IF EXISTS app_settings", [])
             .map_err(|e| format!("Failed to drop app_settings table: {}", e))?;
+        conn.execute("DROP TABLE IF EXISTS project_labels", [])
+            .map_err(|e| format!("Failed to drop project_labels table: {}", e))?;
+        conn.execute("DROP TABLE IF EXISTS projects", [])
+            .map_err(|e| format!("Failed to drop projects table: {}", e))?;
+        conn.execute("DROP TABLE IF EXISTS dev_sessions", [])
+            .map_err(|e| format!("Failed to drop dev_sessions table: {}", e))?;
+        conn.execute("DROP TABLE IF EXISTS install_history", [])
+            .map_err(|e| format!("Failed to drop install_history table: {}", e))?;
+        conn.execute("DROP TABLE IF EXISTS scan_snapshots", [])
+            .map_err(|e| format!("Failed to drop scan_snapshots table: {}", e))?;
+        conn.execute("DROP TABLE IF EXISTS server_prefs", [])
+            .map_err(|e| format!("Failed to drop server_prefs table: {}", e))?;
+        conn.execute("DROP TABLE IF EXISTS server_events", [])
+            .map_err(|e| format!("Failed to drop server_events table: {}", e))?;
         
         // Re-enable foreign key constraints
         conn.execute("PRAGMA foreign_keys = ON", [])
@@ -480,6 +494,257 @@ pub async fn storage_reset_database(app: AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+/// Result of `PRAGMA integrity_check`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DatabaseIntegrity {
+    pub ok: bool,
+    pub errors: Vec<String>,
+}
+
+/// Rows salvaged from one table during a repair
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TableRecovery {
+    pub table: String,
+    pub rows_read: usize,
+    pub rows_restored: usize,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DatabaseRepairReport {
+    pub backup_path: String,
+    pub tables: Vec<TableRecovery>,
+}
+
+/// Check the database for corruption
+#[tauri::command]
+pub async fn check_database_integrity(db: State<'_, AgentDb>) -> Result<DatabaseIntegrity, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let mut stmt = conn
+        .prepare("PRAGMA integrity_check")
+        .map_err(|e| format!("Failed to run integrity check: {}", e))?;
+    let messages = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| format!("Failed to run integrity check: {}", e))?
+        .collect::<SqliteResult<Vec<_>>>()
+        .map_err(|e| format!("Failed to read integrity check: {}", e))?;
+
+    let ok = messages.len() == 1 && messages[0] == "ok";
+    Ok(DatabaseIntegrity {
+        ok,
+        errors: if ok { Vec::new() } else { messages },
+    })
+}
+
+/// Columns and rows read from a table before the error that stopped the read, if any
+struct SalvagedTable {
+    name: String,
+    columns: Vec<String>,
+    rows: Vec<Vec<rusqlite::types::Value>>,
+    error: Option<String>,
+}
+
+fn salvage_table(conn: &Connection, table: &str) -> SalvagedTable {
+    let mut salvaged = SalvagedTable {
+        name: table.to_string(),
+        columns: Vec::new(),
+        rows: Vec::new(),
+        error: None,
+    };
+
+    let mut stmt = match conn.prepare(&format!("SELECT * FROM \"{}\"", table)) {
+        Ok(stmt) => stmt,
+        Err(e) => {
+            salvaged.error = Some(e.to_string());
+            return salvaged;
+        }
+    };
+    salvaged.columns = stmt.column_names().iter().map(|c| c.to_string()).collect();
+    let column_count = salvaged.columns.len();
+
+    let mut rows = match stmt.query([]) {
+        Ok(rows) => rows,
+        Err(e) => {
+            salvaged.error = Some(e.to_string());
+            return salvaged;
+        }
+    };
+    // Keep everything read before a corrupt page stops the scan
+    loop {
+        match rows.next() {
+            Ok(Some(row)) => {
+                let values = (0..column_count)
+                    .map(|i| row.get::<_, rusqlite::types::Value>(i))
+                    .collect::<SqliteResult<Vec<_>>>();
+                match values {
+                    Ok(values) => salvaged.rows.push(values),
+                    Err(e) => {
+                        salvaged.error = Some(e.to_string());
+                        break;
+                    }
+                }
+            }
+            Ok(None) => break,
+            Err(e) => {
+                salvaged.error = Some(e.to_string());
+                break;
+            }
+        }
+    }
+
+    salvaged
+}
+
+fn table_columns(conn: &Connection, table: &str) -> Vec<String> {
+    conn.prepare(&format!("PRAGMA table_info(\"{}\")", table))
+        .and_then(|mut stmt| {
+            stmt.query_map([], |row| row.get::<_, String>(1))?
+                .collect::<SqliteResult<Vec<_>>>()
+        })
+        .unwrap_or_default()
+}
+
+fn restore_table(conn: &Connection, salvaged: &SalvagedTable) -> usize {
+    let existing = table_columns(conn, &salvaged.name);
+    // Only columns the fresh schema still has
+    let shared: Vec<usize> = salvaged
+        .columns
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| existing.contains(c))
+        .map(|(i, _)| i)
+        .collect();
+    if shared.is_empty() {
+        return 0;
+    }
+
+    let sql = format!(
+        "INSERT OR REPLACE INTO \"{}\" ({}) VALUES ({})",
+        salvaged.name,
+        shared
+            .iter()
+            .map(|&i| format!("\"{}\"", salvaged.columns[i]))
+            .collect::<Vec<_>>()
+            .join(", "),
+        vec!["?"; shared.len()].join(", ")
+    );
+    let Ok(mut stmt) = conn.prepare(&sql) else {
+        return 0;
+    };
+
+    salvaged
+        .rows
+        .iter()
+        .filter(|row| {
+            stmt.execute(rusqlite::params_from_iter(shared.iter().map(|&i| &row[i])))
+                .is_ok()
+        })
+        .count()
+}
+
+fn remove_database_files(app_dir: &std::path::Path) -> Result<(), String> {
+    for suffix in ["", "-wal", "-shm", "-journal"] {
+        let path = app_dir.join(format!("agents.db{}", suffix));
+        if path.exists() {
+            std::fs::remove_file(&path)
+                .map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Back up a corrupt database, rebuild it from scratch and reload whatever rows
+/// can still be read from the old file
+#[tauri::command]
+pub async fn repair_database(app: AppHandle) -> Result<DatabaseRepairReport, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    let db_path = app_dir.join("agents.db");
+    let backup_path = app_dir.join(format!(
+        "agents.db.{}.bak",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+
+    let db_state = app.state::<AgentDb>();
+    let mut conn_guard = db_state.0.lock().map_err(|e| e.to_string())?;
+
+    // Read everything we can before touching the file
+    let tables: Vec<String> = conn_guard
+        .prepare("SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'")
+        .and_then(|mut stmt| {
+            stmt.query_map([], |row| row.get::<_, String>(0))?
+                .collect::<SqliteResult<Vec<_>>>()
+        })
+        .map_err(|e| format!("Failed to read the database schema: {}", e))?;
+    let salvaged: Vec<SalvagedTable> = tables.iter().map(|t| salvage_table(&conn_guard, t)).collect();
+
+    // Back up while the live connection still works, so a failure here changes nothing
+    let _ = conn_guard.execute_batch("PRAGMA wal_checkpoint(TRUNCATE)");
+    std::fs::copy(&db_path, &backup_path)
+        .map_err(|e| format!("Failed to back up database: {}", e))?;
+
+    // Release the file before moving it aside
+    let placeholder = Connection::open_in_memory().map_err(|e| e.to_string())?;
+    drop(std::mem::replace(&mut *conn_guard, placeholder));
+
+    let rebuilt = remove_database_files(&app_dir).and_then(|_| {
+        let new_conn = init_database(&app).map_err(|e| format!("Failed to rebuild database: {}", e))?;
+        new_conn
+            .execute("PRAGMA foreign_keys = OFF", [])
+            .map_err(|e| format!("Failed to disable foreign keys: {}", e))?;
+
+        let recovery: Vec<TableRecovery> = salvaged
+            .iter()
+            .map(|table| TableRecovery {
+                table: table.name.clone(),
+                rows_read: table.rows.len(),
+                rows_restored: restore_table(&new_conn, table),
+                error: table.error.clone(),
+            })
+            .collect();
+
+        new_conn
+            .execute("PRAGMA foreign_keys = ON", [])
+            .map_err(|e| format!("Failed to re-enable foreign keys: {}", e))?;
+        Ok((new_conn, recovery))
+    });
+
+    let recovery = match rebuilt {
+        Ok((new_conn, recovery)) => {
+            *conn_guard = new_conn;
+            recovery
+        }
+        Err(e) => {
+            // Never leave the app running on the in-memory placeholder
+            let restored = remove_database_files(&app_dir)
+                .and_then(|_| {
+                    std::fs::copy(&backup_path, &db_path)
+                        .map_err(|e| format!("Failed to restore the backup: {}", e))
+                })
+                .and_then(|_| Connection::open(&db_path).map_err(|e| e.to_string()));
+            return match restored {
+                Ok(original) => {
+                    *conn_guard = original;
+                    Err(format!("{}; the original database was put back", e))
+                }
+                Err(restore_error) => Err(format!(
+                    "{}; restoring the original also failed ({}), restart the app to reload it from {}",
+                    e,
+                    restore_error,
+                    backup_path.display()
+                )),
+            };
+        }
+    };
+
+    Ok(DatabaseRepairReport {
+        backup_path: backup_path.to_string_lossy().to_string(),
+        tables: recovery,
+    })
+}
+
 /// Helper function to validate table name exists
 fn is_valid_table_name(conn: &Connection, table_name: &str) -> Result<bool, String> {
     let count: i64 = conn
@@ -38,15 +38,57 @@ use commands::mcp::{
 use commands::usage::{
     get_session_stats, get_usage_by_date_range, get_usage_details, get_usage_stats,
 };
+use commands::signatures::{get_service_signatures, set_custom_service_signatures};
 use commands::storage::{
     storage_list_tables, storage_read_table, storage_update_row, storage_delete_row,
     storage_insert_row, storage_execute_sql, storage_reset_database,
+    check_database_integrity, repair_database,
 };
 use commands::proxy::{get_proxy_settings, save_proxy_settings, apply_proxy_settings};
-use commands::servers::{scan_dev_servers, kill_dev_server};
+use commands::backend_log::{get_backend_log_level, set_backend_log_level, get_backend_logs};
+use commands::servers::{
+    scan_dev_servers, kill_dev_server, confirmed_kill, get_require_kill_confirmation,
+    set_require_kill_confirmation, get_kill_managed_on_exit, set_kill_managed_on_exit, check_kill_dependents, send_signal_to_server, launch_dev_server, start_project_dev_server, list_managed_servers,
+    enable_autorestart, disable_autorestart, tail_project_logs, stop_project_log_tail,
+    label_project_servers, remove_project_label, scan_package_manager_processes,
+    security_audit, scan_as_tree, scan_grouped_by_pid, scan_grouped_by_process_name, get_devtools_url, get_app_self_ports, get_app_dev_ports, diagnose_port_access, explain_port_unavailable, check_ports_available,
+    set_process_priority, detect_duplicate_servers, find_stale_servers, scan_queen_project_servers, import_running_as_projects, get_reproduce_command, compare_scan_methods, get_scan_backend, set_scan_backend_override,
+    scan_dev_servers_native, scan_dev_servers_versioned, get_scan_schema_version,
+    get_available_browsers, get_default_browser, set_default_browser, open_dev_server_in_browser, get_server_url,
+    open_project_servers_in_browser,
+    get_terminal_preference, set_terminal_preference, open_terminal, open_server_terminal,
+    watch_project_files, unwatch_project_files, FileWatchState, start_resource_sampling,
+    stop_resource_sampling, get_resource_samples, ResourceSamplingState,
+    start_server_watch, stop_server_watch, get_scan_interval, ServerWatchState,
+    get_server_uptime_history,
+    schedule_kill_all, schedule_kill_idle, list_scheduled_kills, cancel_scheduled_kill,
+    ScheduledKillState, get_protected_ports, set_protected_ports, save_dev_session,
+    list_dev_sessions, delete_dev_session, export_dev_session_script,
+    save_scan_snapshot, list_scan_snapshots, delete_scan_snapshot, get_server_prefs, set_server_pref, diff_snapshots, check_server_health, identify_service, scan_unclassified, check_inotify_limits,
+    check_privileged_port_capability, check_fd_limits, check_environment_readiness, check_macos_permissions,
+    set_server_health_path, find_file_lock_holders, reserve_port, release_port,
+    list_port_reservations, PortReservationState, suggest_kill_strategy, get_process_ancestry,
+    KillConfirmationState, ManagedServersState,
+};
 use commands::queen::{
-    check_queen_cli_status, install_queen_cli, get_queen_templates, create_queen_project,
-    get_queen_projects_directory, set_queen_projects_directory,
+    check_queen_cli_status, install_queen_cli, get_queen_templates, create_queen_project, reinit_queen_project, check_reinit_git_status,
+    validate_templates_against_cli,
+    check_npm_name_available, get_offline_mode, set_offline_mode, get_queen_cli_release_notes,
+    open_queen_cli_changelog, set_queen_cli_changelog_url, get_create_log, open_create_log,
+    get_queen_projects_directory, suggest_projects_directory, scan_projects_directory,
+    dismiss_projects_directory_suggestion, set_queen_projects_directory, check_projects_directory_sync_risk,
+    verify_queen_cli_detailed,
+    cancel_queen_cli_verification, QueenCliVerifyState, check_npm_global_path, fix_npm_global_path,
+    list_projects_directories, add_projects_directory, set_active_projects_directory,
+    find_command_shadows, get_node_version_managers, set_node_version_manager, get_install_history,
+    check_path_length, get_dashboard, QueenCliStatusCache, create_queen_projects,
+    get_create_concurrency, set_create_concurrency, check_cli_symlink_integrity, detect_multiple_cli_installs,
+    create_support_bundle,
+};
+use commands::projects::{
+    get_project_dev_port, list_tracked_projects, add_tracked_project, remove_tracked_project,
+    detect_project_port_conflicts, compute_project_cruft, clean_project_cruft,
+    get_project_compose_ports, check_node_version_match, detect_lockfile_conflicts,
 };
 use process::ProcessRegistryState;
 use std::sync::Mutex;
@@ -58,7 +100,7 @@ use window_vibrancy::{apply_vibrancy, NSVisualEffectMaterial};
 
 fn main() {
     // Initialize logger
-    env_logger::init();
+    commands::backend_log::init();
 
 
     tauri::Builder::default()
@@ -117,6 +159,15 @@ fn main() {
             // Re-open the connection for the app to manage
             let conn = init_database(&app.handle()).expect("Failed to initialize agents database");
             app.manage(AgentDb(Mutex::new(conn)));
+            if let Err(e) = commands::signatures::load_custom_signatures(&app.state::<AgentDb>()) {
+                log::warn!("Failed to load custom service signatures: {}", e);
+            }
+            if let Err(e) = commands::servers::load_scan_backend(&app.state::<AgentDb>()) {
+                log::warn!("Failed to load scan backend preference: {}", e);
+            }
+            if let Err(e) = commands::backend_log::load_backend_log_level(&app.state::<AgentDb>()) {
+                log::warn!("Failed to load backend log level: {}", e);
+            }
 
             // Initialize checkpoint state
             let checkpoint_state = CheckpointState::new();
@@ -145,6 +196,19 @@ fn main() {
             // Initialize Claude process state
             app.manage(ClaudeProcessState::default());
 
+            // Initialize managed dev server state
+            app.manage(ManagedServersState::default());
+            app.manage(KillConfirmationState::default());
+            app.manage(FileWatchState::default());
+            app.manage(ResourceSamplingState::default());
+            app.manage(ScheduledKillState::default());
+            app.manage(ServerWatchState::default());
+            app.manage(PortReservationState::default());
+
+            // Initialize Queen CLI verification state
+            app.manage(QueenCliVerifyState::default());
+            app.manage(QueenCliStatusCache::default());
+
             // Apply window vibrancy with rounded corners on macOS
             #[cfg(target_os = "macos")]
             {
@@ -279,6 +343,8 @@ fn main() {
             storage_insert_row,
             storage_execute_sql,
             storage_reset_database,
+            check_database_integrity,
+            repair_database,
             
             // Slash Commands
             commands::slash_commands::slash_commands_list,
@@ -290,18 +356,165 @@ fn main() {
             get_proxy_settings,
             save_proxy_settings,
 
+            // Backend Logging
+            get_backend_log_level,
+            set_backend_log_level,
+            get_backend_logs,
+
             // Server Management
             scan_dev_servers,
             kill_dev_server,
+            confirmed_kill,
+            get_require_kill_confirmation,
+            set_require_kill_confirmation,
+            get_kill_managed_on_exit,
+            set_kill_managed_on_exit,
+            check_kill_dependents,
+            send_signal_to_server,
+            label_project_servers,
+            remove_project_label,
+            scan_package_manager_processes,
+            security_audit,
+            scan_as_tree,
+            scan_grouped_by_pid,
+            scan_grouped_by_process_name,
+            get_devtools_url,
+            get_app_self_ports,
+            get_app_dev_ports,
+            diagnose_port_access,
+            explain_port_unavailable,
+            check_ports_available,
+            set_process_priority,
+            detect_duplicate_servers,
+            find_stale_servers,
+            scan_queen_project_servers,
+            import_running_as_projects,
+            get_reproduce_command,
+            compare_scan_methods,
+            get_scan_backend,
+            set_scan_backend_override,
+            scan_dev_servers_native,
+            scan_dev_servers_versioned,
+            get_scan_schema_version,
+            get_available_browsers,
+            get_default_browser,
+            set_default_browser,
+            open_dev_server_in_browser,
+            open_project_servers_in_browser,
+            get_server_url,
+            get_terminal_preference,
+            set_terminal_preference,
+            open_terminal,
+            open_server_terminal,
+            watch_project_files,
+            unwatch_project_files,
+            start_resource_sampling,
+            stop_resource_sampling,
+            get_resource_samples,
+            start_server_watch,
+            stop_server_watch,
+            get_scan_interval,
+            get_server_uptime_history,
+            schedule_kill_all,
+            schedule_kill_idle,
+            list_scheduled_kills,
+            cancel_scheduled_kill,
+            get_protected_ports,
+            set_protected_ports,
+            save_dev_session,
+            list_dev_sessions,
+            delete_dev_session,
+            export_dev_session_script,
+            save_scan_snapshot,
+            list_scan_snapshots,
+            delete_scan_snapshot,
+            get_server_prefs,
+            set_server_pref,
+            diff_snapshots,
+            check_server_health,
+            identify_service,
+            scan_unclassified,
+            check_inotify_limits,
+            check_privileged_port_capability,
+            check_fd_limits,
+            check_environment_readiness,
+            check_macos_permissions,
+            get_service_signatures,
+            set_custom_service_signatures,
+            set_server_health_path,
+            find_file_lock_holders,
+            reserve_port,
+            release_port,
+            list_port_reservations,
+            suggest_kill_strategy,
+            get_process_ancestry,
+            launch_dev_server,
+            start_project_dev_server,
+            list_managed_servers,
+            enable_autorestart,
+            disable_autorestart,
+            tail_project_logs,
+            stop_project_log_tail,
 
             // Queen Project Management
             check_queen_cli_status,
             install_queen_cli,
             get_queen_templates,
+            validate_templates_against_cli,
             create_queen_project,
+            get_create_log,
+            open_create_log,
+            reinit_queen_project,
+            check_reinit_git_status,
+            check_npm_name_available,
+            get_queen_cli_release_notes,
+            open_queen_cli_changelog,
+            set_queen_cli_changelog_url,
+            get_offline_mode,
+            set_offline_mode,
             get_queen_projects_directory,
+            scan_projects_directory,
+            suggest_projects_directory,
+            dismiss_projects_directory_suggestion,
             set_queen_projects_directory,
+            check_projects_directory_sync_risk,
+            list_projects_directories,
+            add_projects_directory,
+            set_active_projects_directory,
+            verify_queen_cli_detailed,
+            cancel_queen_cli_verification,
+            check_npm_global_path,
+            fix_npm_global_path,
+            find_command_shadows,
+            get_node_version_managers,
+            set_node_version_manager,
+            get_install_history,
+            check_path_length,
+            get_dashboard,
+            create_queen_projects,
+            get_create_concurrency,
+            set_create_concurrency,
+            check_cli_symlink_integrity,
+            detect_multiple_cli_installs,
+            create_support_bundle,
+
+            // Project Tools
+            get_project_dev_port,
+            list_tracked_projects,
+            add_tracked_project,
+            remove_tracked_project,
+            detect_project_port_conflicts,
+            compute_project_cruft,
+            clean_project_cruft,
+            get_project_compose_ports,
+            check_node_version_match,
+            detect_lockfile_conflicts,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                commands::servers::stop_managed_servers_on_exit(app);
+            }
+        });
 }
@@ -42,6 +42,7 @@ export const QueenProjectCreator: React.FC<QueenProjectCreatorProps> = ({
   const [installing, setInstalling] = useState(false);
   const [creating, setCreating] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  const [createdWithWarning, setCreatedWithWarning] = useState<{ path: string; warning: string } | null>(null);
   const [nameError, setNameError] = useState<string | null>(null);
 
   useEffect(() => {
@@ -136,13 +137,17 @@ export const QueenProjectCreator: React.FC<QueenProjectCreatorProps> = ({
     setStep("creating");
 
     try {
-      const projectPath = await invoke<string>("create_queen_project", {
+      const created = await invoke<{ path: string; warning: string | null }>("create_queen_project", {
         template: selectedTemplate,
         projectName,
         parentDirectory: projectsDirectory,
       });
 
-      onProjectCreated(projectPath);
+      if (created.warning) {
+        setCreatedWithWarning({ path: created.path, warning: created.warning });
+        return;
+      }
+      onProjectCreated(created.path);
     } catch (err) {
       console.error("Failed to create project:", err);
       setError(err as string || "Failed to create project");
@@ -182,12 +187,25 @@ export const QueenProjectCreator: React.FC<QueenProjectCreatorProps> = ({
           <motion.div
             initial={{ opacity: 0, y: -10 }}
             animate={{ opacity: 1, y: 0 }}
-            className="mb-6 rounded-lg border border-destructive/50 bg-destructive/10 p-4 text-sm text-destructive"
+            className="mb-6 whitespace-pre-wrap rounded-lg border border-destructive/50 bg-destructive/10 p-4 text-sm text-destructive"
           >
             {error}
           </motion.div>
         )}
 
+        {createdWithWarning && (
+          <motion.div
+            initial={{ opacity: 0, y: -10 }}
+            animate={{ opacity: 1, y: 0 }}
+            className="mb-6 rounded-lg border border-amber-500/50 bg-amber-500/10 p-4 text-sm"
+          >
+            <p className="mb-3">{createdWithWarning.warning}</p>
+            <Button size="sm" onClick={() => onProjectCreated(createdWithWarning.path)}>
+              Open Project
+            </Button>
+          </motion.div>
+        )}
+
         {!isCliInstalled && (
           <Card className="p-6 mb-6 border-amber-500/50 bg-amber-500/10">
             <div className="flex items-start gap-4">
@@ -370,7 +388,7 @@ export const QueenProjectCreator: React.FC<QueenProjectCreatorProps> = ({
           </div>
         )}
 
-        {step === "creating" && (
+        {step === "creating" && !createdWithWarning && (
           <Card className="p-12">
             <div className="flex flex-col items-center justify-center text-center">
               <Loader2 className="h-12 w-12 animate-spin text-primary mb-4" />
@@ -5,7 +5,8 @@ import {
   Globe,
   X,
   CircleNotch,
-  CheckCircle
+  CheckCircle,
+  TreeStructure
 } from "@phosphor-icons/react";
 import { Button } from "@/components/ui/button";
 import { Card } from "@/components/ui/card";
@@ -20,6 +21,28 @@ interface ServerInfo {
   processName: string;
   pid: number;
   pids: number[];
+  url?: string;
+  localhost_hint?: string | null;
+}
+
+type KillOutcome =
+  | {
+      status: 'killed';
+      pids: number[];
+      defunct: { pid: number; parent_pid: number; parent_name: string | null; note: string }[];
+    }
+  | {
+      status: 'confirmation_required';
+      token: string;
+      targets: { pid: number; process_name: string | null }[];
+      expires_in_secs: number;
+    };
+
+interface ProcessAncestry {
+  pid: number;
+  chain: { pid: number; name: string; command: string; role: string | null }[];
+  launched_by: string | null;
+  truncated: boolean;
 }
 
 interface ServerDashboardProps {
@@ -37,6 +60,7 @@ export const ServerDashboard: React.FC<ServerDashboardProps> = ({
   const [loading, setLoading] = useState(true);
   const [scanning, setScanning] = useState(false);
   const [toast, setToast] = useState<{ message: string; type: 'success' | 'error' } | null>(null);
+  const [details, setDetails] = useState<{ port: number; ancestry: ProcessAncestry } | null>(null);
 
   const scanPorts = async () => {
     setScanning(true);
@@ -55,33 +79,69 @@ export const ServerDashboard: React.FC<ServerDashboardProps> = ({
   };
 
   const openServer = async (server: ServerInfo) => {
-    const url = `http://localhost:${server.port}`;
+    const url = server.url ?? `http://localhost:${server.port}`;
 
     try {
-      const newWindow = window.open(url, '_blank');
-      if (newWindow) {
-        setToast({ message: `Opened ${server.service} in browser`, type: 'success' });
-      } else {
-        await navigator.clipboard.writeText(url);
-        setToast({ message: `URL copied: ${url}`, type: 'error' });
-      }
+      await invoke('open_dev_server_in_browser', { port: server.port });
+      setToast({ message: `Opened ${server.service} in browser`, type: 'success' });
     } catch (error) {
       console.error('Failed to open server:', error);
-      setToast({ message: 'Failed to open server', type: 'error' });
+      await navigator.clipboard.writeText(url);
+      setToast({ message: `URL copied: ${url}`, type: 'error' });
     }
   };
 
   const killServer = async (server: ServerInfo) => {
+    const sharedPorts = servers
+      .filter(s => s.port !== server.port && s.pids.some(pid => server.pids.includes(pid)))
+      .map(s => s.port);
+    if (
+      sharedPorts.length > 0 &&
+      !window.confirm(`Killing PID ${server.pid} will also stop ports ${sharedPorts.join(', ')}. Continue?`)
+    ) {
+      return;
+    }
+
     try {
-      await invoke('kill_dev_server', { pids: server.pids });
-      setServers(prev => prev.filter(s => s.port !== server.port));
-      setToast({ message: `Killed ${server.service} on port ${server.port}`, type: 'success' });
+      let outcome = await invoke<KillOutcome>('kill_dev_server', { pids: server.pids });
+      if (outcome.status === 'confirmation_required') {
+        const names = outcome.targets
+          .map(t => `${t.process_name ?? 'unknown'} (PID ${t.pid})`)
+          .join(', ');
+        if (!window.confirm(`Kill ${server.service} on port ${server.port}?\n${names}`)) {
+          return;
+        }
+        outcome = await invoke<KillOutcome>('confirmed_kill', { token: outcome.token });
+      }
+      setServers(prev => prev.filter(s => s.port !== server.port && !sharedPorts.includes(s.port)));
+      const defunct = outcome.status === 'killed' ? outcome.defunct : [];
+      setToast({
+        message: defunct.length > 0
+          ? `Killed ${server.service} on port ${server.port}. ${defunct.map(d => d.note).join(' ')}`
+          : `Killed ${server.service} on port ${server.port}`,
+        type: 'success'
+      });
     } catch (error) {
       console.error('Failed to kill server:', error);
       setToast({ message: 'Failed to kill server', type: 'error' });
     }
   };
 
+  const toggleDetails = async (server: ServerInfo) => {
+    if (details?.port === server.port) {
+      setDetails(null);
+      return;
+    }
+
+    try {
+      const ancestry = await invoke<ProcessAncestry>('get_process_ancestry', { pid: server.pid });
+      setDetails({ port: server.port, ancestry });
+    } catch (error) {
+      console.error('Failed to load process ancestry:', error);
+      setToast({ message: 'Failed to load process details', type: 'error' });
+    }
+  };
+
   // Scan ports on component mount
   useEffect(() => {
     console.log('[ServerDashboard] Component mounted, scanning ports...');
@@ -157,8 +217,9 @@ export const ServerDashboard: React.FC<ServerDashboardProps> = ({
                         animate={{ opacity: 1, x: 0 }}
                         exit={{ opacity: 0, x: 20 }}
                         transition={{ duration: 0.2 }}
-                        className="flex items-center justify-between p-3 border rounded-md bg-card hover:bg-accent/5 transition-colors"
+                        className="p-3 border rounded-md bg-card hover:bg-accent/5 transition-colors"
                       >
+                      <div className="flex items-center justify-between">
                         <div className="flex items-center gap-3">
                           <Badge variant="outline" className="font-mono">
                             :{server.port}
@@ -166,8 +227,11 @@ export const ServerDashboard: React.FC<ServerDashboardProps> = ({
                           <div>
                             <p className="font-medium">{server.service}</p>
                             <p className="text-xs text-muted-foreground">
-                              {server.processName} • http://localhost:{server.port}
+                              {server.processName} • {server.url ?? `http://localhost:${server.port}`}
                             </p>
+                            {server.localhost_hint && (
+                              <p className="text-xs text-amber-500">{server.localhost_hint}</p>
+                            )}
                           </div>
                         </div>
 
@@ -190,7 +254,38 @@ export const ServerDashboard: React.FC<ServerDashboardProps> = ({
                             <X className="h-3.5 w-3.5" weight="bold" />
                             Kill
                           </Button>
+                          <Button
+                            size="sm"
+                            variant="ghost"
+                            onClick={() => toggleDetails(server)}
+                            className="gap-1.5"
+                          >
+                            <TreeStructure className="h-3.5 w-3.5" weight="duotone" />
+                            Details
+                          </Button>
+                        </div>
+                      </div>
+                      {details?.port === server.port && (
+                        <div className="mt-3 pt-3 border-t border-border space-y-1">
+                          <p className="text-xs text-muted-foreground">
+                            Launched by: <strong>{details.ancestry.launched_by ?? 'unknown'}</strong>
+                          </p>
+                          {details.ancestry.chain.map((process, index) => (
+                            <p
+                              key={process.pid}
+                              className="text-xs font-mono truncate"
+                              style={{ paddingLeft: `${index * 12}px` }}
+                              title={process.command}
+                            >
+                              {process.pid} {process.name}
+                              {process.role && <span className="text-muted-foreground"> ({process.role})</span>}
+                            </p>
+                          ))}
+                          {details.ancestry.truncated && (
+                            <p className="text-xs text-muted-foreground">…chain truncated</p>
+                          )}
                         </div>
+                      )}
                       </motion.div>
                     ))}
                   </AnimatePresence>